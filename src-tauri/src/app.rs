@@ -0,0 +1,118 @@
+use std::{fs, io};
+
+use tauri::Manager;
+
+use crate::{
+    commands,
+    db::sessions::init_db,
+    services::{queue, reconcile, relocate, retention, settings},
+    state::AppState,
+};
+
+#[tauri::command]
+fn close_splashscreen(app: tauri::AppHandle) -> Result<(), String> {
+    if let Some(splashscreen_window) = app.get_webview_window("splashscreen") {
+        splashscreen_window
+            .close()
+            .map_err(|error| error.to_string())?;
+    }
+
+    let main_window = app
+        .get_webview_window("main")
+        .ok_or_else(|| String::from("Main window not found"))?;
+
+    main_window.show().map_err(|error| error.to_string())?;
+    main_window.set_focus().map_err(|error| error.to_string())?;
+
+    Ok(())
+}
+
+#[cfg_attr(mobile, tauri::mobile_entry_point)]
+pub fn run() {
+    #[cfg(target_os = "windows")]
+    {
+        // Safety: this runs at startup before worker threads are spawned.
+        std::env::set_var(
+            "WEBVIEW2_ADDITIONAL_BROWSER_ARGUMENTS",
+            "--disable-features=CalculateNativeWinOcclusion",
+        );
+    }
+
+    tauri::Builder::default()
+        .plugin(tauri_plugin_dialog::init())
+        .plugin(tauri_plugin_opener::init())
+        .setup(|app| {
+            let app_data_dir = app
+                .path()
+                .app_data_dir()
+                .map_err(|error| io::Error::other(error.to_string()))?;
+            fs::create_dir_all(&app_data_dir)?;
+
+            let db_path = app_data_dir.join("sessions.db");
+            let settings_path = app_data_dir.join("settings.json");
+            let documents_dir = app
+                .path()
+                .document_dir()
+                .unwrap_or_else(|_| app_data_dir.clone());
+            let db = init_db(&db_path).map_err(|error| io::Error::other(error.to_string()))?;
+            let app_settings = settings::bootstrap_settings(&settings_path, &documents_dir)
+                .map_err(|error| io::Error::other(error.to_string()))?;
+            let _ = relocate::sync_path_storage(&db, &app_settings);
+            let _ = reconcile::reconcile_sessions(&db);
+            let retention_db = db.clone();
+            std::thread::spawn(move || {
+                let _ = retention::apply_retention(&retention_db, &app_settings.retention, false);
+            });
+            let state = AppState::new(db, settings_path);
+            let _ = queue::load_queue(&state);
+            app.manage(state);
+            Ok(())
+        })
+        .invoke_handler(tauri::generate_handler![
+            commands::export::start_export,
+            commands::export::start_export_with_profile,
+            commands::export::download_scrape_image,
+            commands::export::cancel_export,
+            commands::export::pause_export,
+            commands::export::resume_export_run,
+            commands::export::is_export_paused,
+            commands::export::report_export_total,
+            commands::export::append_scraped_note,
+            commands::export::finish_scrape,
+            commands::export::fail_scrape,
+            commands::queue::get_export_queue,
+            commands::queue::enqueue_export,
+            commands::queue::remove_queued_export,
+            commands::queue::move_queued_export,
+            commands::queue::start_export_queue,
+            commands::queue::stop_export_queue,
+            commands::sessions::get_sessions,
+            commands::sessions::get_session,
+            commands::sessions::delete_session,
+            commands::sessions::get_export_statistics,
+            commands::sessions::set_session_pinned,
+            commands::sessions::update_session_metadata,
+            commands::sessions::apply_retention_policy,
+            commands::sessions::relocate_sessions,
+            commands::sessions::verify_session,
+            commands::files::read_export_file,
+            commands::files::write_export_file,
+            commands::files::list_export_files,
+            commands::files::open_in_explorer,
+            commands::converter::convert_to_json,
+            commands::diff::diff_exports,
+            commands::duplicates::find_duplicate_notes,
+            commands::search::search_notes,
+            commands::search::reindex_notes,
+            commands::settings::get_app_settings,
+            commands::settings::update_app_settings,
+            commands::settings::save_export_profile,
+            commands::settings::delete_export_profile,
+            commands::settings::check_latest_release_version,
+            commands::backup::export_app_data,
+            commands::backup::import_app_data,
+            close_splashscreen
+        ])
+        .run(tauri::generate_context!())
+        .expect("error while running tauri application");
+}
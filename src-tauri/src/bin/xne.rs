@@ -0,0 +1,437 @@
+//! Headless command-line interface over the exporter core, sharing the desktop app's data.
+
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use serde::Serialize;
+use xiaomi_note_exporter_lib::{
+    db::{connection::Database, sessions as sessions_db},
+    error::{AppError, AppResult},
+    models::{
+        AppSettings, ExportCompleteEvent, ExportErrorEvent, ExportFilterSpec, ExportProgressEvent,
+        NoteSearchFilters, ScrapedNoteInput, SessionFilters, UnparsedDatePolicy,
+        UnsupportedNotesFilter,
+    },
+    services::{
+        converter, diff,
+        export::{self, ExportEvents, ExportOptions, NoteDetailsTimestamps},
+        files,
+        micloud::{self, MiCloudClient},
+        relocate, search, settings,
+        timezone::OutputTimezone,
+    },
+    state::AppState,
+};
+
+const APP_IDENTIFIER: &str = "com.nogiszd.xiaomi-note-exporter";
+
+#[derive(Parser)]
+#[command(
+    name = "xne",
+    version,
+    about = "Export and manage Xiaomi notes from the terminal"
+)]
+struct Cli {
+    /// App data directory with sessions.db and settings.json [default: the desktop app's]
+    #[arg(long, global = true, env = "XNE_DATA_DIR")]
+    data_dir: Option<PathBuf>,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Convert a Markdown export (file or folder) to JSON
+    Convert { source: String, output: String },
+    /// List, show or delete export sessions
+    #[command(subcommand)]
+    Sessions(SessionsCommand),
+    /// Compare two exports given as paths or session ids
+    Diff {
+        left: String,
+        right: String,
+        /// Also write a Markdown or HTML summary to this path
+        #[arg(long)]
+        summary: Option<String>,
+    },
+    /// Search indexed notes
+    Search {
+        query: String,
+        #[arg(long)]
+        session: Option<String>,
+        #[arg(long)]
+        after: Option<String>,
+        #[arg(long)]
+        before: Option<String>,
+        #[arg(long)]
+        limit: Option<u32>,
+    },
+    /// Export notes from Mi Cloud, or from a previously captured JSON note dump
+    Export(ExportArgs),
+}
+
+#[derive(Subcommand)]
+enum SessionsCommand {
+    List {
+        #[arg(long, default_value_t = 1)]
+        page: u32,
+        #[arg(long, default_value_t = 50)]
+        per_page: u32,
+        #[arg(long)]
+        status: Option<String>,
+        #[arg(long)]
+        search: Option<String>,
+    },
+    Show {
+        id: String,
+    },
+    Delete {
+        id: String,
+        /// Remove the exported files as well
+        #[arg(long)]
+        delete_files: bool,
+    },
+}
+
+#[derive(Args)]
+struct ExportArgs {
+    /// JSON array of notes in the shape the scraper sends to `append_scraped_note`
+    #[arg(required_unless_present = "cookie")]
+    dump: Option<PathBuf>,
+    /// Mi Cloud `Cookie` header of a signed-in browser; fetches notes from the API
+    #[arg(
+        long,
+        env = "XNE_COOKIE",
+        hide_env_values = true,
+        conflicts_with = "dump"
+    )]
+    cookie: Option<String>,
+    /// Target folder [default: the default export directory from settings]
+    #[arg(long)]
+    output_dir: Option<PathBuf>,
+    /// Saved export profile (id or name) supplying every option but the note source,
+    /// `--output-dir` and `--dry-run`
+    #[arg(long)]
+    profile: Option<String>,
+    #[arg(long, default_value = "us.i.mi.com")]
+    domain: String,
+    /// Write one Markdown file per note
+    #[arg(long)]
+    split: bool,
+    #[arg(long)]
+    name_by_title: bool,
+    #[arg(long, default_value = export::DEFAULT_TIMESTAMP_FORMAT)]
+    timestamp_format: String,
+    #[arg(long, default_value = export::DEFAULT_CREATED_DATE_FORMAT)]
+    created_date_format: String,
+    #[arg(long)]
+    no_images: bool,
+    #[arg(long)]
+    skip_duplicates: bool,
+    #[arg(long, value_enum, default_value_t = DatePolicy::UseNow)]
+    unparsed_dates: DatePolicy,
+    /// Only export notes in this Mi Cloud folder (name or id); repeatable
+    #[arg(long)]
+    folder: Vec<String>,
+    /// Only export notes created on or after this date (YYYY-MM-DD or RFC 3339)
+    #[arg(long)]
+    created_after: Option<String>,
+    /// Only export notes created before this date (YYYY-MM-DD or RFC 3339)
+    #[arg(long)]
+    created_before: Option<String>,
+    /// Only export notes whose title matches this regular expression
+    #[arg(long)]
+    title_regex: Option<String>,
+    /// Only export notes whose body matches this regular expression
+    #[arg(long)]
+    body_regex: Option<String>,
+    /// Skip notes whose body is shorter than this many characters
+    #[arg(long)]
+    min_length: Option<u32>,
+    #[arg(long, value_enum, default_value_t = UnsupportedNotes::Include)]
+    unsupported: UnsupportedNotes,
+    /// Print the planned files, image volume and flagged notes without writing anything
+    #[arg(long)]
+    dry_run: bool,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum DatePolicy {
+    Fail,
+    UseNow,
+    UseEpoch,
+    LeaveBlank,
+}
+
+impl From<DatePolicy> for UnparsedDatePolicy {
+    fn from(value: DatePolicy) -> Self {
+        match value {
+            DatePolicy::Fail => UnparsedDatePolicy::Fail,
+            DatePolicy::UseNow => UnparsedDatePolicy::UseNow,
+            DatePolicy::UseEpoch => UnparsedDatePolicy::UseEpoch,
+            DatePolicy::LeaveBlank => UnparsedDatePolicy::LeaveBlank,
+        }
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum UnsupportedNotes {
+    Include,
+    Exclude,
+    Only,
+}
+
+impl From<UnsupportedNotes> for UnsupportedNotesFilter {
+    fn from(value: UnsupportedNotes) -> Self {
+        match value {
+            UnsupportedNotes::Include => UnsupportedNotesFilter::Include,
+            UnsupportedNotes::Exclude => UnsupportedNotesFilter::Exclude,
+            UnsupportedNotes::Only => UnsupportedNotesFilter::Only,
+        }
+    }
+}
+
+/// Progress goes to stderr, the completion summary to stdout.
+struct CliEvents;
+
+impl ExportEvents for CliEvents {
+    fn progress(&self, event: ExportProgressEvent) {
+        eprintln!("[{}/{}] {}", event.current, event.total, event.log_line);
+    }
+
+    fn complete(&self, event: ExportCompleteEvent) {
+        let _ = print_json(&event);
+    }
+
+    fn error(&self, _event: ExportErrorEvent) {
+        // The failing call's error is returned and printed by `main`.
+    }
+}
+
+struct Context {
+    db: Database,
+    settings_path: PathBuf,
+    settings: AppSettings,
+}
+
+impl Context {
+    fn open(data_dir: &Path) -> AppResult<Self> {
+        fs::create_dir_all(data_dir)?;
+        let db = sessions_db::init_db(&data_dir.join("sessions.db"))?;
+        let documents_dir = home_dir()
+            .map(|home| home.join("Documents"))
+            .unwrap_or_else(|| data_dir.to_path_buf());
+        let settings_path = data_dir.join("settings.json");
+        let settings = settings::bootstrap_settings(&settings_path, &documents_dir)?;
+        relocate::sync_path_storage(&db, &settings)?;
+        Ok(Self {
+            db,
+            settings_path,
+            settings,
+        })
+    }
+
+    fn timezone(&self) -> OutputTimezone {
+        OutputTimezone::from_settings(&self.settings)
+    }
+}
+
+fn home_dir() -> Option<PathBuf> {
+    env::var_os("HOME")
+        .or_else(|| env::var_os("USERPROFILE"))
+        .map(PathBuf::from)
+}
+
+/// Mirrors Tauri's `app_data_dir` so the CLI and the desktop app share history.
+fn default_data_dir() -> PathBuf {
+    let base = if cfg!(target_os = "windows") {
+        env::var_os("APPDATA").map(PathBuf::from)
+    } else if cfg!(target_os = "macos") {
+        home_dir().map(|home| home.join("Library/Application Support"))
+    } else {
+        env::var_os("XDG_DATA_HOME")
+            .map(PathBuf::from)
+            .or_else(|| home_dir().map(|home| home.join(".local/share")))
+    };
+    base.unwrap_or_else(env::temp_dir).join(APP_IDENTIFIER)
+}
+
+fn print_json<T: Serialize>(value: &T) -> AppResult<()> {
+    println!("{}", serde_json::to_string_pretty(value)?);
+    Ok(())
+}
+
+fn run_sessions(context: &Context, command: SessionsCommand) -> AppResult<()> {
+    match command {
+        SessionsCommand::List {
+            page,
+            per_page,
+            status,
+            search,
+        } => {
+            let filters = SessionFilters {
+                status,
+                search,
+                ..SessionFilters::default()
+            };
+            print_json(&sessions_db::fetch_sessions(
+                &context.db,
+                page,
+                per_page,
+                &filters,
+            )?)
+        }
+        SessionsCommand::Show { id } => {
+            let session = sessions_db::fetch_session_by_id(&context.db, &id)?
+                .ok_or_else(|| AppError::Message(format!("Session not found: {id}")))?;
+            print_json(&session)
+        }
+        SessionsCommand::Delete { id, delete_files } => {
+            let session = sessions_db::delete_session_by_id(&context.db, &id)?
+                .ok_or_else(|| AppError::Message(format!("Session not found: {id}")))?;
+            if delete_files {
+                files::remove_export_artifacts(
+                    Path::new(&session.output_path),
+                    session.split_mode,
+                    session.images_dir_name.as_deref(),
+                )?;
+            }
+            print_json(&session)
+        }
+    }
+}
+
+fn cli_export_options(context: &Context, args: &ExportArgs) -> ExportOptions {
+    ExportOptions {
+        domain: args.domain.clone(),
+        output_dir: args
+            .output_dir
+            .clone()
+            .unwrap_or_else(|| PathBuf::from(&context.settings.default_export_dir)),
+        split: args.split,
+        name_by_title: args.name_by_title,
+        timestamp_format: args.timestamp_format.clone(),
+        created_date_format: args.created_date_format.clone(),
+        export_images: !args.no_images,
+        skip_duplicates: args.skip_duplicates,
+        unparsed_date_policy: args.unparsed_dates.into(),
+        notes_locale: context.settings.notes_locale.clone(),
+        output_timezone: context.timezone(),
+        filter: ExportFilterSpec {
+            created_after: args.created_after.clone(),
+            created_before: args.created_before.clone(),
+            title_regex: args.title_regex.clone(),
+            body_regex: args.body_regex.clone(),
+            unsupported: args.unsupported.into(),
+            min_length: args.min_length,
+            folders: args.folder.clone(),
+        },
+        dry_run: args.dry_run,
+    }
+}
+
+fn run_export(context: &Context, args: ExportArgs) -> AppResult<()> {
+    let notes: Vec<ScrapedNoteInput> = match &args.dump {
+        Some(dump) => serde_json::from_str(&fs::read_to_string(dump)?)?,
+        None => Vec::new(),
+    };
+    let options = match &args.profile {
+        Some(profile) => {
+            let profile = settings::find_export_profile(&context.settings, profile)?;
+            let mut options = ExportOptions::from_job(&profile.settings, &context.settings);
+            if let Some(output_dir) = &args.output_dir {
+                options.output_dir = output_dir.clone();
+            }
+            options.dry_run |= args.dry_run;
+            options
+        }
+        None => cli_export_options(context, &args),
+    };
+    let client = args
+        .cookie
+        .as_deref()
+        .map(|cookie| MiCloudClient::for_domain(&options.domain, cookie))
+        .transpose()?;
+
+    let state = AppState::new(context.db.clone(), context.settings_path.clone());
+    let session_id = export::start_export(&state, &options)?;
+    if let Some(client) = client {
+        return micloud::run_export(&state, &session_id, &client, &CliEvents);
+    }
+
+    export::report_export_total(&state, &session_id, notes.len() as u32, &CliEvents)?;
+    for note in &notes {
+        if let Err(error) = export::append_scraped_note(
+            &state,
+            &session_id,
+            note,
+            NoteDetailsTimestamps::default(),
+            &CliEvents,
+        ) {
+            let _ = export::fail_scrape(&state, &session_id, &error.to_string(), &CliEvents);
+            return Err(error);
+        }
+    }
+
+    export::finish_scrape(&state, &session_id, &CliEvents)?;
+    Ok(())
+}
+
+fn run(cli: Cli) -> AppResult<()> {
+    let data_dir = cli.data_dir.unwrap_or_else(default_data_dir);
+    let context = Context::open(&data_dir)?;
+
+    match cli.command {
+        Command::Convert { source, output } => {
+            let written = converter::convert_to_json(&source, &output, &context.timezone())?;
+            print_json(&written)
+        }
+        Command::Sessions(command) => run_sessions(&context, command),
+        Command::Diff {
+            left,
+            right,
+            summary,
+        } => print_json(&diff::diff_exports(
+            &context.db,
+            &left,
+            &right,
+            summary.as_deref(),
+            &context.timezone(),
+        )?),
+        Command::Search {
+            query,
+            session,
+            after,
+            before,
+            limit,
+        } => {
+            let filters = NoteSearchFilters {
+                session_id: session,
+                created_after: after,
+                created_before: before,
+                limit,
+            };
+            print_json(&search::search_notes(
+                &context.db,
+                &query,
+                &filters,
+                &context.timezone(),
+            )?)
+        }
+        Command::Export(args) => run_export(&context, args),
+    }
+}
+
+fn main() -> ExitCode {
+    match run(Cli::parse()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("error: {error}");
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,44 @@
+use std::path::Path;
+
+use tauri::State;
+
+use crate::{
+    error::{AppError, CommandResult},
+    models::{AppDataImportReport, AppDataManifest},
+    services::backup,
+    state::AppState,
+};
+
+#[tauri::command]
+pub fn export_app_data(
+    state: State<'_, AppState>,
+    path: String,
+    include_search_index: Option<bool>,
+) -> CommandResult<AppDataManifest> {
+    backup::export_app_data(
+        &state.db,
+        &state.settings_path,
+        Path::new(&path),
+        include_search_index.unwrap_or(true),
+    )
+    .map_err(Into::into)
+}
+
+#[tauri::command]
+pub fn import_app_data(
+    state: State<'_, AppState>,
+    path: String,
+    export_root: Option<String>,
+) -> CommandResult<AppDataImportReport> {
+    {
+        let guard = state
+            .active_export
+            .lock()
+            .map_err(|_| "Internal state lock poisoned.".to_string())?;
+        if guard.is_some() {
+            return Err(AppError::ExportRunning.to_string());
+        }
+    }
+
+    backup::import_app_data(&state, Path::new(&path), export_root.as_deref()).map_err(Into::into)
+}
@@ -1,6 +1,17 @@
-use crate::{error::CommandResult, services::converter};
+use tauri::State;
+
+use crate::{
+    error::CommandResult,
+    services::{converter, settings},
+    state::AppState,
+};
 
 #[tauri::command]
-pub fn convert_to_json(source_path: String, output_path: String) -> CommandResult<String> {
-    converter::convert_to_json(&source_path, &output_path).map_err(Into::into)
+pub fn convert_to_json(
+    state: State<'_, AppState>,
+    source_path: String,
+    output_path: String,
+) -> CommandResult<String> {
+    let timezone = settings::load_output_timezone(&state.settings_path)?;
+    converter::convert_to_json(&source_path, &output_path, &timezone).map_err(Into::into)
 }
@@ -0,0 +1,20 @@
+use tauri::State;
+
+use crate::{
+    error::CommandResult,
+    models::ExportDiffReport,
+    services::{diff, settings},
+    state::AppState,
+};
+
+#[tauri::command]
+pub fn diff_exports(
+    state: State<'_, AppState>,
+    left: String,
+    right: String,
+    summary_path: Option<String>,
+) -> CommandResult<ExportDiffReport> {
+    let timezone = settings::load_output_timezone(&state.settings_path)?;
+    diff::diff_exports(&state.db, &left, &right, summary_path.as_deref(), &timezone)
+        .map_err(Into::into)
+}
@@ -0,0 +1,18 @@
+use tauri::State;
+
+use crate::{
+    error::CommandResult,
+    models::DuplicateReport,
+    services::{duplicates, settings},
+    state::AppState,
+};
+
+#[tauri::command]
+pub fn find_duplicate_notes(
+    state: State<'_, AppState>,
+    source: String,
+    threshold: Option<f32>,
+) -> CommandResult<DuplicateReport> {
+    let timezone = settings::load_output_timezone(&state.settings_path)?;
+    duplicates::find_duplicate_notes(&state.db, &source, threshold, &timezone).map_err(Into::into)
+}
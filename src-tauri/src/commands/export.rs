@@ -1,119 +1,22 @@
-use std::path::PathBuf;
-
-use base64::Engine as _;
-use sha2::{Digest, Sha256};
-use chrono::{Local, TimeZone, Utc};
-use reqwest::{blocking::Client, header};
-use serde_json::Value;
-use tauri::{AppHandle, Emitter, Manager, State};
-use url::Url;
-use uuid::Uuid;
+use tauri::{AppHandle, Manager, State};
 
 use crate::{
-    db::sessions as sessions_db,
-    error::{AppError, CommandResult},
+    error::CommandResult,
     models::{
-        ExportCompleteEvent, ExportErrorEvent, ExportProgressEvent, ScrapedNoteInput, Session,
+        ExportFilterSpec, ExportJobSettings, ExportSource, ScrapedNoteInput, UnparsedDatePolicy,
+    },
+    services::{
+        export::{self, TauriEvents},
+        micloud::{self, MiCloudClient},
+        queue, scraper, settings,
     },
-    services::{date_parser, files, markdown, scraper, settings},
     state::{ActiveExportState, AppState},
 };
 
-fn now_utc() -> String {
-    Utc::now().to_rfc3339()
-}
-
-fn lock_error() -> AppError {
-    AppError::Message("Internal state lock poisoned.".to_string())
-}
-
-fn take_active_export(state: &AppState) -> Result<ActiveExportState, AppError> {
-    let mut guard = state.active_export.lock().map_err(|_| lock_error())?;
-    guard.take().ok_or(AppError::ExportNotRunning)
-}
-
-fn emit_progress(app: &AppHandle, export: &ActiveExportState, last_title: &str, log_line: &str) {
-    let total = export.total_notes.max(export.notes_count.max(1));
-    let _ = app.emit(
-        "export:progress",
-        ExportProgressEvent {
-            session_id: export.session_id.clone(),
-            current: export.notes_count,
-            total,
-            last_title: last_title.to_string(),
-            notes_count: export.notes_count,
-            images_count: export.images_count,
-            log_line: log_line.to_string(),
-        },
-    );
-}
-
-fn is_allowed_image_host(host: &str) -> bool {
-    host == "i.mi.com"
-        || host.ends_with(".i.mi.com")
-        || host.ends_with(".xiaomi.net")
-        || host.ends_with(".mi.com")
-}
-
-fn parse_unix_timestamp_to_local(timestamp: i64) -> Option<chrono::DateTime<Local>> {
-    if timestamp.abs() >= 1_000_000_000_000 {
-        let seconds = timestamp.div_euclid(1_000);
-        let millis = timestamp.rem_euclid(1_000) as u32;
-        return Utc
-            .timestamp_opt(seconds, millis * 1_000_000)
-            .single()
-            .map(|dt| dt.with_timezone(&Local));
-    }
-
-    Utc.timestamp_opt(timestamp, 0)
-        .single()
-        .map(|dt| dt.with_timezone(&Local))
-}
-
-fn fetch_created_at_from_note_details(
-    note_id: Option<&str>,
-    cookie_header: Option<&str>,
-) -> Option<chrono::DateTime<Local>> {
-    let normalized_note_id = note_id?.trim();
-    if normalized_note_id.is_empty() {
-        return None;
-    }
-
-    let url = format!("https://us.i.mi.com/note/note/{normalized_note_id}");
-    let client = Client::builder()
-        .timeout(std::time::Duration::from_secs(10))
-        .user_agent(
-            "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 \
-             (KHTML, like Gecko) Chrome/124.0.0.0 Safari/537.36",
-        )
-        .build()
-        .ok()?;
-
-    let mut request = client.get(url);
-    if let Some(cookie) = cookie_header
-        .map(str::trim)
-        .filter(|value| !value.is_empty())
-    {
-        request = request.header(header::COOKIE, cookie);
-    }
-
-    let response = request.send().ok()?;
-    if !response.status().is_success() {
-        return None;
+fn close_auth_window(app: &AppHandle, export: &ActiveExportState) {
+    if let Some(window) = app.get_webview_window(&export.auth_window_label) {
+        let _ = window.close();
     }
-
-    let payload: Value = response.json().ok()?;
-    let created_timestamp = payload
-        .get("data")
-        .and_then(|data| data.get("entry"))
-        .and_then(|entry| entry.get("createdDate"))
-        .and_then(|value| {
-            value
-                .as_i64()
-                .or_else(|| value.as_str().and_then(|raw| raw.parse::<i64>().ok()))
-        })?;
-
-    parse_unix_timestamp_to_local(created_timestamp)
 }
 
 #[tauri::command]
@@ -127,158 +30,27 @@ pub fn start_export(
     timestamp_format: String,
     created_date_format: String,
     export_images: bool,
+    skip_duplicates: Option<bool>,
+    unparsed_date_policy: Option<UnparsedDatePolicy>,
+    source: Option<ExportSource>,
+    filter: Option<ExportFilterSpec>,
+    dry_run: Option<bool>,
 ) -> CommandResult<String> {
-    {
-        let guard = state
-            .active_export
-            .lock()
-            .map_err(|_| lock_error().to_string())?;
-        if guard.is_some() {
-            return Err(AppError::ExportRunning.to_string());
-        }
-    }
-
-    let resolved_output_dir = if output_dir.trim().is_empty() {
-        let app_settings =
-            settings::load_settings(&state.settings_path).map_err(|e| e.to_string())?;
-        app_settings.default_export_dir
-    } else {
-        output_dir
-    };
-
-    let session_id = Uuid::new_v4().to_string();
-    let stamp = Local::now().format("%d-%m-%Y_%H-%M-%S").to_string();
-    let output_dir = PathBuf::from(resolved_output_dir);
-
-    let output_root = if split {
-        output_dir.join(format!("exported_notes_{stamp}"))
-    } else {
-        output_dir.join(format!("exported_notes_{stamp}.md"))
-    };
-
-    let images_dir = if split {
-        output_root.join("images")
-    } else {
-        output_dir.join(format!("images_{stamp}"))
-    };
-    let images_dir_name = images_dir
-        .file_name()
-        .and_then(|name| name.to_str())
-        .map(ToString::to_string);
-
-    if split {
-        std::fs::create_dir_all(&output_root).map_err(|e| e.to_string())?;
-    } else {
-        files::ensure_parent(&output_root).map_err(|e| e.to_string())?;
-    }
-
-    if export_images {
-        std::fs::create_dir_all(&images_dir).map_err(|e| e.to_string())?;
-    }
-
-    let session = Session {
-        id: session_id.clone(),
-        domain: domain.clone(),
-        started_at: now_utc(),
-        completed_at: None,
-        status: "running".to_string(),
-        notes_count: 0,
-        images_count: 0,
-        split_mode: split,
-        name_by_title,
-        timestamp_format: timestamp_format.clone(),
-        images_enabled: export_images,
-        output_path: output_root.to_string_lossy().to_string(),
-        images_dir_name,
-        error_message: None,
-    };
-
-    sessions_db::insert_session(&state.db_path, &session).map_err(|e| e.to_string())?;
-
-    let active_export = ActiveExportState {
-        session_id: session_id.clone(),
+    let job = ExportJobSettings {
+        domain,
+        output_dir,
         split,
         name_by_title,
-        chrono_timestamp_format: markdown::dotnet_to_chrono_format(&timestamp_format),
-        chrono_created_date_format: markdown::dotnet_to_chrono_created_date_format(
-            &created_date_format,
-        ),
+        timestamp_format,
+        created_date_format,
         export_images,
-        output_root: output_root.clone(),
-        images_dir,
-        total_notes: 0,
-        notes_count: 0,
-        images_count: 0,
-        started_at: None,
-        auth_window_label: scraper::auth_window_label(&session_id),
+        skip_duplicates: skip_duplicates.unwrap_or(false),
+        unparsed_date_policy: unparsed_date_policy.unwrap_or_default(),
+        source: source.unwrap_or_default(),
+        filter: filter.unwrap_or_default(),
+        dry_run: dry_run.unwrap_or(false),
     };
-
-    {
-        let mut guard = state
-            .active_export
-            .lock()
-            .map_err(|_| lock_error().to_string())?;
-        *guard = Some(active_export);
-    }
-
-    let app_handle = app.clone();
-    let state_handle = state.inner().clone();
-    let session_id_for_window = session_id.clone();
-    let domain_for_window = domain.clone();
-    tauri::async_runtime::spawn(async move {
-        match scraper::create_auth_window(
-            &app_handle,
-            &session_id_for_window,
-            &domain_for_window,
-            export_images,
-        ) {
-            Ok(_) => {
-                let _ = app_handle.emit(
-                    "export:progress",
-                    ExportProgressEvent {
-                        session_id: session_id_for_window.clone(),
-                        current: 0,
-                        total: 1,
-                        last_title: String::new(),
-                        notes_count: 0,
-                        images_count: 0,
-                        log_line: format!(
-                            "Opened shell window for domain {domain_for_window}. Complete login (or wait till session is loaded) to start export."
-                        ),
-                    },
-                );
-            }
-            Err(error) => {
-                let _ = sessions_db::set_session_outcome(
-                    &state_handle.db_path,
-                    &session_id_for_window,
-                    "error",
-                    &now_utc(),
-                    0,
-                    0,
-                    Some(&error.to_string()),
-                );
-                if let Ok(mut guard) = state_handle.active_export.lock() {
-                    let should_clear = guard
-                        .as_ref()
-                        .map(|active| active.session_id == session_id_for_window)
-                        .unwrap_or(false);
-                    if should_clear {
-                        *guard = None;
-                    }
-                }
-                let _ = app_handle.emit(
-                    "export:error",
-                    ExportErrorEvent {
-                        session_id: session_id_for_window,
-                        message: format!("Failed to open shell window: {error}"),
-                    },
-                );
-            }
-        }
-    });
-
-    Ok(session_id)
+    scraper::launch_export(&app, &state, &job).map_err(Into::into)
 }
 
 #[tauri::command]
@@ -286,90 +58,47 @@ pub fn download_scrape_image(
     source_url: String,
     cookie_header: Option<String>,
 ) -> CommandResult<String> {
-    let parsed = Url::parse(&source_url).map_err(|e| format!("Invalid image URL: {e}"))?;
-    if parsed.scheme() != "https" {
-        return Err("Only HTTPS image URLs are allowed.".to_string());
-    }
-
-    let host = parsed
-        .host_str()
-        .ok_or_else(|| "Image URL does not include a host.".to_string())?;
-    if !is_allowed_image_host(host) {
-        return Err(format!("Unsupported image host: {host}"));
-    }
-
-    let client = Client::builder()
-        .timeout(std::time::Duration::from_secs(20))
-        .user_agent(
-            "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 \
-             (KHTML, like Gecko) Chrome/124.0.0.0 Safari/537.36",
-        )
-        .build()
-        .map_err(|e| format!("Failed to initialize HTTP client: {e}"))?;
-
-    let mut request = client.get(parsed.as_str());
-    if let Some(cookie) = cookie_header
-        .as_deref()
-        .map(str::trim)
-        .filter(|value| !value.is_empty())
-    {
-        request = request.header(header::COOKIE, cookie);
-    }
-
-    let response = request
-        .send()
-        .map_err(|e| format!("Failed to download image: {e}"))?;
-    if !response.status().is_success() {
-        return Err(format!(
-            "Failed to download image: HTTP {}",
-            response.status()
-        ));
-    }
-
-    if let Some(final_host) = response.url().host_str() {
-        if !is_allowed_image_host(final_host) {
-            return Err(format!("Image redirect host is not allowed: {final_host}"));
-        }
-    }
+    let client = MiCloudClient::new(
+        micloud::default_base_url()?,
+        cookie_header.as_deref().unwrap_or_default(),
+    )?;
+    micloud::download_scrape_image(&client, &source_url).map_err(Into::into)
+}
 
-    let bytes = response
-        .bytes()
-        .map_err(|e| format!("Failed to read image bytes: {e}"))?;
-    Ok(base64::engine::general_purpose::STANDARD.encode(bytes))
+#[tauri::command]
+pub fn start_export_with_profile(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    profile_id: String,
+) -> CommandResult<String> {
+    let app_settings = settings::load_settings(&state.settings_path)?;
+    let profile = settings::find_export_profile(&app_settings, &profile_id)?;
+    scraper::launch_export(&app, &state, &profile.settings).map_err(Into::into)
 }
 
 #[tauri::command]
 pub fn cancel_export(app: AppHandle, state: State<'_, AppState>) -> CommandResult<()> {
-    let export = match take_active_export(&state) {
-        Ok(export) => export,
-        Err(AppError::ExportNotRunning) => return Ok(()),
-        Err(error) => return Err(error.to_string()),
-    };
-
-    sessions_db::set_session_outcome(
-        &state.db_path,
-        &export.session_id,
-        "cancelled",
-        &now_utc(),
-        export.notes_count,
-        export.images_count,
-        None,
-    )
-    .map_err(|e| e.to_string())?;
-
-    if let Some(window) = app.get_webview_window(&export.auth_window_label) {
-        let _ = window.close();
+    let events = TauriEvents(app.clone());
+    queue::set_queue_running(&state, false)?;
+    if let Some(export) = export::cancel_export(&state, None, None, &events)? {
+        close_auth_window(&app, &export);
     }
+    Ok(())
+}
 
-    let _ = app.emit(
-        "export:error",
-        ExportErrorEvent {
-            session_id: export.session_id,
-            message: "Export cancelled by user.".to_string(),
-        },
-    );
+#[tauri::command]
+pub fn pause_export(app: AppHandle, state: State<'_, AppState>) -> CommandResult<()> {
+    export::pause_export(&state, &TauriEvents(app)).map_err(Into::into)
+}
 
-    Ok(())
+#[tauri::command]
+pub fn resume_export_run(app: AppHandle, state: State<'_, AppState>) -> CommandResult<()> {
+    export::resume_export(&state, &TauriEvents(app)).map_err(Into::into)
+}
+
+#[tauri::command]
+pub fn is_export_paused(state: State<'_, AppState>, session_id: String) -> CommandResult<bool> {
+    export::is_export_paused(&state, &session_id).map_err(Into::into)
 }
 
 #[tauri::command]
@@ -379,28 +108,7 @@ pub fn report_export_total(
     session_id: String,
     total: u32,
 ) -> CommandResult<()> {
-    let mut guard = state
-        .active_export
-        .lock()
-        .map_err(|_| lock_error().to_string())?;
-    let export = guard
-        .as_mut()
-        .ok_or_else(|| AppError::ExportNotRunning.to_string())?;
-    if export.session_id != session_id {
-        return Err(AppError::SessionMismatch.to_string());
-    }
-
-    export.total_notes = total.max(1);
-    if export.started_at.is_none() {
-        export.started_at = Some(std::time::Instant::now());
-    }
-    emit_progress(
-        &app,
-        export,
-        "",
-        &format!("Discovered {} notes.", export.total_notes),
-    );
-    Ok(())
+    export::report_export_total(&state, &session_id, total, &TauriEvents(app)).map_err(Into::into)
 }
 
 #[tauri::command]
@@ -410,127 +118,24 @@ pub fn append_scraped_note(
     session_id: String,
     note: ScrapedNoteInput,
 ) -> CommandResult<()> {
-    let mut guard = state
-        .active_export
-        .lock()
-        .map_err(|_| lock_error().to_string())?;
-    let export = guard
-        .as_mut()
-        .ok_or_else(|| AppError::ExportNotRunning.to_string())?;
-    if export.session_id != session_id {
-        return Err(AppError::SessionMismatch.to_string());
-    }
-
-    let created_at =
-        fetch_created_at_from_note_details(note.note_id.as_deref(), note.cookie_header.as_deref())
-            .unwrap_or_else(|| date_parser::parse_created_date(&note.created_string));
-    let note_index = export.notes_count + 1;
-
-    let mut image_links = Vec::new();
-    let mut skipped_images: u32 = 0;
-    if export.export_images {
-        let images_dir_name = export
-            .images_dir
-            .file_name()
-            .and_then(|name| name.to_str())
-            .unwrap_or("images")
-            .to_string();
-
-        let ts_hash = {
-            let mut hasher = Sha256::new();
-            hasher.update(created_at.to_rfc3339().as_bytes());
-            let result = hasher.finalize();
-            result.iter().take(8).map(|b| format!("{b:02x}")).collect::<String>()
-        };
-
-        for (index, image) in note.images.iter().enumerate() {
-            if image.data_base64.trim().is_empty() {
-                continue;
-            }
-
-            let mut image_name = format!("note_img_{}_{}_{}.png", note_index, index + 1, ts_hash);
-            if !image_name.to_ascii_lowercase().ends_with(".png") {
-                image_name.push_str(".png");
-            }
-
-            let image_path = export.images_dir.join(&image_name);
-            if files::save_base64_image(&image_path, &image.data_base64).is_err() {
-                skipped_images += 1;
-                continue;
-            }
-            export.images_count += 1;
-
-            let relative_path = if export.split {
-                format!("images/{image_name}")
-            } else {
-                format!("{images_dir_name}/{image_name}")
-            };
-            image_links.push(format!("![image {}](<{relative_path}>)", index + 1));
-        }
-    }
-
-    let markdown_note = crate::services::markdown::build_note_markdown(
-        &note.title,
-        &note.content,
-        if note.content_html.trim().is_empty() {
-            None
-        } else {
-            Some(note.content_html.as_str())
-        },
-        &image_links,
-        created_at,
-        &export.chrono_created_date_format,
-        note.unsupported,
-    );
-
-    if export.split {
-        let trimmed_title = note.title.trim();
-        let mut file_name = if export.name_by_title && !trimmed_title.is_empty() {
-            markdown::sanitize_filename(&format!("{}_{:04}.md", trimmed_title, note_index))
-        } else {
-            markdown::sanitize_filename(&format!(
-                "note_{}_{:04}.md",
-                created_at.format(&export.chrono_timestamp_format),
-                note_index,
-            ))
-        };
-        if !file_name.to_ascii_lowercase().ends_with(".md") {
-            file_name.push_str(".md");
-        }
-
-        let file_path = export.output_root.join(file_name);
-        std::fs::write(file_path, markdown_note).map_err(|e| e.to_string())?;
-    } else {
-        files::append_text(&export.output_root, &markdown_note).map_err(|e| e.to_string())?;
-    }
-
-    export.notes_count += 1;
-    sessions_db::update_session_progress(
-        &state.db_path,
-        &export.session_id,
-        export.notes_count,
-        export.images_count,
+    let details_timestamps = micloud::default_base_url()
+        .ok()
+        .and_then(|base_url| {
+            micloud::fetch_note_timestamps(
+                base_url,
+                note.note_id.as_deref(),
+                note.cookie_header.as_deref(),
+            )
+        })
+        .unwrap_or_default();
+    export::append_scraped_note(
+        &state,
+        &session_id,
+        &note,
+        details_timestamps,
+        &TauriEvents(app),
     )
-    .map_err(|e| e.to_string())?;
-
-    let mut log_line = if note.unsupported {
-        format!("Processed note {} (unsupported type).", export.notes_count)
-    } else {
-        let display_title = if note.title.trim().is_empty() {
-            "No title"
-        } else {
-            note.title.trim()
-        };
-        format!("Processed note {}: {}", export.notes_count, display_title)
-    };
-    if skipped_images > 0 {
-        log_line.push_str(&format!(
-            " ({skipped_images} image(s) skipped due to errors)"
-        ));
-    }
-    emit_progress(&app, export, &note.title, &log_line);
-
-    Ok(())
+    .map_err(Into::into)
 }
 
 #[tauri::command]
@@ -539,49 +144,9 @@ pub fn finish_scrape(
     state: State<'_, AppState>,
     session_id: String,
 ) -> CommandResult<()> {
-    let export = {
-        let mut guard = state
-            .active_export
-            .lock()
-            .map_err(|_| lock_error().to_string())?;
-        let taken = guard
-            .take()
-            .ok_or_else(|| AppError::ExportNotRunning.to_string())?;
-        if taken.session_id != session_id {
-            *guard = Some(taken);
-            return Err(AppError::SessionMismatch.to_string());
-        }
-        taken
-    };
-
-    sessions_db::set_session_outcome(
-        &state.db_path,
-        &export.session_id,
-        "completed",
-        &now_utc(),
-        export.notes_count,
-        export.images_count,
-        None,
-    )
-    .map_err(|e| e.to_string())?;
-
-    if let Some(window) = app.get_webview_window(&export.auth_window_label) {
-        let _ = window.close();
-    }
-
-    let _ = app.emit(
-        "export:complete",
-        ExportCompleteEvent {
-            session_id: export.session_id,
-            total: export.notes_count,
-            elapsed_ms: export
-                .started_at
-                .map(|started_at| started_at.elapsed().as_millis() as u64)
-                .unwrap_or(0),
-            output_path: export.output_root.to_string_lossy().to_string(),
-        },
-    );
-
+    let export = export::finish_scrape(&state, &session_id, &TauriEvents(app.clone()))?;
+    close_auth_window(&app, &export);
+    queue::advance_queue(&app);
     Ok(())
 }
 
@@ -592,43 +157,8 @@ pub fn fail_scrape(
     session_id: String,
     message: String,
 ) -> CommandResult<()> {
-    let export = {
-        let mut guard = state
-            .active_export
-            .lock()
-            .map_err(|_| lock_error().to_string())?;
-        let taken = guard
-            .take()
-            .ok_or_else(|| AppError::ExportNotRunning.to_string())?;
-        if taken.session_id != session_id {
-            *guard = Some(taken);
-            return Err(AppError::SessionMismatch.to_string());
-        }
-        taken
-    };
-
-    sessions_db::set_session_outcome(
-        &state.db_path,
-        &export.session_id,
-        "error",
-        &now_utc(),
-        export.notes_count,
-        export.images_count,
-        Some(&message),
-    )
-    .map_err(|e| e.to_string())?;
-
-    let _ = app.emit(
-        "export:error",
-        ExportErrorEvent {
-            session_id: export.session_id,
-            message,
-        },
-    );
-
-    if let Some(window) = app.get_webview_window(&export.auth_window_label) {
-        let _ = window.close();
-    }
-
+    let export = export::fail_scrape(&state, &session_id, &message, &TauriEvents(app.clone()))?;
+    close_auth_window(&app, &export);
+    queue::advance_queue(&app);
     Ok(())
 }
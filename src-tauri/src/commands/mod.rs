@@ -1,5 +1,10 @@
+pub mod backup;
 pub mod converter;
+pub mod diff;
+pub mod duplicates;
 pub mod export;
 pub mod files;
+pub mod queue;
+pub mod search;
 pub mod sessions;
 pub mod settings;
@@ -0,0 +1,55 @@
+use tauri::{AppHandle, State};
+
+use crate::{
+    error::CommandResult,
+    models::{ExportJobSettings, ExportQueueStatus, QueuedExport},
+    services::queue,
+    state::AppState,
+};
+
+#[tauri::command]
+pub fn get_export_queue(state: State<'_, AppState>) -> CommandResult<ExportQueueStatus> {
+    queue::queue_status(&state).map_err(Into::into)
+}
+
+#[tauri::command]
+pub fn enqueue_export(
+    state: State<'_, AppState>,
+    settings: ExportJobSettings,
+) -> CommandResult<QueuedExport> {
+    queue::enqueue_export(&state, settings).map_err(Into::into)
+}
+
+#[tauri::command]
+pub fn remove_queued_export(
+    state: State<'_, AppState>,
+    job_id: String,
+) -> CommandResult<ExportQueueStatus> {
+    queue::remove_queued_export(&state, &job_id).map_err(Into::into)
+}
+
+#[tauri::command]
+pub fn move_queued_export(
+    state: State<'_, AppState>,
+    job_id: String,
+    position: usize,
+) -> CommandResult<ExportQueueStatus> {
+    queue::move_queued_export(&state, &job_id, position).map_err(Into::into)
+}
+
+/// Runs the queued jobs one after another, starting now unless an export is active.
+#[tauri::command]
+pub fn start_export_queue(
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> CommandResult<ExportQueueStatus> {
+    queue::set_queue_running(&state, true)?;
+    queue::advance_queue(&app);
+    queue::queue_status(&state).map_err(Into::into)
+}
+
+/// Lets the active export finish without starting the next queued job.
+#[tauri::command]
+pub fn stop_export_queue(state: State<'_, AppState>) -> CommandResult<ExportQueueStatus> {
+    queue::set_queue_running(&state, false).map_err(Into::into)
+}
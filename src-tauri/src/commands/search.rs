@@ -0,0 +1,25 @@
+use tauri::State;
+
+use crate::{
+    error::CommandResult,
+    models::{NoteSearchFilters, NoteSearchResult, ReindexReport},
+    services::{search, settings},
+    state::AppState,
+};
+
+#[tauri::command]
+pub fn search_notes(
+    state: State<'_, AppState>,
+    query: String,
+    filters: Option<NoteSearchFilters>,
+) -> CommandResult<Vec<NoteSearchResult>> {
+    let timezone = settings::load_output_timezone(&state.settings_path)?;
+    search::search_notes(&state.db, &query, &filters.unwrap_or_default(), &timezone)
+        .map_err(Into::into)
+}
+
+#[tauri::command]
+pub fn reindex_notes(state: State<'_, AppState>, source: String) -> CommandResult<ReindexReport> {
+    let timezone = settings::load_output_timezone(&state.settings_path)?;
+    search::reindex_export(&state.db, &source, &timezone).map_err(Into::into)
+}
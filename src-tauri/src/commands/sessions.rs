@@ -3,7 +3,13 @@ use std::path::PathBuf;
 use tauri::State;
 
 use crate::{
-    db::sessions as sessions_db, error::CommandResult, models::Session, services::files,
+    db::sessions as sessions_db,
+    error::{AppError, CommandResult},
+    models::{
+        ExportStatistics, RelocationReport, RetentionReport, Session, SessionFilters,
+        SessionMetadata, SessionPage, SessionVerification,
+    },
+    services::{files, reconcile, relocate, retention, settings, statistics},
     state::AppState,
 };
 
@@ -12,14 +18,25 @@ pub fn get_sessions(
     state: State<'_, AppState>,
     page: Option<u32>,
     per_page: Option<u32>,
-) -> CommandResult<Vec<Session>> {
-    sessions_db::fetch_sessions(&state.db_path, page.unwrap_or(1), per_page.unwrap_or(50))
-        .map_err(Into::into)
+    filters: Option<SessionFilters>,
+) -> CommandResult<SessionPage> {
+    sessions_db::fetch_sessions(
+        &state.db,
+        page.unwrap_or(1),
+        per_page.unwrap_or(50),
+        &filters.unwrap_or_default(),
+    )
+    .map_err(Into::into)
 }
 
 #[tauri::command]
 pub fn get_session(state: State<'_, AppState>, id: String) -> CommandResult<Option<Session>> {
-    sessions_db::fetch_session_by_id(&state.db_path, &id).map_err(Into::into)
+    sessions_db::fetch_session_by_id(&state.db, &id).map_err(Into::into)
+}
+
+#[tauri::command]
+pub fn get_export_statistics(state: State<'_, AppState>) -> CommandResult<ExportStatistics> {
+    statistics::get_export_statistics(&state.db).map_err(Into::into)
 }
 
 #[tauri::command]
@@ -28,8 +45,7 @@ pub fn delete_session(
     id: String,
     delete_files: bool,
 ) -> CommandResult<()> {
-    let removed =
-        sessions_db::delete_session_by_id(&state.db_path, &id).map_err(|e| e.to_string())?;
+    let removed = sessions_db::delete_session_by_id(&state.db, &id).map_err(|e| e.to_string())?;
     if delete_files {
         if let Some(session) = removed {
             let path = PathBuf::from(session.output_path);
@@ -44,3 +60,79 @@ pub fn delete_session(
 
     Ok(())
 }
+
+#[tauri::command]
+pub fn set_session_pinned(
+    state: State<'_, AppState>,
+    id: String,
+    pinned: bool,
+) -> CommandResult<Option<Session>> {
+    sessions_db::set_session_pinned(&state.db, &id, pinned).map_err(Into::into)
+}
+
+#[tauri::command]
+pub fn update_session_metadata(
+    state: State<'_, AppState>,
+    id: String,
+    metadata: SessionMetadata,
+) -> CommandResult<Option<Session>> {
+    sessions_db::update_session_metadata(&state.db, &id, &metadata).map_err(Into::into)
+}
+
+#[tauri::command]
+pub fn apply_retention_policy(
+    state: State<'_, AppState>,
+    dry_run: bool,
+) -> CommandResult<RetentionReport> {
+    let app_settings = settings::load_settings(&state.settings_path)?;
+    retention::apply_retention(&state.db, &app_settings.retention, dry_run).map_err(Into::into)
+}
+
+#[tauri::command]
+pub fn verify_session(
+    state: State<'_, AppState>,
+    id: String,
+) -> CommandResult<Option<SessionVerification>> {
+    {
+        let guard = state
+            .active_export
+            .lock()
+            .map_err(|_| "Internal state lock poisoned.".to_string())?;
+        if guard.as_ref().is_some_and(|active| active.session_id == id) {
+            return Err(AppError::ExportRunning.to_string());
+        }
+    }
+
+    let Some(session) = sessions_db::fetch_session_by_id(&state.db, &id)? else {
+        return Ok(None);
+    };
+    reconcile::verify_session(&state.db, &session)
+        .map(Some)
+        .map_err(Into::into)
+}
+
+#[tauri::command]
+pub fn relocate_sessions(
+    state: State<'_, AppState>,
+    old_prefix: String,
+    new_prefix: String,
+    move_files: Option<bool>,
+) -> CommandResult<RelocationReport> {
+    {
+        let guard = state
+            .active_export
+            .lock()
+            .map_err(|_| "Internal state lock poisoned.".to_string())?;
+        if guard.is_some() {
+            return Err(AppError::ExportRunning.to_string());
+        }
+    }
+
+    relocate::relocate_sessions(
+        &state.db,
+        &old_prefix,
+        &new_prefix,
+        move_files.unwrap_or(false),
+    )
+    .map_err(Into::into)
+}
@@ -2,8 +2,8 @@ use tauri::State;
 
 use crate::{
     error::CommandResult,
-    models::AppSettings,
-    services::{settings, update},
+    models::{AppSettings, ExportProfile},
+    services::{relocate, settings, update},
     state::AppState,
 };
 
@@ -17,8 +17,27 @@ pub fn update_app_settings(
     state: State<'_, AppState>,
     settings_payload: AppSettings,
 ) -> CommandResult<AppSettings> {
+    let previous = settings::load_settings(&state.settings_path).map_err(|e| e.to_string())?;
     settings::save_settings(&state.settings_path, &settings_payload).map_err(|e| e.to_string())?;
-    settings::load_settings(&state.settings_path).map_err(Into::into)
+    let current = settings::load_settings(&state.settings_path).map_err(|e| e.to_string())?;
+    relocate::update_path_storage(&state.db, &previous, &current).map_err(|e| e.to_string())?;
+    Ok(current)
+}
+
+#[tauri::command]
+pub fn save_export_profile(
+    state: State<'_, AppState>,
+    profile: ExportProfile,
+) -> CommandResult<ExportProfile> {
+    settings::save_export_profile(&state.settings_path, &profile).map_err(Into::into)
+}
+
+#[tauri::command]
+pub fn delete_export_profile(
+    state: State<'_, AppState>,
+    profile_id: String,
+) -> CommandResult<AppSettings> {
+    settings::delete_export_profile(&state.settings_path, &profile_id).map_err(Into::into)
 }
 
 #[tauri::command]
@@ -0,0 +1,73 @@
+use std::{
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex, MutexGuard, RwLock},
+    time::Duration,
+};
+
+use rusqlite::{backup::Progress, Connection, OpenFlags, MAIN_DB};
+
+use crate::{
+    db::migrations::{apply_migrations, current_version, latest_version},
+    error::{AppError, AppResult},
+};
+
+const BUSY_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Clone)]
+pub struct Database {
+    conn: Arc<Mutex<Connection>>,
+    path_root: Arc<RwLock<Option<PathBuf>>>,
+}
+
+impl Database {
+    pub fn open(path: &Path) -> AppResult<Self> {
+        let mut conn = Connection::open(path)?;
+        conn.busy_timeout(BUSY_TIMEOUT)?;
+        conn.pragma_update(None, "journal_mode", "WAL")?;
+        conn.pragma_update(None, "synchronous", "NORMAL")?;
+        apply_migrations(&mut conn)?;
+
+        Ok(Self {
+            conn: Arc::new(Mutex::new(conn)),
+            path_root: Arc::new(RwLock::new(None)),
+        })
+    }
+
+    pub fn backup_to(&self, path: &Path) -> AppResult<()> {
+        let conn = self.connection()?;
+        conn.backup(MAIN_DB, path, None)?;
+        Ok(())
+    }
+
+    pub fn restore_from(&self, path: &Path) -> AppResult<()> {
+        let mut conn = self.connection()?;
+        conn.restore(MAIN_DB, path, None::<fn(Progress)>)?;
+        apply_migrations(&mut conn)
+    }
+
+    /// Root that relative session output paths are stored against and resolved from.
+    pub fn path_root(&self) -> Option<PathBuf> {
+        self.path_root.read().ok().and_then(|root| root.clone())
+    }
+
+    pub fn set_path_root(&self, root: Option<PathBuf>) {
+        if let Ok(mut guard) = self.path_root.write() {
+            *guard = root;
+        }
+    }
+
+    pub fn connection(&self) -> AppResult<MutexGuard<'_, Connection>> {
+        self.conn
+            .lock()
+            .map_err(|_| AppError::Message("Database connection lock poisoned.".to_string()))
+    }
+}
+
+pub fn latest_schema_version() -> i64 {
+    latest_version()
+}
+
+pub fn read_schema_version(path: &Path) -> AppResult<i64> {
+    let conn = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+    current_version(&conn)
+}
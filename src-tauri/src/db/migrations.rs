@@ -1,4 +1,4 @@
-use std::{collections::BTreeSet, path::Path};
+use std::collections::BTreeSet;
 
 use rusqlite::{params, Connection};
 
@@ -35,6 +35,68 @@ const MIGRATIONS: &[Migration] = &[
         name: "0002_add_name_by_title",
         sql: "ALTER TABLE sessions ADD COLUMN name_by_title INTEGER NOT NULL DEFAULT 0;",
     },
+    Migration {
+        version: 3,
+        name: "0003_add_unparsed_dates",
+        sql: "ALTER TABLE sessions ADD COLUMN unparsed_dates INTEGER NOT NULL DEFAULT 0;",
+    },
+    Migration {
+        version: 4,
+        name: "0004_create_note_search_index",
+        sql: "CREATE VIRTUAL TABLE IF NOT EXISTS note_search USING fts5(
+            title,
+            content,
+            session_id UNINDEXED,
+            file_path UNINDEXED,
+            creation_date UNINDEXED,
+            tokenize = 'unicode61 remove_diacritics 2'
+        );",
+    },
+    Migration {
+        version: 5,
+        name: "0005_add_session_query_indices",
+        sql: "CREATE INDEX IF NOT EXISTS idx_sessions_started_at ON sessions (started_at);
+            CREATE INDEX IF NOT EXISTS idx_sessions_status ON sessions (status, started_at);
+            CREATE INDEX IF NOT EXISTS idx_sessions_domain ON sessions (domain, started_at);",
+    },
+    Migration {
+        version: 6,
+        name: "0006_add_session_pinned",
+        sql: "ALTER TABLE sessions ADD COLUMN pinned INTEGER NOT NULL DEFAULT 0;",
+    },
+    Migration {
+        version: 7,
+        name: "0007_add_session_metadata",
+        sql: "ALTER TABLE sessions ADD COLUMN label TEXT;
+            ALTER TABLE sessions ADD COLUMN comment TEXT;
+            ALTER TABLE sessions ADD COLUMN account_hint TEXT;",
+    },
+    Migration {
+        version: 8,
+        name: "0008_add_session_folders",
+        sql: "ALTER TABLE sessions ADD COLUMN folders TEXT;",
+    },
+    Migration {
+        version: 9,
+        name: "0009_add_session_filtered_count",
+        sql: "ALTER TABLE sessions ADD COLUMN filtered_count INTEGER NOT NULL DEFAULT 0;",
+    },
+    Migration {
+        version: 10,
+        name: "0010_create_export_queue",
+        sql: "CREATE TABLE IF NOT EXISTS export_queue (
+            id TEXT PRIMARY KEY,
+            position INTEGER NOT NULL,
+            enqueued_at TEXT NOT NULL,
+            settings TEXT NOT NULL
+        );",
+    },
+    Migration {
+        version: 11,
+        name: "0011_add_session_outcome_before_missing",
+        sql: "ALTER TABLE sessions ADD COLUMN status_before_missing TEXT;
+            ALTER TABLE sessions ADD COLUMN error_before_missing TEXT;",
+    },
 ];
 // Add new migrations here with strictly increasing versions.
 // Example: Migration { version: 3, name: "0003_add_new_column", sql: "ALTER TABLE ..." }
@@ -53,7 +115,31 @@ fn validate_migrations() -> AppResult<()> {
     Ok(())
 }
 
-fn apply_migrations(conn: &mut Connection) -> AppResult<()> {
+pub(crate) fn latest_version() -> i64 {
+    MIGRATIONS
+        .iter()
+        .map(|migration| migration.version)
+        .max()
+        .unwrap_or(0)
+}
+
+pub(crate) fn current_version(conn: &Connection) -> AppResult<i64> {
+    let has_table = conn
+        .prepare("SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = 'schema_migrations'")?
+        .exists([])?;
+    if !has_table {
+        return Ok(0);
+    }
+
+    let version = conn.query_row(
+        "SELECT COALESCE(MAX(version), 0) FROM schema_migrations",
+        [],
+        |row| row.get::<_, i64>(0),
+    )?;
+    Ok(version)
+}
+
+pub(crate) fn apply_migrations(conn: &mut Connection) -> AppResult<()> {
     validate_migrations()?;
 
     let tx = conn.transaction()?;
@@ -90,8 +176,3 @@ fn apply_migrations(conn: &mut Connection) -> AppResult<()> {
     tx.commit()?;
     Ok(())
 }
-
-pub fn run_migrations(db_path: &Path) -> AppResult<()> {
-    let mut conn = Connection::open(db_path)?;
-    apply_migrations(&mut conn)
-}
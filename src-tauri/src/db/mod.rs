@@ -1,2 +1,5 @@
+pub mod connection;
 mod migrations;
+pub mod queue;
+pub mod search;
 pub mod sessions;
@@ -0,0 +1,48 @@
+use rusqlite::params;
+
+use crate::{db::connection::Database, error::AppResult, models::QueuedExport};
+
+pub fn fetch_queue(db: &Database) -> AppResult<Vec<QueuedExport>> {
+    let conn = db.connection()?;
+    let mut stmt =
+        conn.prepare("SELECT id, enqueued_at, settings FROM export_queue ORDER BY position")?;
+    let rows = stmt.query_map([], |row| {
+        Ok((
+            row.get::<_, String>(0)?,
+            row.get::<_, String>(1)?,
+            row.get::<_, String>(2)?,
+        ))
+    })?;
+
+    let mut jobs = Vec::new();
+    for row in rows {
+        let (id, enqueued_at, settings) = row?;
+        jobs.push(QueuedExport {
+            id,
+            enqueued_at,
+            settings: serde_json::from_str(&settings)?,
+        });
+    }
+    Ok(jobs)
+}
+
+/// Replaces the stored queue with `jobs`, keeping their order.
+pub fn save_queue(db: &Database, jobs: &[QueuedExport]) -> AppResult<()> {
+    let mut conn = db.connection()?;
+    let tx = conn.transaction()?;
+    tx.execute("DELETE FROM export_queue", [])?;
+    for (position, job) in jobs.iter().enumerate() {
+        tx.execute(
+            "INSERT INTO export_queue (id, position, enqueued_at, settings) VALUES (?1, ?2, ?3, ?4)",
+            params![
+                job.id,
+                position as i64,
+                job.enqueued_at,
+                serde_json::to_string(&job.settings)?,
+            ],
+        )?;
+    }
+    tx.commit()?;
+
+    Ok(())
+}
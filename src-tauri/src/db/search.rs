@@ -0,0 +1,122 @@
+use rusqlite::{params, Connection, Row, ToSql};
+
+use crate::{db::connection::Database, error::AppResult, models::NoteSearchResult};
+
+#[derive(Debug, Clone)]
+pub struct NoteIndexEntry {
+    pub session_id: Option<String>,
+    pub file_path: String,
+    pub title: String,
+    pub content: String,
+    pub creation_date: String,
+}
+
+pub struct NoteSearchQuery<'a> {
+    pub match_expression: &'a str,
+    pub session_id: Option<&'a str>,
+    pub created_after: Option<&'a str>,
+    pub created_before: Option<&'a str>,
+    pub limit: u32,
+}
+
+pub(crate) fn insert_entry(conn: &Connection, entry: &NoteIndexEntry) -> rusqlite::Result<usize> {
+    conn.execute(
+        "INSERT INTO note_search (title, content, session_id, file_path, creation_date)
+         VALUES (?1, ?2, ?3, ?4, ?5)",
+        params![
+            entry.title,
+            entry.content,
+            entry.session_id.as_deref().unwrap_or_default(),
+            entry.file_path,
+            entry.creation_date,
+        ],
+    )
+}
+
+fn map_search_row(row: &Row<'_>) -> Result<NoteSearchResult, rusqlite::Error> {
+    let session_id: String = row.get("session_id")?;
+    Ok(NoteSearchResult {
+        session_id: if session_id.is_empty() {
+            None
+        } else {
+            Some(session_id)
+        },
+        file_path: row.get("file_path")?,
+        title: row.get("title")?,
+        creation_date: row.get("creation_date")?,
+        snippet: row.get("snippet")?,
+        rank: row.get("rank")?,
+    })
+}
+
+pub fn replace_path_index(
+    db: &Database,
+    source_path: &str,
+    entries: &[NoteIndexEntry],
+) -> AppResult<()> {
+    let nested_prefix = format!(
+        "{}{}",
+        source_path.trim_end_matches(['/', '\\']),
+        std::path::MAIN_SEPARATOR
+    );
+
+    let mut conn = db.connection()?;
+    let tx = conn.transaction()?;
+    tx.execute(
+        "DELETE FROM note_search
+         WHERE file_path = ?1 OR substr(file_path, 1, length(?2)) = ?2",
+        params![source_path, nested_prefix],
+    )?;
+    for entry in entries {
+        insert_entry(&tx, entry)?;
+    }
+    tx.commit()?;
+    Ok(())
+}
+
+pub fn search_notes(
+    db: &Database,
+    query: &NoteSearchQuery<'_>,
+) -> AppResult<Vec<NoteSearchResult>> {
+    let mut sql = String::from(
+        "SELECT session_id, file_path, title, creation_date,
+                snippet(note_search, -1, '**', '**', '...', 16) AS snippet,
+                bm25(note_search, 5.0, 1.0) AS rank
+         FROM note_search
+         WHERE note_search MATCH ?1",
+    );
+    let mut values: Vec<&dyn ToSql> = vec![&query.match_expression];
+
+    if let Some(session_id) = &query.session_id {
+        values.push(session_id);
+        sql.push_str(&format!(" AND session_id = ?{}", values.len()));
+    }
+    if let Some(created_after) = &query.created_after {
+        values.push(created_after);
+        sql.push_str(&format!(
+            " AND creation_date <> '' AND creation_date >= ?{}",
+            values.len()
+        ));
+    }
+    if let Some(created_before) = &query.created_before {
+        values.push(created_before);
+        sql.push_str(&format!(
+            " AND creation_date <> '' AND creation_date < ?{}",
+            values.len()
+        ));
+    }
+
+    let limit = query.limit as i64;
+    values.push(&limit);
+    sql.push_str(&format!(" ORDER BY rank LIMIT ?{}", values.len()));
+
+    let conn = db.connection()?;
+    let mut stmt = conn.prepare(&sql)?;
+    let rows = stmt.query_map(values.as_slice(), map_search_row)?;
+    let mut results = Vec::new();
+    for row in rows {
+        results.push(row?);
+    }
+
+    Ok(results)
+}
@@ -1,8 +1,26 @@
 use std::path::Path;
 
-use rusqlite::{params, Connection, OptionalExtension, Row};
+use chrono::{DateTime, Utc};
+use rusqlite::{params, params_from_iter, types::Value, Connection, OptionalExtension, Row};
 
-use crate::{db::migrations::run_migrations, error::AppResult, models::Session};
+use crate::{
+    db::{
+        connection::Database,
+        search::{insert_entry, NoteIndexEntry},
+    },
+    error::{AppError, AppResult},
+    models::{
+        Session, SessionFilters, SessionMetadata, SessionPage, SessionSortKey, SortDirection,
+    },
+    services::{
+        date_parser::parse_filter_date,
+        files::{relative_to_root, replace_path_prefix, resolve_from_root},
+    },
+};
+
+const SESSION_COLUMNS: &str = "id, domain, started_at, completed_at, status, notes_count, images_count,
+    split_mode, name_by_title, timestamp_fmt, images_enabled, output_path, images_dir_name, error_message,
+    unparsed_dates, pinned, label, comment, account_hint, folders, filtered_count";
 
 fn bool_to_i64(value: bool) -> i64 {
     if value {
@@ -28,20 +46,61 @@ fn map_session_row(row: &Row<'_>) -> Result<Session, rusqlite::Error> {
         output_path: row.get("output_path")?,
         images_dir_name: row.get("images_dir_name")?,
         error_message: row.get("error_message")?,
+        unparsed_dates: row.get::<_, i64>("unparsed_dates")? as u32,
+        pinned: row.get::<_, i64>("pinned")? != 0,
+        label: row.get("label")?,
+        comment: row.get("comment")?,
+        account_hint: row.get("account_hint")?,
+        folders: row
+            .get::<_, Option<String>>("folders")?
+            .and_then(|raw| serde_json::from_str(&raw).ok())
+            .unwrap_or_default(),
+        filtered_count: row.get::<_, i64>("filtered_count")? as u32,
     })
 }
 
-pub fn init_db(db_path: &Path) -> AppResult<()> {
-    run_migrations(db_path)
+fn folders_json(folders: &[String]) -> Option<String> {
+    if folders.is_empty() {
+        None
+    } else {
+        serde_json::to_string(folders).ok()
+    }
+}
+
+fn stored_output_path(root: Option<&Path>, path: &str) -> String {
+    root.and_then(|root| relative_to_root(path, root))
+        .unwrap_or_else(|| path.to_string())
+}
+
+fn resolve_session(mut session: Session, root: Option<&Path>) -> Session {
+    session.output_path = resolve_from_root(&session.output_path, root);
+    session
+}
+
+fn output_paths(conn: &Connection) -> AppResult<Vec<(String, String)>> {
+    let mut stmt = conn.prepare("SELECT id, output_path FROM sessions")?;
+    let rows = stmt.query_map([], |row| {
+        Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+    })?;
+    Ok(rows.collect::<Result<Vec<_>, _>>()?)
+}
+
+pub fn init_db(db_path: &Path) -> AppResult<Database> {
+    Database::open(db_path)
 }
 
-pub fn insert_session(db_path: &Path, session: &Session) -> AppResult<()> {
-    let conn = Connection::open(db_path)?;
+pub fn insert_session(db: &Database, session: &Session) -> AppResult<()> {
+    let root = db.path_root();
+    let conn = db.connection()?;
     conn.execute(
         "INSERT INTO sessions (
             id, domain, started_at, completed_at, status, notes_count, images_count,
-            split_mode, name_by_title, timestamp_fmt, images_enabled, output_path, images_dir_name, error_message
-        ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14)",
+            split_mode, name_by_title, timestamp_fmt, images_enabled, output_path, images_dir_name, error_message,
+            unparsed_dates, pinned, label, comment, account_hint, folders, filtered_count
+        ) VALUES (
+            ?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20,
+            ?21
+        )",
         params![
             session.id,
             session.domain,
@@ -54,9 +113,16 @@ pub fn insert_session(db_path: &Path, session: &Session) -> AppResult<()> {
             bool_to_i64(session.name_by_title),
             session.timestamp_format,
             bool_to_i64(session.images_enabled),
-            session.output_path,
+            stored_output_path(root.as_deref(), &session.output_path),
             session.images_dir_name,
             session.error_message,
+            session.unparsed_dates as i64,
+            bool_to_i64(session.pinned),
+            session.label,
+            session.comment,
+            session.account_hint,
+            folders_json(&session.folders),
+            session.filtered_count as i64,
         ],
     )?;
 
@@ -64,22 +130,38 @@ pub fn insert_session(db_path: &Path, session: &Session) -> AppResult<()> {
 }
 
 pub fn update_session_progress(
-    db_path: &Path,
+    db: &Database,
     session_id: &str,
     notes_count: u32,
     images_count: u32,
+    unparsed_dates: u32,
+    filtered_count: u32,
+    index_entries: &[NoteIndexEntry],
 ) -> AppResult<()> {
-    let conn = Connection::open(db_path)?;
-    conn.execute(
-        "UPDATE sessions SET notes_count = ?1, images_count = ?2 WHERE id = ?3",
-        params![notes_count as i64, images_count as i64, session_id],
+    let mut conn = db.connection()?;
+    let tx = conn.transaction()?;
+    for entry in index_entries {
+        insert_entry(&tx, entry)?;
+    }
+    tx.execute(
+        "UPDATE sessions
+         SET notes_count = ?1, images_count = ?2, unparsed_dates = ?3, filtered_count = ?4
+         WHERE id = ?5",
+        params![
+            notes_count as i64,
+            images_count as i64,
+            unparsed_dates as i64,
+            filtered_count as i64,
+            session_id
+        ],
     )?;
+    tx.commit()?;
 
     Ok(())
 }
 
 pub fn set_session_outcome(
-    db_path: &Path,
+    db: &Database,
     session_id: &str,
     status: &str,
     completed_at: &str,
@@ -87,7 +169,7 @@ pub fn set_session_outcome(
     images_count: u32,
     error_message: Option<&str>,
 ) -> AppResult<()> {
-    let conn = Connection::open(db_path)?;
+    let conn = db.connection()?;
     conn.execute(
         "UPDATE sessions
          SET status = ?1, completed_at = ?2, notes_count = ?3, images_count = ?4, error_message = ?5
@@ -105,59 +187,386 @@ pub fn set_session_outcome(
     Ok(())
 }
 
-pub fn fetch_sessions(db_path: &Path, page: u32, per_page: u32) -> AppResult<Vec<Session>> {
+pub fn set_session_status(db: &Database, session_id: &str, status: &str) -> AppResult<()> {
+    let conn = db.connection()?;
+    conn.execute(
+        "UPDATE sessions SET status = ?1 WHERE id = ?2",
+        params![status, session_id],
+    )?;
+
+    Ok(())
+}
+
+/// Remembers the status and error a session had before its output went missing, or clears
+/// them with `None`.
+pub fn set_outcome_before_missing(
+    db: &Database,
+    session_id: &str,
+    outcome: Option<(&str, Option<&str>)>,
+) -> AppResult<()> {
+    let (status, error_message) = outcome.unzip();
+    let conn = db.connection()?;
+    conn.execute(
+        "UPDATE sessions SET status_before_missing = ?1, error_before_missing = ?2 WHERE id = ?3",
+        params![status, error_message.flatten(), session_id],
+    )?;
+
+    Ok(())
+}
+
+pub fn fetch_outcome_before_missing(
+    db: &Database,
+    session_id: &str,
+) -> AppResult<Option<(String, Option<String>)>> {
+    let conn = db.connection()?;
+    let outcome = conn
+        .query_row(
+            "SELECT status_before_missing, error_before_missing FROM sessions WHERE id = ?1",
+            params![session_id],
+            |row| {
+                Ok((
+                    row.get::<_, Option<String>>(0)?,
+                    row.get::<_, Option<String>>(1)?,
+                ))
+            },
+        )
+        .optional()?;
+
+    Ok(outcome.and_then(|(status, error_message)| status.map(|status| (status, error_message))))
+}
+
+pub fn set_session_folders(db: &Database, session_id: &str, folders: &[String]) -> AppResult<()> {
+    let conn = db.connection()?;
+    conn.execute(
+        "UPDATE sessions SET folders = ?1 WHERE id = ?2",
+        params![folders_json(folders), session_id],
+    )?;
+
+    Ok(())
+}
+
+pub fn set_session_pinned(
+    db: &Database,
+    session_id: &str,
+    pinned: bool,
+) -> AppResult<Option<Session>> {
+    let conn = db.connection()?;
+    let updated = conn.execute(
+        "UPDATE sessions SET pinned = ?1 WHERE id = ?2",
+        params![bool_to_i64(pinned), session_id],
+    )?;
+    if updated == 0 {
+        return Ok(None);
+    }
+
+    query_session_by_id(&conn, db.path_root().as_deref(), session_id)
+}
+
+pub fn relocate_output_paths(db: &Database, old_prefix: &str, new_prefix: &str) -> AppResult<u32> {
+    let root = db.path_root();
+    let mut conn = db.connection()?;
+    let tx = conn.transaction()?;
+
+    let mut relocated = 0;
+    for (session_id, stored_path) in output_paths(&tx)? {
+        let output_path = resolve_from_root(&stored_path, root.as_deref());
+        if let Some(new_path) = replace_path_prefix(&output_path, old_prefix, new_prefix) {
+            tx.execute(
+                "UPDATE sessions SET output_path = ?1 WHERE id = ?2",
+                params![stored_output_path(root.as_deref(), &new_path), session_id],
+            )?;
+            relocated += 1;
+        }
+    }
+
+    let indexed_paths = {
+        let mut stmt = tx.prepare("SELECT DISTINCT file_path FROM note_search")?;
+        let rows = stmt.query_map([], |row| row.get::<_, String>(0))?;
+        rows.collect::<Result<Vec<_>, _>>()?
+    };
+    for file_path in indexed_paths {
+        if let Some(new_path) = replace_path_prefix(&file_path, old_prefix, new_prefix) {
+            tx.execute(
+                "UPDATE note_search SET file_path = ?1 WHERE file_path = ?2",
+                params![new_path, file_path],
+            )?;
+        }
+    }
+
+    tx.commit()?;
+    Ok(relocated)
+}
+
+/// Rewrites every session path as absolute, or as relative to `root` when it lives under it.
+pub fn convert_path_storage(db: &Database, root: &Path, relative: bool) -> AppResult<u32> {
+    let mut conn = db.connection()?;
+    let tx = conn.transaction()?;
+
+    let mut converted = 0;
+    for (session_id, stored_path) in output_paths(&tx)? {
+        let output_path = resolve_from_root(&stored_path, Some(root));
+        let new_path = if relative {
+            stored_output_path(Some(root), &output_path)
+        } else {
+            output_path
+        };
+        if new_path != stored_path {
+            tx.execute(
+                "UPDATE sessions SET output_path = ?1 WHERE id = ?2",
+                params![new_path, session_id],
+            )?;
+            converted += 1;
+        }
+    }
+
+    tx.commit()?;
+    Ok(converted)
+}
+
+pub fn update_session_metadata(
+    db: &Database,
+    session_id: &str,
+    metadata: &SessionMetadata,
+) -> AppResult<Option<Session>> {
+    let conn = db.connection()?;
+    let updated = conn.execute(
+        "UPDATE sessions
+         SET label = ?1, comment = ?2, account_hint = ?3, pinned = COALESCE(?4, pinned)
+         WHERE id = ?5",
+        params![
+            non_empty(metadata.label.as_deref()),
+            non_empty(metadata.comment.as_deref()),
+            non_empty(metadata.account_hint.as_deref()),
+            metadata.pinned.map(bool_to_i64),
+            session_id,
+        ],
+    )?;
+    if updated == 0 {
+        return Ok(None);
+    }
+
+    query_session_by_id(&conn, db.path_root().as_deref(), session_id)
+}
+
+fn sort_column(key: SessionSortKey) -> &'static str {
+    match key {
+        SessionSortKey::StartedAt => "started_at",
+        SessionSortKey::CompletedAt => "completed_at",
+        SessionSortKey::Status => "status",
+        SessionSortKey::Domain => "domain",
+        SessionSortKey::NotesCount => "notes_count",
+        SessionSortKey::ImagesCount => "images_count",
+    }
+}
+
+fn escape_like(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
+}
+
+fn non_empty(value: Option<&str>) -> Option<&str> {
+    value.map(str::trim).filter(|value| !value.is_empty())
+}
+
+/// Normalizes a date filter to the `Utc::to_rfc3339` form `started_at` is stored in, so the
+/// bound compares as text and can use `idx_sessions_started_at`.
+fn started_at_bound(value: Option<&str>) -> AppResult<Option<String>> {
+    parse_filter_date(value)?
+        .map(|bound| {
+            DateTime::parse_from_rfc3339(&bound)
+                .map(|parsed| parsed.with_timezone(&Utc).to_rfc3339())
+                .map_err(|error| AppError::Message(error.to_string()))
+        })
+        .transpose()
+}
+
+pub fn fetch_sessions(
+    db: &Database,
+    page: u32,
+    per_page: u32,
+    filters: &SessionFilters,
+) -> AppResult<SessionPage> {
+    let root = db.path_root();
     let safe_page = if page == 0 { 1 } else { page };
     let safe_per_page = if per_page == 0 { 50 } else { per_page.min(500) };
     let offset = (safe_page - 1) * safe_per_page;
 
-    let conn = Connection::open(db_path)?;
-    let mut stmt = conn.prepare(
-        "SELECT
-            id, domain, started_at, completed_at, status, notes_count, images_count,
-            split_mode, name_by_title, timestamp_fmt, images_enabled, output_path, images_dir_name, error_message
-         FROM sessions
-         ORDER BY started_at DESC
-         LIMIT ?1 OFFSET ?2",
-    )?;
+    let mut conditions = Vec::<String>::new();
+    let mut values = Vec::<Value>::new();
+    let mut push_condition = |condition: &str, value: Value| {
+        values.push(value);
+        conditions.push(condition.replace('?', &format!("?{}", values.len())));
+    };
 
-    let rows = stmt.query_map(
-        params![safe_per_page as i64, offset as i64],
-        map_session_row,
+    if let Some(status) = non_empty(filters.status.as_deref()) {
+        push_condition("status = ?", Value::Text(status.to_string()));
+    }
+    if let Some(domain) = non_empty(filters.domain.as_deref()) {
+        push_condition("domain = ? COLLATE NOCASE", Value::Text(domain.to_string()));
+    }
+    if let Some(started_after) = started_at_bound(filters.started_after.as_deref())? {
+        push_condition("started_at >= ?", Value::Text(started_after));
+    }
+    if let Some(started_before) = started_at_bound(filters.started_before.as_deref())? {
+        push_condition("started_at < ?", Value::Text(started_before));
+    }
+    if let Some(split_mode) = filters.split_mode {
+        push_condition("split_mode = ?", Value::Integer(bool_to_i64(split_mode)));
+    }
+    if let Some(images_enabled) = filters.images_enabled {
+        push_condition(
+            "images_enabled = ?",
+            Value::Integer(bool_to_i64(images_enabled)),
+        );
+    }
+    if let Some(label) = non_empty(filters.label.as_deref()) {
+        push_condition("label = ? COLLATE NOCASE", Value::Text(label.to_string()));
+    }
+    if let Some(account_hint) = non_empty(filters.account_hint.as_deref()) {
+        push_condition(
+            "account_hint = ? COLLATE NOCASE",
+            Value::Text(account_hint.to_string()),
+        );
+    }
+    if let Some(pinned) = filters.pinned {
+        push_condition("pinned = ?", Value::Integer(bool_to_i64(pinned)));
+    }
+    if let Some(search) = non_empty(filters.search.as_deref()) {
+        push_condition(
+            "(output_path LIKE ? ESCAPE '\\'
+              OR COALESCE(error_message, '') LIKE ? ESCAPE '\\'
+              OR COALESCE(label, '') LIKE ? ESCAPE '\\'
+              OR COALESCE(comment, '') LIKE ? ESCAPE '\\'
+              OR COALESCE(account_hint, '') LIKE ? ESCAPE '\\')",
+            Value::Text(format!("%{}%", escape_like(search))),
+        );
+    }
+
+    let where_clause = if conditions.is_empty() {
+        String::new()
+    } else {
+        format!("WHERE {}", conditions.join(" AND "))
+    };
+    let direction = match filters.sort_direction {
+        SortDirection::Asc => "ASC",
+        SortDirection::Desc => "DESC",
+    };
+
+    let conn = db.connection()?;
+    let total = conn.query_row(
+        &format!("SELECT COUNT(*) FROM sessions {where_clause}"),
+        params_from_iter(values.iter()),
+        |row| row.get::<_, i64>(0),
     )?;
+
+    let limit_index = values.len() + 1;
+    let offset_index = values.len() + 2;
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {SESSION_COLUMNS}
+         FROM sessions
+         {where_clause}
+         ORDER BY {} {direction}, started_at DESC
+         LIMIT ?{limit_index} OFFSET ?{offset_index}",
+        sort_column(filters.sort_by)
+    ))?;
+
+    values.push(Value::Integer(safe_per_page as i64));
+    values.push(Value::Integer(offset as i64));
+    let rows = stmt.query_map(params_from_iter(values.iter()), map_session_row)?;
+    let mut sessions = Vec::new();
+    for row in rows {
+        sessions.push(resolve_session(row?, root.as_deref()));
+    }
+
+    Ok(SessionPage {
+        sessions,
+        total: total as u32,
+        page: safe_page,
+        per_page: safe_per_page,
+    })
+}
+
+pub fn fetch_all_sessions(db: &Database) -> AppResult<Vec<Session>> {
+    let root = db.path_root();
+    let conn = db.connection()?;
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {SESSION_COLUMNS}
+         FROM sessions
+         ORDER BY started_at ASC"
+    ))?;
+
+    let rows = stmt.query_map([], map_session_row)?;
     let mut sessions = Vec::new();
     for row in rows {
-        sessions.push(row?);
+        sessions.push(resolve_session(row?, root.as_deref()));
     }
 
     Ok(sessions)
 }
 
-pub fn fetch_session_by_id(db_path: &Path, session_id: &str) -> AppResult<Option<Session>> {
-    let conn = Connection::open(db_path)?;
-    let mut stmt = conn.prepare(
-        "SELECT
-            id, domain, started_at, completed_at, status, notes_count, images_count,
-            split_mode, name_by_title, timestamp_fmt, images_enabled, output_path, images_dir_name, error_message
+fn query_session_by_id(
+    conn: &Connection,
+    root: Option<&Path>,
+    session_id: &str,
+) -> AppResult<Option<Session>> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {SESSION_COLUMNS}
          FROM sessions
          WHERE id = ?1
-         LIMIT 1",
-    )?;
+         LIMIT 1"
+    ))?;
 
     let session = stmt
         .query_row(params![session_id], map_session_row)
         .optional()?;
 
-    Ok(session)
+    Ok(session.map(|session| resolve_session(session, root)))
 }
 
-pub fn delete_session_by_id(db_path: &Path, session_id: &str) -> AppResult<Option<Session>> {
-    let session = fetch_session_by_id(db_path, session_id)?;
+pub fn fetch_session_by_id(db: &Database, session_id: &str) -> AppResult<Option<Session>> {
+    let conn = db.connection()?;
+    query_session_by_id(&conn, db.path_root().as_deref(), session_id)
+}
+
+pub fn fetch_session_by_output_path(
+    db: &Database,
+    output_path: &str,
+) -> AppResult<Option<Session>> {
+    let root = db.path_root();
+    let conn = db.connection()?;
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {SESSION_COLUMNS}
+         FROM sessions
+         WHERE output_path IN (?1, ?2)
+         ORDER BY started_at DESC
+         LIMIT 1"
+    ))?;
+
+    let stored_path = stored_output_path(root.as_deref(), output_path);
+    let session = stmt
+        .query_row(params![output_path, stored_path], map_session_row)
+        .optional()?;
+
+    Ok(session.map(|session| resolve_session(session, root.as_deref())))
+}
+
+pub fn delete_session_by_id(db: &Database, session_id: &str) -> AppResult<Option<Session>> {
+    let root = db.path_root();
+    let mut conn = db.connection()?;
+    let tx = conn.transaction()?;
+    let session = query_session_by_id(&tx, root.as_deref(), session_id)?;
     if session.is_none() {
         return Ok(None);
     }
 
-    let conn = Connection::open(db_path)?;
-    conn.execute("DELETE FROM sessions WHERE id = ?1", params![session_id])?;
+    tx.execute("DELETE FROM sessions WHERE id = ?1", params![session_id])?;
+    tx.execute(
+        "DELETE FROM note_search WHERE session_id = ?1",
+        params![session_id],
+    )?;
+    tx.commit()?;
 
     Ok(session)
 }
@@ -15,6 +15,8 @@ pub enum AppError {
     ExportNotRunning,
     #[error("Active export session does not match request.")]
     SessionMismatch,
+    #[error("Unrecognized creation date: '{raw}'.")]
+    UnparsedDate { raw: String },
     #[error(transparent)]
     Io(#[from] io::Error),
     #[error(transparent)]
@@ -26,6 +28,9 @@ pub enum AppError {
     #[error(transparent)]
     Url(#[from] url::ParseError),
     #[error(transparent)]
+    Zip(#[from] zip::result::ZipError),
+    #[cfg(feature = "gui")]
+    #[error(transparent)]
     Tauri(#[from] tauri::Error),
 }
 
@@ -17,6 +17,147 @@ pub struct Session {
     pub output_path: String,
     pub images_dir_name: Option<String>,
     pub error_message: Option<String>,
+    pub unparsed_dates: u32,
+    #[serde(default)]
+    pub pinned: bool,
+    #[serde(default)]
+    pub label: Option<String>,
+    #[serde(default)]
+    pub comment: Option<String>,
+    #[serde(default)]
+    pub account_hint: Option<String>,
+    #[serde(default)]
+    pub folders: Vec<String>,
+    #[serde(default)]
+    pub filtered_count: u32,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionMetadata {
+    #[serde(default)]
+    pub label: Option<String>,
+    #[serde(default)]
+    pub comment: Option<String>,
+    #[serde(default)]
+    pub account_hint: Option<String>,
+    /// Left unchanged when omitted.
+    #[serde(default)]
+    pub pinned: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionVerification {
+    pub session: Session,
+    pub previous_status: String,
+    pub output_exists: bool,
+    pub notes_on_disk: u32,
+    pub images_on_disk: u32,
+    pub changed: bool,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum UnparsedDatePolicy {
+    Fail,
+    #[default]
+    UseNow,
+    UseEpoch,
+    LeaveBlank,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ExportSource {
+    /// Reads notes from the Mi Cloud API with the cookies of the login window.
+    #[default]
+    Api,
+    /// Drives the Mi Cloud web page with the injected scraper script.
+    Dom,
+}
+
+/// Everything needed to start one export, as sent by `start_export` or stored in the queue.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportJobSettings {
+    pub domain: String,
+    /// Empty uses the default export directory from settings.
+    #[serde(default)]
+    pub output_dir: String,
+    pub split: bool,
+    pub name_by_title: bool,
+    pub timestamp_format: String,
+    pub created_date_format: String,
+    pub export_images: bool,
+    #[serde(default)]
+    pub skip_duplicates: bool,
+    #[serde(default)]
+    pub unparsed_date_policy: UnparsedDatePolicy,
+    #[serde(default)]
+    pub source: ExportSource,
+    #[serde(default)]
+    pub filter: ExportFilterSpec,
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+/// A named set of export options saved in settings.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportProfile {
+    /// Empty when saving a new profile; an id is assigned on save.
+    #[serde(default)]
+    pub id: String,
+    pub name: String,
+    pub settings: ExportJobSettings,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueuedExport {
+    pub id: String,
+    pub enqueued_at: String,
+    pub settings: ExportJobSettings,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportQueueStatus {
+    /// Whether finished exports start the next queued job.
+    pub running: bool,
+    pub jobs: Vec<QueuedExport>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum UnsupportedNotesFilter {
+    #[default]
+    Include,
+    Exclude,
+    /// Exports only notes the exporter cannot render, such as mind maps.
+    Only,
+}
+
+/// Which scraped notes an export keeps; every criterion left empty keeps all notes.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportFilterSpec {
+    #[serde(default)]
+    pub created_after: Option<String>,
+    #[serde(default)]
+    pub created_before: Option<String>,
+    #[serde(default)]
+    pub title_regex: Option<String>,
+    #[serde(default)]
+    pub body_regex: Option<String>,
+    #[serde(default)]
+    pub unsupported: UnsupportedNotesFilter,
+    #[serde(default)]
+    pub min_length: Option<u32>,
+    /// Folder names (case-insensitive) or Mi Cloud folder ids.
+    #[serde(default)]
+    pub folders: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -35,6 +176,7 @@ pub struct ExportProgressEvent {
     pub last_title: String,
     pub notes_count: u32,
     pub images_count: u32,
+    pub filtered_count: u32,
     pub log_line: String,
 }
 
@@ -45,6 +187,39 @@ pub struct ExportCompleteEvent {
     pub total: u32,
     pub elapsed_ms: u64,
     pub output_path: String,
+    /// Set for dry runs, which write nothing and report the planned export instead.
+    pub dry_run: Option<ExportDryRunReport>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DryRunUnparsedDate {
+    pub title: String,
+    pub created: String,
+}
+
+/// Notes whose file names only differ by the counter the exporter appends.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DryRunCollision {
+    pub name: String,
+    pub files: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportDryRunReport {
+    pub notes_count: u32,
+    pub images_count: u32,
+    pub image_bytes: u64,
+    pub markdown_bytes: u64,
+    pub duplicates_skipped: u32,
+    pub filtered_count: u32,
+    /// Note files relative to the output root; a single-file export lists that file once.
+    pub files: Vec<String>,
+    pub collisions: Vec<DryRunCollision>,
+    pub unsupported_notes: Vec<String>,
+    pub unparsed_dates: Vec<DryRunUnparsedDate>,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -69,12 +244,22 @@ pub struct ScrapedNoteInput {
     pub content_html: String,
     pub created_string: String,
     #[serde(default)]
+    pub modified_timestamp: Option<i64>,
+    #[serde(default)]
     pub note_id: Option<String>,
     #[serde(default)]
     pub cookie_header: Option<String>,
+    #[serde(default)]
     pub unsupported: bool,
     #[serde(default)]
+    pub folder_id: Option<String>,
+    #[serde(default)]
+    pub folder: Option<String>,
+    #[serde(default)]
     pub images: Vec<ScrapedImageInput>,
+    /// Images that could not be downloaded before the note reached the exporter.
+    #[serde(default)]
+    pub failed_images: u32,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -84,6 +269,237 @@ pub struct NoteDto {
     pub content: String,
     pub creation_date: String,
     pub last_modified: String,
+    pub folder: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NoteSearchFilters {
+    #[serde(default)]
+    pub session_id: Option<String>,
+    #[serde(default)]
+    pub created_after: Option<String>,
+    #[serde(default)]
+    pub created_before: Option<String>,
+    #[serde(default)]
+    pub limit: Option<u32>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NoteSearchResult {
+    pub session_id: Option<String>,
+    pub file_path: String,
+    pub title: String,
+    pub creation_date: String,
+    pub snippet: String,
+    pub rank: f64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReindexReport {
+    pub source: String,
+    pub session_id: Option<String>,
+    pub files_count: u32,
+    pub notes_count: u32,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SessionSortKey {
+    #[default]
+    StartedAt,
+    CompletedAt,
+    Status,
+    Domain,
+    NotesCount,
+    ImagesCount,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SortDirection {
+    Asc,
+    #[default]
+    Desc,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionFilters {
+    #[serde(default)]
+    pub status: Option<String>,
+    #[serde(default)]
+    pub domain: Option<String>,
+    #[serde(default)]
+    pub started_after: Option<String>,
+    #[serde(default)]
+    pub started_before: Option<String>,
+    #[serde(default)]
+    pub split_mode: Option<bool>,
+    #[serde(default)]
+    pub images_enabled: Option<bool>,
+    #[serde(default)]
+    pub search: Option<String>,
+    #[serde(default)]
+    pub label: Option<String>,
+    #[serde(default)]
+    pub account_hint: Option<String>,
+    #[serde(default)]
+    pub pinned: Option<bool>,
+    #[serde(default)]
+    pub sort_by: SessionSortKey,
+    #[serde(default)]
+    pub sort_direction: SortDirection,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionPage {
+    pub sessions: Vec<Session>,
+    pub total: u32,
+    pub page: u32,
+    pub per_page: u32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionStatistics {
+    pub session_id: String,
+    pub started_at: String,
+    pub status: String,
+    pub notes_count: u32,
+    pub images_count: u32,
+    pub duration_ms: Option<i64>,
+    pub notes_per_minute: Option<f64>,
+    pub output_exists: bool,
+    pub disk_usage_bytes: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StatusCount {
+    pub status: String,
+    pub count: u32,
+    pub rate: f64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ErrorFrequency {
+    pub message: String,
+    pub count: u32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportStatistics {
+    pub total_sessions: u32,
+    pub total_notes: u64,
+    pub total_images: u64,
+    pub average_notes_per_session: f64,
+    pub average_images_per_session: f64,
+    pub average_notes_per_minute: Option<f64>,
+    pub failure_rate: f64,
+    pub recent_failure_rate: f64,
+    pub status_counts: Vec<StatusCount>,
+    pub common_errors: Vec<ErrorFrequency>,
+    pub total_disk_usage_bytes: u64,
+    pub sessions: Vec<SessionStatistics>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NoteSummary {
+    pub id: String,
+    pub creation_date: String,
+    pub preview: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModifiedNoteEntry {
+    pub left_id: String,
+    pub right_id: String,
+    pub creation_date: String,
+    pub preview: String,
+    pub diff: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportDiffReport {
+    pub left_source: String,
+    pub right_source: String,
+    pub left_count: u32,
+    pub right_count: u32,
+    pub unchanged_count: u32,
+    pub added: Vec<NoteSummary>,
+    pub removed: Vec<NoteSummary>,
+    pub modified: Vec<ModifiedNoteEntry>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DuplicateCluster {
+    pub similarity: f32,
+    pub notes: Vec<NoteSummary>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DuplicateReport {
+    pub source: String,
+    pub notes_count: u32,
+    pub exact_clusters: Vec<DuplicateCluster>,
+    pub near_clusters: Vec<DuplicateCluster>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RetentionPolicy {
+    #[serde(default)]
+    pub keep_last: Option<u32>,
+    #[serde(default)]
+    pub max_age_days: Option<u32>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RetentionReport {
+    pub dry_run: bool,
+    pub sessions: Vec<Session>,
+    pub freed_bytes: u64,
+    pub failures: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppDataManifest {
+    pub format_version: u32,
+    pub app_version: String,
+    pub schema_version: i64,
+    pub created_at: String,
+    pub export_root: String,
+    pub includes_search_index: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppDataImportReport {
+    pub manifest: AppDataManifest,
+    pub sessions_count: u32,
+    pub relocated_sessions: u32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RelocationReport {
+    pub relocated_sessions: u32,
+    pub moved_files: bool,
+    pub verified_sessions: u32,
+    pub missing_sessions: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -91,4 +507,14 @@ pub struct NoteDto {
 pub struct AppSettings {
     pub default_export_dir: String,
     pub theme: String,
+    #[serde(default)]
+    pub notes_locale: String,
+    #[serde(default)]
+    pub output_timezone: String,
+    #[serde(default)]
+    pub retention: RetentionPolicy,
+    #[serde(default)]
+    pub relative_session_paths: bool,
+    #[serde(default)]
+    pub export_profiles: Vec<ExportProfile>,
 }
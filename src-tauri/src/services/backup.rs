@@ -0,0 +1,147 @@
+use std::{
+    fs::{self, File},
+    io::{self, Read, Write},
+    path::{Path, PathBuf},
+};
+
+use chrono::Utc;
+use uuid::Uuid;
+use zip::{write::SimpleFileOptions, CompressionMethod, ZipArchive, ZipWriter};
+
+use crate::{
+    db::{
+        connection::{latest_schema_version, read_schema_version, Database},
+        sessions as sessions_db,
+    },
+    error::{AppError, AppResult},
+    models::{AppDataImportReport, AppDataManifest},
+    services::{queue, relocate, settings},
+    state::AppState,
+};
+
+const BACKUP_FORMAT_VERSION: u32 = 1;
+const MANIFEST_ENTRY: &str = "manifest.json";
+const DATABASE_ENTRY: &str = "sessions.db";
+const SETTINGS_ENTRY: &str = "settings.json";
+
+struct TempFile(PathBuf);
+
+impl TempFile {
+    fn new(extension: &str) -> Self {
+        Self(std::env::temp_dir().join(format!(
+            "xiaomi_note_exporter_{}.{extension}",
+            Uuid::new_v4()
+        )))
+    }
+}
+
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.0);
+    }
+}
+
+fn read_entry(archive: &mut ZipArchive<File>, name: &str) -> AppResult<Vec<u8>> {
+    let mut entry = archive
+        .by_name(name)
+        .map_err(|_| AppError::Message(format!("Backup archive is missing '{name}'.")))?;
+    let mut bytes = Vec::new();
+    entry.read_to_end(&mut bytes)?;
+    Ok(bytes)
+}
+
+fn strip_search_index(database_path: &Path) -> AppResult<()> {
+    let conn = rusqlite::Connection::open(database_path)?;
+    conn.execute_batch("DELETE FROM note_search; VACUUM;")?;
+    Ok(())
+}
+
+pub fn export_app_data(
+    db: &Database,
+    settings_path: &Path,
+    archive_path: &Path,
+    include_search_index: bool,
+) -> AppResult<AppDataManifest> {
+    let app_settings = settings::load_settings(settings_path)?;
+    let database_copy = TempFile::new("db");
+    db.backup_to(&database_copy.0)?;
+    if !include_search_index {
+        strip_search_index(&database_copy.0)?;
+    }
+
+    let manifest = AppDataManifest {
+        format_version: BACKUP_FORMAT_VERSION,
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        schema_version: read_schema_version(&database_copy.0)?,
+        created_at: Utc::now().to_rfc3339(),
+        export_root: app_settings.default_export_dir.clone(),
+        includes_search_index: include_search_index,
+    };
+
+    if let Some(parent) = archive_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+    let mut writer = ZipWriter::new(File::create(archive_path)?);
+    writer.start_file(MANIFEST_ENTRY, options)?;
+    writer.write_all(&serde_json::to_vec_pretty(&manifest)?)?;
+    writer.start_file(SETTINGS_ENTRY, options)?;
+    writer.write_all(&serde_json::to_vec_pretty(&app_settings)?)?;
+    writer.start_file(DATABASE_ENTRY, options)?;
+    io::copy(&mut File::open(&database_copy.0)?, &mut writer)?;
+    writer.finish()?;
+
+    Ok(manifest)
+}
+
+pub fn import_app_data(
+    state: &AppState,
+    archive_path: &Path,
+    export_root: Option<&str>,
+) -> AppResult<AppDataImportReport> {
+    let db = &state.db;
+    let mut archive = ZipArchive::new(File::open(archive_path)?)?;
+    let manifest: AppDataManifest =
+        serde_json::from_slice(&read_entry(&mut archive, MANIFEST_ENTRY)?)?;
+    if manifest.format_version != BACKUP_FORMAT_VERSION {
+        return Err(AppError::Message(format!(
+            "Unsupported backup format version {}.",
+            manifest.format_version
+        )));
+    }
+
+    let database_copy = TempFile::new("db");
+    fs::write(&database_copy.0, read_entry(&mut archive, DATABASE_ENTRY)?)?;
+    let schema_version = read_schema_version(&database_copy.0)?;
+    let supported_version = latest_schema_version();
+    if schema_version > supported_version {
+        return Err(AppError::Message(format!(
+            "Backup uses database schema version {schema_version}, but this app supports up to \
+             {supported_version}. Update the app before restoring."
+        )));
+    }
+
+    let settings_copy = TempFile::new("json");
+    fs::write(&settings_copy.0, read_entry(&mut archive, SETTINGS_ENTRY)?)?;
+    let mut restored_settings = settings::load_settings(&settings_copy.0)?;
+
+    db.restore_from(&database_copy.0)?;
+    db.set_path_root(relocate::path_root(&restored_settings));
+
+    let relocated_sessions = match export_root.map(str::trim).filter(|root| !root.is_empty()) {
+        Some(new_root) if new_root != manifest.export_root => {
+            restored_settings.default_export_dir = new_root.to_string();
+            sessions_db::relocate_output_paths(db, &manifest.export_root, new_root)?
+        }
+        _ => 0,
+    };
+    settings::save_settings(&state.settings_path, &restored_settings)?;
+    relocate::sync_path_storage(db, &restored_settings)?;
+    queue::load_queue(state)?;
+
+    Ok(AppDataImportReport {
+        sessions_count: sessions_db::fetch_all_sessions(db)?.len() as u32,
+        relocated_sessions,
+        manifest,
+    })
+}
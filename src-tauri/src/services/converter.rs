@@ -6,10 +6,16 @@ use walkdir::WalkDir;
 use crate::{
     error::{AppError, AppResult},
     models::NoteDto,
-    services::date_parser::parse_markdown_created_line,
+    services::{
+        date_parser::{
+            markdown_created_value, parse_markdown_created_line, parse_markdown_modified_line,
+        },
+        markdown,
+        timezone::OutputTimezone,
+    },
 };
 
-fn sha256_hex(input: &str) -> String {
+pub(crate) fn sha256_hex(input: &str) -> String {
     let mut hasher = Sha256::new();
     hasher.update(input.as_bytes());
     hasher
@@ -29,7 +35,7 @@ pub(crate) fn is_markdown_file(path: &Path) -> bool {
         .unwrap_or(false)
 }
 
-pub(crate) fn parse_single_note(raw: &str) -> AppResult<NoteDto> {
+pub(crate) fn parse_single_note(raw: &str, timezone: &OutputTimezone) -> AppResult<NoteDto> {
     let mut lines = raw
         .replace("\r\n", "\n")
         .split('\n')
@@ -44,7 +50,26 @@ pub(crate) fn parse_single_note(raw: &str) -> AppResult<NoteDto> {
         })?;
 
     let created_line = lines[created_line_index].clone();
-    let created_at = parse_markdown_created_line(&created_line)?;
+    let created_at = if markdown_created_value(&created_line).is_empty() {
+        String::new()
+    } else {
+        match parse_markdown_created_line(&created_line, timezone) {
+            Ok(value) => value,
+            Err(AppError::UnparsedDate { .. }) => String::new(),
+            Err(error) => return Err(error),
+        }
+    };
+
+    let modified_line_index = created_line_index + 1;
+    let modified_at = if lines
+        .get(modified_line_index)
+        .is_some_and(|line| line.trim_start().starts_with("*Modified at:"))
+    {
+        let modified_line = lines.remove(modified_line_index);
+        parse_markdown_modified_line(&modified_line, timezone).ok()
+    } else {
+        None
+    };
 
     lines.remove(created_line_index);
 
@@ -55,6 +80,14 @@ pub(crate) fn parse_single_note(raw: &str) -> AppResult<NoteDto> {
         lines.remove(0);
     }
 
+    let folder = lines
+        .first()
+        .and_then(|line| markdown::parse_folder_heading(line))
+        .map(ToString::to_string);
+    if folder.is_some() {
+        lines.remove(0);
+    }
+
     while lines.last().is_some_and(|line| line.trim().is_empty()) {
         lines.pop();
     }
@@ -68,13 +101,14 @@ pub(crate) fn parse_single_note(raw: &str) -> AppResult<NoteDto> {
     Ok(NoteDto {
         id: sha256_hex(&content),
         content,
-        creation_date: created_at.clone(),
-        last_modified: created_at,
+        last_modified: modified_at.unwrap_or_else(|| created_at.clone()),
+        creation_date: created_at,
+        folder,
     })
 }
 
-pub(crate) fn parse_notes_from_markdown(content: &str) -> AppResult<Vec<NoteDto>> {
-    let sections = if content.contains("****") {
+pub(crate) fn split_note_sections(content: &str) -> Vec<String> {
+    if content.contains("****") {
         content
             .split("****")
             .filter_map(|section| {
@@ -88,39 +122,77 @@ pub(crate) fn parse_notes_from_markdown(content: &str) -> AppResult<Vec<NoteDto>
             .collect::<Vec<_>>()
     } else {
         vec![content.trim().to_string()]
-    };
+    }
+}
+
+pub(crate) fn section_title(section: &str) -> String {
+    section
+        .lines()
+        .map(str::trim)
+        .find(|line| {
+            !line.is_empty() && *line != "****" && markdown::parse_folder_heading(line).is_none()
+        })
+        .and_then(|line| line.strip_prefix("## "))
+        .map(|title| title.trim_start_matches("Title:").trim().to_string())
+        .unwrap_or_default()
+}
 
+pub(crate) fn parse_notes_from_markdown(
+    content: &str,
+    timezone: &OutputTimezone,
+) -> AppResult<Vec<NoteDto>> {
     let mut notes = Vec::new();
-    for section in sections {
-        notes.push(parse_single_note(&section)?);
+    let mut folder = None;
+    for section in split_note_sections(content) {
+        let mut note = parse_single_note(&section, timezone)?;
+        // Single-file exports only head the first note of each folder.
+        match &note.folder {
+            Some(note_folder) => folder = Some(note_folder.clone()),
+            None => note.folder = folder.clone(),
+        }
+        notes.push(note);
     }
 
     Ok(notes)
 }
 
-pub fn convert_to_json(source_path: &str, output_path: &str) -> AppResult<String> {
-    let source = std::path::PathBuf::from(source_path);
+pub(crate) fn collect_notes_from_path(
+    source: &Path,
+    timezone: &OutputTimezone,
+) -> AppResult<Vec<NoteDto>> {
     if !source.exists() {
         return Err(AppError::Message("Source path does not exist.".to_string()));
     }
 
     let mut notes = Vec::new();
     if source.is_dir() {
-        for entry in WalkDir::new(&source)
+        for entry in WalkDir::new(source)
             .min_depth(1)
+            .sort_by_file_name()
             .into_iter()
             .filter_map(Result::ok)
             .filter(|entry| entry.file_type().is_file() && is_markdown_file(entry.path()))
         {
             let file_content = fs::read_to_string(entry.path())?;
-            let parsed = parse_notes_from_markdown(&file_content)?;
+            let parsed = parse_notes_from_markdown(&file_content, timezone)?;
             notes.extend(parsed);
         }
     } else if source.is_file() {
-        let file_content = fs::read_to_string(&source)?;
-        notes = parse_notes_from_markdown(&file_content)?;
+        let file_content = fs::read_to_string(source)?;
+        notes = parse_notes_from_markdown(&file_content, timezone)?;
     }
 
+    Ok(notes)
+}
+
+pub fn convert_to_json(
+    source_path: &str,
+    output_path: &str,
+    timezone: &OutputTimezone,
+) -> AppResult<String> {
+    let source = std::path::PathBuf::from(source_path);
+    let notes = collect_notes_from_path(&source, timezone)?;
+
     if notes.is_empty() {
         return Err(AppError::Message(
             "No markdown notes were found to convert.".to_string(),
@@ -0,0 +1,275 @@
+use url::Url;
+
+const MINUTE: i64 = 60;
+const HOUR: i64 = 60 * MINUTE;
+const DAY: i64 = 24 * HOUR;
+const WEEK: i64 = 7 * DAY;
+const MONTH: i64 = 30 * DAY;
+const YEAR: i64 = 365 * DAY;
+
+pub const DEFAULT_NOTES_LOCALE: &str = "en-US";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DateOrder {
+    DayMonthYear,
+    MonthDayYear,
+    YearMonthDay,
+}
+
+#[derive(Debug)]
+pub struct DateLocale {
+    pub language: &'static str,
+    pub now_phrases: &'static [&'static str],
+    pub yesterday_phrases: &'static [&'static str],
+    pub day_before_yesterday_phrases: &'static [&'static str],
+    pub ago_prefixes: &'static [&'static str],
+    pub ago_suffixes: &'static [&'static str],
+    pub units: &'static [(&'static str, i64)],
+    pub months: &'static [(&'static str, u32)],
+    pub numeric_replacements: &'static [(&'static str, &'static str)],
+    pub date_order: DateOrder,
+}
+
+pub static ENGLISH: DateLocale = DateLocale {
+    language: "en",
+    now_phrases: &["now"],
+    yesterday_phrases: &["yesterday"],
+    day_before_yesterday_phrases: &[],
+    ago_prefixes: &[],
+    ago_suffixes: &["ago"],
+    units: &[
+        ("sec", 1),
+        ("min", MINUTE),
+        ("hour", HOUR),
+        ("day", DAY),
+        ("week", WEEK),
+        ("month", MONTH),
+        ("year", YEAR),
+    ],
+    months: &[
+        ("jan", 1),
+        ("feb", 2),
+        ("mar", 3),
+        ("apr", 4),
+        ("may", 5),
+        ("jun", 6),
+        ("jul", 7),
+        ("aug", 8),
+        ("sep", 9),
+        ("oct", 10),
+        ("nov", 11),
+        ("dec", 12),
+    ],
+    numeric_replacements: &[],
+    date_order: DateOrder::MonthDayYear,
+};
+
+pub static CHINESE: DateLocale = DateLocale {
+    language: "zh",
+    now_phrases: &["刚刚", "刚才"],
+    yesterday_phrases: &["昨天"],
+    day_before_yesterday_phrases: &["前天"],
+    ago_prefixes: &[],
+    ago_suffixes: &["前"],
+    units: &[
+        ("秒", 1),
+        ("分钟", MINUTE),
+        ("分", MINUTE),
+        ("小时", HOUR),
+        ("天", DAY),
+        ("周", WEEK),
+        ("星期", WEEK),
+        ("个月", MONTH),
+        ("月", MONTH),
+        ("年", YEAR),
+    ],
+    months: &[],
+    numeric_replacements: &[("年", "/"), ("月", "/"), ("日", " ")],
+    date_order: DateOrder::YearMonthDay,
+};
+
+pub static RUSSIAN: DateLocale = DateLocale {
+    language: "ru",
+    now_phrases: &["сейчас", "только что"],
+    yesterday_phrases: &["вчера"],
+    day_before_yesterday_phrases: &["позавчера"],
+    ago_prefixes: &[],
+    ago_suffixes: &["назад"],
+    units: &[
+        ("сек", 1),
+        ("мин", MINUTE),
+        ("час", HOUR),
+        ("дн", DAY),
+        ("день", DAY),
+        ("недел", WEEK),
+        ("мес", MONTH),
+        ("год", YEAR),
+        ("лет", YEAR),
+    ],
+    months: &[
+        ("янв", 1),
+        ("фев", 2),
+        ("мар", 3),
+        ("апр", 4),
+        ("май", 5),
+        ("мая", 5),
+        ("июн", 6),
+        ("июл", 7),
+        ("авг", 8),
+        ("сен", 9),
+        ("окт", 10),
+        ("ноя", 11),
+        ("дек", 12),
+    ],
+    numeric_replacements: &[],
+    date_order: DateOrder::DayMonthYear,
+};
+
+pub static POLISH: DateLocale = DateLocale {
+    language: "pl",
+    now_phrases: &["teraz", "przed chwilą"],
+    yesterday_phrases: &["wczoraj"],
+    day_before_yesterday_phrases: &["przedwczoraj"],
+    ago_prefixes: &[],
+    ago_suffixes: &["temu"],
+    units: &[
+        ("sek", 1),
+        ("min", MINUTE),
+        ("godz", HOUR),
+        ("dzie", DAY),
+        ("dni", DAY),
+        ("tydz", WEEK),
+        ("tygod", WEEK),
+        ("miesi", MONTH),
+        ("rok", YEAR),
+        ("lat", YEAR),
+    ],
+    months: &[
+        ("sty", 1),
+        ("lut", 2),
+        ("mar", 3),
+        ("kwi", 4),
+        ("maj", 5),
+        ("cze", 6),
+        ("lip", 7),
+        ("sie", 8),
+        ("wrz", 9),
+        ("paź", 10),
+        ("paz", 10),
+        ("lis", 11),
+        ("gru", 12),
+    ],
+    numeric_replacements: &[],
+    date_order: DateOrder::DayMonthYear,
+};
+
+pub static SPANISH: DateLocale = DateLocale {
+    language: "es",
+    now_phrases: &["ahora"],
+    yesterday_phrases: &["ayer"],
+    day_before_yesterday_phrases: &["anteayer", "antier"],
+    ago_prefixes: &["hace"],
+    ago_suffixes: &[],
+    units: &[
+        ("seg", 1),
+        ("min", MINUTE),
+        ("hora", HOUR),
+        ("día", DAY),
+        ("dia", DAY),
+        ("semana", WEEK),
+        ("mes", MONTH),
+        ("año", YEAR),
+        ("ano", YEAR),
+    ],
+    months: &[
+        ("ene", 1),
+        ("feb", 2),
+        ("mar", 3),
+        ("abr", 4),
+        ("may", 5),
+        ("jun", 6),
+        ("jul", 7),
+        ("ago", 8),
+        ("sep", 9),
+        ("set", 9),
+        ("oct", 10),
+        ("nov", 11),
+        ("dic", 12),
+    ],
+    numeric_replacements: &[],
+    date_order: DateOrder::DayMonthYear,
+};
+
+pub static GERMAN: DateLocale = DateLocale {
+    language: "de",
+    now_phrases: &["jetzt", "gerade eben"],
+    yesterday_phrases: &["gestern"],
+    day_before_yesterday_phrases: &["vorgestern"],
+    ago_prefixes: &["vor"],
+    ago_suffixes: &[],
+    units: &[
+        ("sek", 1),
+        ("min", MINUTE),
+        ("std", HOUR),
+        ("stunde", HOUR),
+        ("tag", DAY),
+        ("woche", WEEK),
+        ("monat", MONTH),
+        ("jahr", YEAR),
+    ],
+    months: &[
+        ("jan", 1),
+        ("jän", 1),
+        ("feb", 2),
+        ("mär", 3),
+        ("mar", 3),
+        ("apr", 4),
+        ("mai", 5),
+        ("jun", 6),
+        ("jul", 7),
+        ("aug", 8),
+        ("sep", 9),
+        ("okt", 10),
+        ("nov", 11),
+        ("dez", 12),
+    ],
+    numeric_replacements: &[],
+    date_order: DateOrder::DayMonthYear,
+};
+
+static LOCALES: &[&DateLocale] = &[&ENGLISH, &CHINESE, &RUSSIAN, &POLISH, &SPANISH, &GERMAN];
+
+pub fn locale_for_code(code: &str) -> &'static DateLocale {
+    let language = code
+        .trim()
+        .split(['-', '_'])
+        .next()
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+
+    LOCALES
+        .iter()
+        .copied()
+        .find(|locale| locale.language == language)
+        .unwrap_or(&ENGLISH)
+}
+
+pub fn locale_from_notes_url(url: &Url) -> &'static DateLocale {
+    url.query_pairs()
+        .find(|(key, _)| key == "_locale")
+        .map(|(_, value)| locale_for_code(&value))
+        .unwrap_or(&ENGLISH)
+}
+
+pub fn normalize_notes_locale(code: &str) -> String {
+    let trimmed = code.trim();
+    if trimmed.is_empty()
+        || !trimmed
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        DEFAULT_NOTES_LOCALE.to_string()
+    } else {
+        trimmed.to_string()
+    }
+}
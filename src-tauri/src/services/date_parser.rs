@@ -1,48 +1,175 @@
-use chrono::{DateTime, Datelike, Local, NaiveDateTime, TimeZone, Utc};
+use chrono::{
+    DateTime, Datelike, Local, LocalResult, NaiveDate, NaiveDateTime, SecondsFormat, TimeZone, Utc,
+};
 
-use crate::error::{AppError, AppResult};
+use crate::{
+    error::{AppError, AppResult},
+    services::{
+        date_locale::{DateLocale, DateOrder, ENGLISH},
+        timezone::OutputTimezone,
+    },
+};
 
-fn local_from_parts(
+pub(crate) fn resolve_local_datetime<Tz: TimeZone>(
+    tz: &Tz,
+    naive: &NaiveDateTime,
+) -> Option<DateTime<Tz>> {
+    match tz.from_local_datetime(naive) {
+        LocalResult::Single(value) => Some(value),
+        LocalResult::Ambiguous(earliest, _) => Some(earliest),
+        // Wall-clock times skipped by a DST transition are moved past the gap.
+        LocalResult::None => tz
+            .from_local_datetime(&(*naive + chrono::Duration::hours(1)))
+            .earliest(),
+    }
+}
+
+fn datetime_from_parts<Tz: TimeZone>(
+    tz: &Tz,
     year: i32,
     month: u32,
     day: u32,
     hour: u32,
     minute: u32,
-) -> Option<DateTime<Local>> {
-    Local
-        .with_ymd_and_hms(year, month, day, hour, minute, 0)
-        .single()
-        .or_else(|| {
-            Local
-                .with_ymd_and_hms(year, month, day, hour, minute, 0)
-                .earliest()
+) -> Option<DateTime<Tz>> {
+    let naive = NaiveDate::from_ymd_opt(year, month, day)?.and_hms_opt(hour, minute, 0)?;
+    resolve_local_datetime(tz, &naive)
+}
+
+fn now_in<Tz: TimeZone>(tz: &Tz) -> DateTime<Tz> {
+    Utc::now().with_timezone(tz)
+}
+
+fn extract_time(input: &str) -> Option<(u32, u32)> {
+    let chars = input.chars().collect::<Vec<_>>();
+    for (index, character) in chars.iter().enumerate() {
+        if *character != ':' {
+            continue;
+        }
+
+        let hour_digits = chars[..index]
+            .iter()
+            .rev()
+            .take_while(|c| c.is_ascii_digit())
+            .take(2)
+            .copied()
+            .collect::<Vec<_>>();
+        let minute_digits = chars[index + 1..]
+            .iter()
+            .take_while(|c| c.is_ascii_digit())
+            .take(2)
+            .collect::<String>();
+        if hour_digits.is_empty() || minute_digits.len() != 2 {
+            continue;
+        }
+
+        let hour = hour_digits
+            .iter()
+            .rev()
+            .collect::<String>()
+            .parse::<u32>()
+            .ok()?;
+        let minute = minute_digits.parse::<u32>().ok()?;
+        if hour < 24 && minute < 60 {
+            return Some((hour, minute));
+        }
+    }
+    None
+}
+
+fn days_ago_at_time<Tz: TimeZone>(tz: &Tz, input: &str, days: i64) -> DateTime<Tz> {
+    let date = now_in(tz) - chrono::Duration::days(days);
+    extract_time(input)
+        .and_then(|(hour, minute)| {
+            datetime_from_parts(tz, date.year(), date.month(), date.day(), hour, minute)
         })
+        .unwrap_or(date)
 }
 
-fn parse_relative_time(input: &str) -> Option<DateTime<Local>> {
+fn parse_relative_time<Tz: TimeZone>(
+    tz: &Tz,
+    input: &str,
+    locale: &DateLocale,
+) -> Option<DateTime<Tz>> {
     let normalized = input.trim().to_lowercase();
-    let parts = normalized.split_whitespace().collect::<Vec<_>>();
-    if parts.len() != 3 || parts[2] != "ago" {
-        return None;
-    }
+    let body = locale
+        .ago_suffixes
+        .iter()
+        .find_map(|suffix| normalized.strip_suffix(suffix))
+        .or_else(|| {
+            locale.ago_prefixes.iter().find_map(|prefix| {
+                normalized
+                    .strip_prefix(prefix)
+                    .filter(|rest| rest.starts_with(char::is_whitespace))
+            })
+        })?
+        .trim();
 
-    let value = parts[0].parse::<i64>().ok()?;
-    let unit = parts[1];
-    let seconds = match unit {
-        "second" | "seconds" => value,
-        "minute" | "minutes" => value * 60,
-        "hour" | "hours" => value * 3600,
-        "day" | "days" => value * 86400,
-        "week" | "weeks" => value * 7 * 86400,
-        "month" | "months" => value * 30 * 86400,
-        "year" | "years" => value * 365 * 86400,
-        _ => return None,
+    let digits = body
+        .chars()
+        .take_while(|c| c.is_ascii_digit())
+        .collect::<String>();
+    let (value, unit_text) = if digits.is_empty() {
+        let mut words = body.split_whitespace();
+        let first = words.next()?;
+        if find_unit(first, locale).is_some() {
+            (1, first)
+        } else {
+            (1, words.next()?)
+        }
+    } else {
+        (digits.parse::<i64>().ok()?, body[digits.len()..].trim())
     };
 
-    Some(Local::now() - chrono::Duration::seconds(seconds))
+    let seconds = find_unit(unit_text, locale)?;
+    Some(now_in(tz) - chrono::Duration::seconds(value * seconds))
 }
 
-fn parse_simplified_md_hm(input: &str) -> Option<DateTime<Local>> {
+fn find_unit(word: &str, locale: &DateLocale) -> Option<i64> {
+    locale
+        .units
+        .iter()
+        .find(|(stem, _)| word.starts_with(stem))
+        .map(|(_, seconds)| *seconds)
+}
+
+fn order_parts(parts: &[u32], order: DateOrder, current_year: i32) -> Option<(i32, u32, u32)> {
+    match (parts, order) {
+        ([year, month, day], _) if *year >= 1000 => Some((*year as i32, *month, *day)),
+        ([day, month, year], DateOrder::DayMonthYear) => Some((*year as i32, *month, *day)),
+        ([month, day, year], DateOrder::MonthDayYear) => Some((*year as i32, *month, *day)),
+        ([year, month, day], DateOrder::YearMonthDay) => Some((*year as i32, *month, *day)),
+        ([day, month], DateOrder::DayMonthYear) => Some((current_year, *month, *day)),
+        ([month, day], _) => Some((current_year, *month, *day)),
+        _ => None,
+    }
+}
+
+fn parse_numeric_date<Tz: TimeZone>(
+    tz: &Tz,
+    input: &str,
+    order: DateOrder,
+    separators: &[char],
+) -> Option<DateTime<Tz>> {
+    let date_token = input
+        .split_whitespace()
+        .find(|token| token.contains(separators) && !token.contains(':'))?;
+    let parts = date_token
+        .split(separators)
+        .filter(|part| !part.is_empty())
+        .map(|part| part.parse::<u32>().ok())
+        .collect::<Option<Vec<_>>>()?;
+
+    let (year, month, day) = order_parts(&parts, order, now_in(tz).year())?;
+    let (hour, minute) = extract_time(input).unwrap_or((0, 0));
+    datetime_from_parts(tz, year, month, day, hour, minute)
+}
+
+fn parse_simplified_md_hm<Tz: TimeZone>(
+    tz: &Tz,
+    input: &str,
+    order: DateOrder,
+) -> Option<DateTime<Tz>> {
     let chunks = input.trim().split(' ').collect::<Vec<_>>();
     if chunks.len() != 2 {
         return None;
@@ -54,37 +181,130 @@ fn parse_simplified_md_hm(input: &str) -> Option<DateTime<Local>> {
         return None;
     }
 
-    let month = date_parts[0].parse::<u32>().ok()?;
-    let day = date_parts[1].parse::<u32>().ok()?;
+    let first = date_parts[0].parse::<u32>().ok()?;
+    let second = date_parts[1].parse::<u32>().ok()?;
     let hour = time_parts[0].parse::<u32>().ok()?;
     let minute = time_parts[1].parse::<u32>().ok()?;
 
-    local_from_parts(Local::now().year(), month, day, hour, minute)
+    let (year, month, day) = order_parts(&[first, second], order, now_in(tz).year())?;
+    datetime_from_parts(tz, year, month, day, hour, minute)
+}
+
+fn parse_month_name_date<Tz: TimeZone>(
+    tz: &Tz,
+    input: &str,
+    locale: &DateLocale,
+) -> Option<DateTime<Tz>> {
+    if locale.months.is_empty() {
+        return None;
+    }
+
+    let lower = input.to_lowercase();
+    let tokens = lower
+        .split(|c: char| c.is_whitespace() || c == ',' || c == '.' || c == '/')
+        .filter(|token| !token.is_empty() && !token.contains(':'))
+        .collect::<Vec<_>>();
+
+    let month = tokens.iter().find_map(|token| {
+        if !token.chars().all(char::is_alphabetic) {
+            return None;
+        }
+        locale
+            .months
+            .iter()
+            .find(|(stem, _)| token.starts_with(stem))
+            .map(|(_, month)| *month)
+    })?;
+
+    let numbers = tokens
+        .iter()
+        .filter_map(|token| token.parse::<u32>().ok())
+        .collect::<Vec<_>>();
+    let year = numbers
+        .iter()
+        .find(|value| **value >= 1000)
+        .map(|value| *value as i32)
+        .unwrap_or_else(|| now_in(tz).year());
+    let day = numbers
+        .iter()
+        .copied()
+        .find(|value| (1..=31).contains(value))?;
+    let (hour, minute) = extract_time(input).unwrap_or((0, 0));
+
+    datetime_from_parts(tz, year, month, day, hour, minute)
 }
 
 pub fn parse_created_date(input: &str) -> DateTime<Local> {
+    parse_created_date_with_locale(input, &ENGLISH)
+}
+
+pub fn parse_created_date_with_locale(input: &str, locale: &DateLocale) -> DateTime<Local> {
+    try_parse_created_date_with_locale(input, locale).unwrap_or_else(|_| Local::now())
+}
+
+pub fn try_parse_created_date(input: &str) -> AppResult<DateTime<Local>> {
+    try_parse_created_date_with_locale(input, &ENGLISH)
+}
+
+pub fn try_parse_created_date_with_locale(
+    input: &str,
+    locale: &DateLocale,
+) -> AppResult<DateTime<Local>> {
+    try_parse_created_date_in(input, locale, &Local)
+}
+
+pub fn try_parse_created_date_in<Tz: TimeZone>(
+    input: &str,
+    locale: &DateLocale,
+    tz: &Tz,
+) -> AppResult<DateTime<Tz>> {
+    let unparsed = || AppError::UnparsedDate {
+        raw: input.to_string(),
+    };
+
     let normalized = input.trim();
     if normalized.is_empty() {
-        return Local::now();
+        return Err(unparsed());
     }
 
     let lower = normalized.to_lowercase();
-    if lower.contains("now") {
-        return Local::now();
+    if locale
+        .now_phrases
+        .iter()
+        .any(|phrase| lower.contains(phrase))
+    {
+        return Ok(now_in(tz));
     }
 
-    if lower.contains("yesterday") {
-        return Local::now() - chrono::Duration::days(1);
+    if locale
+        .day_before_yesterday_phrases
+        .iter()
+        .any(|phrase| lower.contains(phrase))
+    {
+        return Ok(days_ago_at_time(tz, &lower, 2));
     }
 
-    if lower.ends_with("ago") {
-        if let Some(parsed) = parse_relative_time(&lower) {
-            return parsed;
-        }
+    if locale
+        .yesterday_phrases
+        .iter()
+        .any(|phrase| lower.contains(phrase))
+    {
+        return Ok(days_ago_at_time(tz, &lower, 1));
+    }
+
+    if let Some(parsed) = parse_relative_time(tz, &lower, locale) {
+        return Ok(parsed);
+    }
+
+    let mut replaced = normalized.to_string();
+    for (from, to) in locale.numeric_replacements {
+        replaced = replaced.replace(from, to);
     }
+    let normalized = replaced.split_whitespace().collect::<Vec<_>>().join(" ");
+    let normalized = normalized.as_str();
 
-    if let Some(parsed) = parse_simplified_md_hm(normalized) {
-        return parsed;
+    if let Some(parsed) = parse_simplified_md_hm(tz, normalized, locale.date_order) {
+        return Ok(parsed);
     }
 
     let formats = [
@@ -98,40 +318,65 @@ pub fn parse_created_date(input: &str) -> DateTime<Local> {
     ];
 
     for format in formats {
-        if let Ok(naive) = NaiveDateTime::parse_from_str(normalized, format) {
-            if let Some(local) = Local.from_local_datetime(&naive).single() {
-                return local;
-            }
-            if let Some(local) = Local.from_local_datetime(&naive).earliest() {
-                return local;
-            }
+        if let Some(parsed) = NaiveDateTime::parse_from_str(normalized, format)
+            .ok()
+            .and_then(|naive| resolve_local_datetime(tz, &naive))
+        {
+            return Ok(parsed);
         }
     }
 
     if let Ok(parsed_rfc) = DateTime::parse_from_rfc3339(normalized) {
-        return parsed_rfc.with_timezone(&Local);
+        return Ok(parsed_rfc.with_timezone(tz));
     }
 
-    Local::now()
+    if let Some(parsed) = parse_numeric_date(tz, normalized, locale.date_order, &['.', '-', '/']) {
+        return Ok(parsed);
+    }
+
+    if let Some(parsed) = parse_month_name_date(tz, normalized, locale) {
+        return Ok(parsed);
+    }
+
+    Err(unparsed())
 }
 
 pub fn parse_created_line_to_rfc3339(input: &str) -> AppResult<String> {
+    parse_created_line_to_rfc3339_in(input, &OutputTimezone::Local)
+}
+
+pub fn parse_created_line_to_rfc3339_in(
+    input: &str,
+    timezone: &OutputTimezone,
+) -> AppResult<String> {
     let cleaned = input.trim();
 
     if let Ok(parsed_rfc) = DateTime::parse_from_rfc3339(cleaned) {
-        return Ok(parsed_rfc.with_timezone(&Utc).to_rfc3339());
+        return Ok(timezone.format_rfc3339(&parsed_rfc));
     }
 
-    let parsed = parse_created_date(cleaned);
-    Ok(parsed.with_timezone(&Utc).to_rfc3339())
+    let parsed = timezone.parse_created_date(cleaned, &ENGLISH)?;
+    Ok(timezone.format_rfc3339(&parsed))
 }
 
-pub fn parse_markdown_created_line(input: &str) -> AppResult<String> {
-    let cleaned = input
+fn markdown_date_value<'a>(input: &'a str, label: &str) -> &'a str {
+    input
         .trim()
-        .trim_start_matches("*Created at:")
+        .trim_start_matches(label)
         .trim_end_matches('*')
-        .trim();
+        .trim()
+}
+
+pub(crate) fn markdown_created_value(input: &str) -> &str {
+    markdown_date_value(input, "*Created at:")
+}
+
+pub(crate) fn markdown_modified_value(input: &str) -> &str {
+    markdown_date_value(input, "*Modified at:")
+}
+
+pub fn parse_markdown_created_line(input: &str, timezone: &OutputTimezone) -> AppResult<String> {
+    let cleaned = markdown_created_value(input);
 
     if cleaned.is_empty() {
         return Err(AppError::Message(
@@ -139,5 +384,41 @@ pub fn parse_markdown_created_line(input: &str) -> AppResult<String> {
         ));
     }
 
-    parse_created_line_to_rfc3339(cleaned)
+    parse_created_line_to_rfc3339_in(cleaned, timezone)
+}
+
+pub fn parse_markdown_modified_line(input: &str, timezone: &OutputTimezone) -> AppResult<String> {
+    let cleaned = markdown_modified_value(input);
+
+    if cleaned.is_empty() {
+        return Err(AppError::Message(
+            "Invalid note format: missing modified date value.".to_string(),
+        ));
+    }
+
+    parse_created_line_to_rfc3339_in(cleaned, timezone)
+}
+
+pub(crate) fn parse_filter_date(value: Option<&str>) -> AppResult<Option<String>> {
+    let Some(trimmed) = value.map(str::trim).filter(|value| !value.is_empty()) else {
+        return Ok(None);
+    };
+
+    if let Ok(parsed) = DateTime::parse_from_rfc3339(trimmed) {
+        return Ok(Some(
+            parsed
+                .with_timezone(&Utc)
+                .to_rfc3339_opts(SecondsFormat::Secs, true),
+        ));
+    }
+    if let Ok(date) = NaiveDate::parse_from_str(trimmed, "%Y-%m-%d") {
+        let start_of_day = date.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc();
+        return Ok(Some(
+            start_of_day.to_rfc3339_opts(SecondsFormat::Secs, true),
+        ));
+    }
+
+    Err(AppError::Message(format!(
+        "Invalid date filter '{trimmed}'. Use YYYY-MM-DD or RFC 3339."
+    )))
 }
@@ -0,0 +1,304 @@
+use std::{
+    collections::HashSet,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use similar::TextDiff;
+
+use crate::{
+    db::{connection::Database, sessions as sessions_db},
+    error::{AppError, AppResult},
+    models::{ExportDiffReport, ModifiedNoteEntry, NoteDto, NoteSummary},
+    services::{converter::collect_notes_from_path, timezone::OutputTimezone},
+};
+
+const PREVIEW_MAX_CHARS: usize = 80;
+const DIFF_CONTEXT_LINES: usize = 3;
+/// Minimum line similarity for a removed and an added note to be reported as one modified note.
+const MODIFIED_SIMILARITY_THRESHOLD: f32 = 0.5;
+
+pub(crate) fn resolve_export_source(db: &Database, source: &str) -> AppResult<PathBuf> {
+    let trimmed = source.trim();
+    if trimmed.is_empty() {
+        return Err(AppError::Message(
+            "Export source cannot be empty.".to_string(),
+        ));
+    }
+
+    if let Some(session) = sessions_db::fetch_session_by_id(db, trimmed)? {
+        return Ok(PathBuf::from(session.output_path));
+    }
+
+    Ok(PathBuf::from(trimmed))
+}
+
+pub(crate) fn note_preview(content: &str) -> String {
+    let first_line = content
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .unwrap_or_default();
+
+    if first_line.chars().count() > PREVIEW_MAX_CHARS {
+        let truncated = first_line
+            .chars()
+            .take(PREVIEW_MAX_CHARS)
+            .collect::<String>();
+        format!("{truncated}...")
+    } else {
+        first_line.to_string()
+    }
+}
+
+pub(crate) fn summarize_note(note: &NoteDto) -> NoteSummary {
+    NoteSummary {
+        id: note.id.clone(),
+        creation_date: note.creation_date.clone(),
+        preview: note_preview(&note.content),
+    }
+}
+
+pub(crate) fn unified_note_diff(left: &str, right: &str) -> String {
+    TextDiff::from_lines(left, right)
+        .unified_diff()
+        .context_radius(DIFF_CONTEXT_LINES)
+        .header("left", "right")
+        .to_string()
+}
+
+fn similarity(left: &str, right: &str) -> f32 {
+    TextDiff::from_lines(left, right).ratio()
+}
+
+pub(crate) fn diff_notes(
+    left_source: &str,
+    right_source: &str,
+    left: &[NoteDto],
+    right: &[NoteDto],
+) -> ExportDiffReport {
+    let right_ids = right
+        .iter()
+        .map(|note| note.id.as_str())
+        .collect::<HashSet<_>>();
+    let left_ids = left
+        .iter()
+        .map(|note| note.id.as_str())
+        .collect::<HashSet<_>>();
+
+    let unchanged_count = left
+        .iter()
+        .filter(|note| right_ids.contains(note.id.as_str()))
+        .count() as u32;
+
+    let left_only = left
+        .iter()
+        .filter(|note| !right_ids.contains(note.id.as_str()))
+        .collect::<Vec<_>>();
+    let mut right_only = right
+        .iter()
+        .filter(|note| !left_ids.contains(note.id.as_str()))
+        .collect::<Vec<_>>();
+
+    let mut removed = Vec::new();
+    let mut modified = Vec::new();
+    for left_note in left_only {
+        let best_match = right_only
+            .iter()
+            .enumerate()
+            .filter(|(_, right_note)| {
+                !left_note.creation_date.trim().is_empty()
+                    && right_note.creation_date == left_note.creation_date
+            })
+            .map(|(index, right_note)| (index, similarity(&left_note.content, &right_note.content)))
+            .filter(|(_, ratio)| *ratio >= MODIFIED_SIMILARITY_THRESHOLD)
+            .max_by(|a, b| a.1.total_cmp(&b.1));
+
+        match best_match {
+            Some((index, _)) => {
+                let right_note = right_only.remove(index);
+                modified.push(ModifiedNoteEntry {
+                    left_id: left_note.id.clone(),
+                    right_id: right_note.id.clone(),
+                    creation_date: right_note.creation_date.clone(),
+                    preview: note_preview(&right_note.content),
+                    diff: unified_note_diff(&left_note.content, &right_note.content),
+                });
+            }
+            None => removed.push(summarize_note(left_note)),
+        }
+    }
+
+    let added = right_only
+        .into_iter()
+        .map(summarize_note)
+        .collect::<Vec<_>>();
+
+    ExportDiffReport {
+        left_source: left_source.to_string(),
+        right_source: right_source.to_string(),
+        left_count: left.len() as u32,
+        right_count: right.len() as u32,
+        unchanged_count,
+        added,
+        removed,
+        modified,
+    }
+}
+
+fn escape_html(input: &str) -> String {
+    input
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+pub(crate) fn render_markdown_summary(report: &ExportDiffReport) -> String {
+    let mut output = String::new();
+    output.push_str("# Export diff\n\n");
+    output.push_str(&format!(
+        "- Left: `{}` ({} notes)\n",
+        report.left_source, report.left_count
+    ));
+    output.push_str(&format!(
+        "- Right: `{}` ({} notes)\n",
+        report.right_source, report.right_count
+    ));
+    output.push_str(&format!(
+        "- Added: {}, removed: {}, modified: {}, unchanged: {}\n",
+        report.added.len(),
+        report.removed.len(),
+        report.modified.len(),
+        report.unchanged_count
+    ));
+
+    for (heading, entries) in [("Added", &report.added), ("Removed", &report.removed)] {
+        if entries.is_empty() {
+            continue;
+        }
+        output.push_str(&format!("\n## {heading}\n\n"));
+        for entry in entries {
+            output.push_str(&format!("- {} ({})\n", entry.preview, entry.creation_date));
+        }
+    }
+
+    if !report.modified.is_empty() {
+        output.push_str("\n## Modified\n");
+        for entry in &report.modified {
+            output.push_str(&format!(
+                "\n### {} ({})\n\n```diff\n{}```\n",
+                entry.preview, entry.creation_date, entry.diff
+            ));
+        }
+    }
+
+    output
+}
+
+pub(crate) fn render_html_summary(report: &ExportDiffReport) -> String {
+    let mut output = String::new();
+    output.push_str("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n");
+    output.push_str("<title>Export diff</title>\n</head>\n<body>\n<h1>Export diff</h1>\n<ul>\n");
+    output.push_str(&format!(
+        "<li>Left: <code>{}</code> ({} notes)</li>\n",
+        escape_html(&report.left_source),
+        report.left_count
+    ));
+    output.push_str(&format!(
+        "<li>Right: <code>{}</code> ({} notes)</li>\n",
+        escape_html(&report.right_source),
+        report.right_count
+    ));
+    output.push_str(&format!(
+        "<li>Added: {}, removed: {}, modified: {}, unchanged: {}</li>\n</ul>\n",
+        report.added.len(),
+        report.removed.len(),
+        report.modified.len(),
+        report.unchanged_count
+    ));
+
+    for (heading, entries) in [("Added", &report.added), ("Removed", &report.removed)] {
+        if entries.is_empty() {
+            continue;
+        }
+        output.push_str(&format!("<h2>{heading}</h2>\n<ul>\n"));
+        for entry in entries {
+            output.push_str(&format!(
+                "<li>{} ({})</li>\n",
+                escape_html(&entry.preview),
+                escape_html(&entry.creation_date)
+            ));
+        }
+        output.push_str("</ul>\n");
+    }
+
+    if !report.modified.is_empty() {
+        output.push_str("<h2>Modified</h2>\n");
+        for entry in &report.modified {
+            output.push_str(&format!(
+                "<h3>{} ({})</h3>\n<pre>{}</pre>\n",
+                escape_html(&entry.preview),
+                escape_html(&entry.creation_date),
+                escape_html(&entry.diff)
+            ));
+        }
+    }
+
+    output.push_str("</body>\n</html>\n");
+    output
+}
+
+fn is_html_file(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| {
+            let lowered = ext.to_ascii_lowercase();
+            lowered == "html" || lowered == "htm"
+        })
+        .unwrap_or(false)
+}
+
+pub fn write_summary(report: &ExportDiffReport, summary_path: &Path) -> AppResult<()> {
+    let summary = if is_html_file(summary_path) {
+        render_html_summary(report)
+    } else {
+        render_markdown_summary(report)
+    };
+
+    if let Some(parent) = summary_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(summary_path, summary)?;
+    Ok(())
+}
+
+pub fn diff_exports(
+    db: &Database,
+    left: &str,
+    right: &str,
+    summary_path: Option<&str>,
+    timezone: &OutputTimezone,
+) -> AppResult<ExportDiffReport> {
+    let left_path = resolve_export_source(db, left)?;
+    let right_path = resolve_export_source(db, right)?;
+
+    let left_notes = collect_notes_from_path(&left_path, timezone)?;
+    let right_notes = collect_notes_from_path(&right_path, timezone)?;
+
+    let report = diff_notes(
+        &left_path.to_string_lossy(),
+        &right_path.to_string_lossy(),
+        &left_notes,
+        &right_notes,
+    );
+
+    if let Some(path) = summary_path
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+    {
+        write_summary(&report, Path::new(path))?;
+    }
+
+    Ok(report)
+}
@@ -0,0 +1,187 @@
+use std::{
+    collections::{hash_map::DefaultHasher, BTreeMap, HashSet},
+    hash::{Hash, Hasher},
+};
+
+use crate::{
+    db::connection::Database,
+    error::{AppError, AppResult},
+    models::{DuplicateCluster, DuplicateReport, NoteDto},
+    services::{
+        converter::collect_notes_from_path,
+        diff::{resolve_export_source, summarize_note},
+        timezone::OutputTimezone,
+    },
+};
+
+const SHINGLE_SIZE: usize = 5;
+const MINHASH_PERMUTATIONS: u64 = 64;
+pub const DEFAULT_SIMILARITY_THRESHOLD: f32 = 0.8;
+
+pub(crate) fn normalize_note_text(content: &str) -> String {
+    content
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { ' ' })
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+pub(crate) fn shingles(normalized: &str) -> HashSet<String> {
+    let chars = normalized.chars().collect::<Vec<_>>();
+    if chars.is_empty() {
+        return HashSet::new();
+    }
+    if chars.len() <= SHINGLE_SIZE {
+        return HashSet::from([normalized.to_string()]);
+    }
+
+    chars
+        .windows(SHINGLE_SIZE)
+        .map(|window| window.iter().collect::<String>())
+        .collect()
+}
+
+fn seeded_hash(seed: u64, shingle: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    seed.hash(&mut hasher);
+    shingle.hash(&mut hasher);
+    hasher.finish()
+}
+
+pub(crate) fn minhash_signature(shingles: &HashSet<String>) -> Vec<u64> {
+    (0..MINHASH_PERMUTATIONS)
+        .map(|seed| {
+            shingles
+                .iter()
+                .map(|shingle| seeded_hash(seed, shingle))
+                .min()
+                .unwrap_or(u64::MAX)
+        })
+        .collect()
+}
+
+pub(crate) fn estimate_similarity(left: &[u64], right: &[u64]) -> f32 {
+    if left.is_empty() || left.len() != right.len() {
+        return 0.0;
+    }
+
+    let matching = left
+        .iter()
+        .zip(right)
+        .filter(|(left_value, right_value)| left_value == right_value)
+        .count();
+    matching as f32 / left.len() as f32
+}
+
+fn find_root(parents: &mut [usize], index: usize) -> usize {
+    let mut root = index;
+    while parents[root] != root {
+        root = parents[root];
+    }
+
+    let mut current = index;
+    while parents[current] != root {
+        let next = parents[current];
+        parents[current] = root;
+        current = next;
+    }
+    root
+}
+
+pub(crate) fn find_duplicates(source: &str, notes: &[NoteDto], threshold: f32) -> DuplicateReport {
+    let mut groups = BTreeMap::<&str, Vec<&NoteDto>>::new();
+    for note in notes {
+        groups.entry(note.id.as_str()).or_default().push(note);
+    }
+
+    let exact_clusters = groups
+        .values()
+        .filter(|group| group.len() > 1)
+        .map(|group| DuplicateCluster {
+            similarity: 1.0,
+            notes: group.iter().map(|note| summarize_note(note)).collect(),
+        })
+        .collect::<Vec<_>>();
+
+    let representatives = groups
+        .values()
+        .map(|group| group[0])
+        .filter(|note| !note.content.trim().is_empty())
+        .collect::<Vec<_>>();
+    let signatures = representatives
+        .iter()
+        .map(|note| minhash_signature(&shingles(&normalize_note_text(&note.content))))
+        .collect::<Vec<_>>();
+
+    let mut parents = (0..representatives.len()).collect::<Vec<_>>();
+    let mut min_similarity = vec![1.0_f32; representatives.len()];
+    for left in 0..representatives.len() {
+        for right in (left + 1)..representatives.len() {
+            let similarity = estimate_similarity(&signatures[left], &signatures[right]);
+            if similarity < threshold {
+                continue;
+            }
+
+            let left_root = find_root(&mut parents, left);
+            let right_root = find_root(&mut parents, right);
+            let merged = min_similarity[left_root]
+                .min(min_similarity[right_root])
+                .min(similarity);
+            if left_root != right_root {
+                parents[right_root] = left_root;
+            }
+            min_similarity[left_root] = merged;
+        }
+    }
+
+    let mut clusters = BTreeMap::<usize, Vec<usize>>::new();
+    for index in 0..representatives.len() {
+        let root = find_root(&mut parents, index);
+        clusters.entry(root).or_default().push(index);
+    }
+
+    let near_clusters = clusters
+        .into_iter()
+        .filter(|(_, members)| members.len() > 1)
+        .map(|(root, members)| DuplicateCluster {
+            similarity: min_similarity[root],
+            notes: members
+                .into_iter()
+                .map(|index| summarize_note(representatives[index]))
+                .collect(),
+        })
+        .collect::<Vec<_>>();
+
+    DuplicateReport {
+        source: source.to_string(),
+        notes_count: notes.len() as u32,
+        exact_clusters,
+        near_clusters,
+    }
+}
+
+pub fn find_duplicate_notes(
+    db: &Database,
+    source: &str,
+    threshold: Option<f32>,
+    timezone: &OutputTimezone,
+) -> AppResult<DuplicateReport> {
+    let threshold = threshold.unwrap_or(DEFAULT_SIMILARITY_THRESHOLD);
+    if !(0.0..=1.0).contains(&threshold) {
+        return Err(AppError::Message(
+            "Similarity threshold must be between 0 and 1.".to_string(),
+        ));
+    }
+
+    let source_path = resolve_export_source(db, source)?;
+    let notes = collect_notes_from_path(&source_path, timezone)?;
+
+    Ok(find_duplicates(
+        &source_path.to_string_lossy(),
+        &notes,
+        threshold,
+    ))
+}
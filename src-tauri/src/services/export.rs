@@ -0,0 +1,888 @@
+use std::{
+    collections::{BTreeMap, BTreeSet, HashSet},
+    path::{Path, PathBuf},
+    sync::{Mutex, MutexGuard},
+    thread,
+    time::{Duration, Instant},
+};
+
+use chrono::{DateTime, FixedOffset, Utc};
+use sha2::{Digest, Sha256};
+#[cfg(feature = "gui")]
+use tauri::Emitter;
+use uuid::Uuid;
+
+use crate::{
+    db::{connection::Database, search::NoteIndexEntry, sessions as sessions_db},
+    error::{AppError, AppResult},
+    models::{
+        AppSettings, DryRunCollision, DryRunUnparsedDate, ExportCompleteEvent, ExportDryRunReport,
+        ExportErrorEvent, ExportFilterSpec, ExportJobSettings, ExportProgressEvent,
+        ScrapedNoteInput, Session, UnparsedDatePolicy,
+    },
+    services::{
+        converter, date_locale, export_filter::ExportFilter, files, markdown, scraper, search,
+        timezone::OutputTimezone,
+    },
+    state::{ActiveExportState, AppState},
+};
+
+pub const DEFAULT_TIMESTAMP_FORMAT: &str = "dd-MM-yyyy_HH-mm-ss";
+pub const DEFAULT_CREATED_DATE_FORMAT: &str = "dd/MM/yyyy HH:mm";
+
+const CANCELLED_MESSAGE: &str = "Export cancelled by user.";
+const STATUS_RUNNING: &str = "running";
+const STATUS_PAUSED: &str = "paused";
+/// How often a paused scrape loop checks whether it may continue.
+pub const PAUSE_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Receives export lifecycle events; implemented by the Tauri app, the CLI and tests.
+pub trait ExportEvents {
+    fn progress(&self, event: ExportProgressEvent);
+    fn complete(&self, event: ExportCompleteEvent);
+    fn error(&self, event: ExportErrorEvent);
+}
+
+#[derive(Debug, Clone)]
+pub enum ExportEvent {
+    Progress(ExportProgressEvent),
+    Complete(ExportCompleteEvent),
+    Error(ExportErrorEvent),
+}
+
+/// Collects events in memory, for headless runs that inspect them afterwards.
+#[derive(Debug, Default)]
+pub struct MemoryEvents {
+    events: Mutex<Vec<ExportEvent>>,
+}
+
+impl MemoryEvents {
+    pub fn events(&self) -> Vec<ExportEvent> {
+        self.events
+            .lock()
+            .map(|events| events.clone())
+            .unwrap_or_default()
+    }
+
+    fn push(&self, event: ExportEvent) {
+        if let Ok(mut events) = self.events.lock() {
+            events.push(event);
+        }
+    }
+}
+
+impl ExportEvents for MemoryEvents {
+    fn progress(&self, event: ExportProgressEvent) {
+        self.push(ExportEvent::Progress(event));
+    }
+
+    fn complete(&self, event: ExportCompleteEvent) {
+        self.push(ExportEvent::Complete(event));
+    }
+
+    fn error(&self, event: ExportErrorEvent) {
+        self.push(ExportEvent::Error(event));
+    }
+}
+
+/// Forwards export events to the frontend as Tauri events.
+#[cfg(feature = "gui")]
+pub struct TauriEvents(pub tauri::AppHandle);
+
+#[cfg(feature = "gui")]
+impl ExportEvents for TauriEvents {
+    fn progress(&self, event: ExportProgressEvent) {
+        let _ = self.0.emit("export:progress", event);
+    }
+
+    fn complete(&self, event: ExportCompleteEvent) {
+        let _ = self.0.emit("export:complete", event);
+    }
+
+    fn error(&self, event: ExportErrorEvent) {
+        let _ = self.0.emit("export:error", event);
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ExportOptions {
+    pub domain: String,
+    pub output_dir: PathBuf,
+    pub split: bool,
+    pub name_by_title: bool,
+    pub timestamp_format: String,
+    pub created_date_format: String,
+    pub export_images: bool,
+    pub skip_duplicates: bool,
+    pub unparsed_date_policy: UnparsedDatePolicy,
+    pub notes_locale: String,
+    pub output_timezone: OutputTimezone,
+    pub filter: ExportFilterSpec,
+    /// Runs the scrape and plans every file without writing or recording a session.
+    pub dry_run: bool,
+}
+
+impl ExportOptions {
+    /// Resolves a job against the app settings, which supply the locale, the time zone and
+    /// the output directory when the job has none.
+    pub fn from_job(job: &ExportJobSettings, settings: &AppSettings) -> Self {
+        let output_dir = if job.output_dir.trim().is_empty() {
+            settings.default_export_dir.clone()
+        } else {
+            job.output_dir.clone()
+        };
+        Self {
+            domain: job.domain.clone(),
+            output_dir: output_dir.into(),
+            split: job.split,
+            name_by_title: job.name_by_title,
+            timestamp_format: job.timestamp_format.clone(),
+            created_date_format: job.created_date_format.clone(),
+            export_images: job.export_images,
+            skip_duplicates: job.skip_duplicates,
+            unparsed_date_policy: job.unparsed_date_policy,
+            notes_locale: settings.notes_locale.clone(),
+            output_timezone: OutputTimezone::from_settings(settings),
+            filter: job.filter.clone(),
+            dry_run: job.dry_run,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ExportScope {
+    pub export_images: bool,
+    pub filter: ExportFilter,
+}
+
+#[derive(Debug, Default)]
+pub struct NoteDetailsTimestamps {
+    pub created: Option<i64>,
+    pub modified: Option<i64>,
+}
+
+/// Collects what a dry run would have written.
+#[derive(Debug, Clone, Default)]
+pub struct DryRunPlan {
+    pub report: ExportDryRunReport,
+    /// Planned files keyed by their case-folded name without the trailing counter.
+    names: BTreeMap<String, DryRunCollision>,
+}
+
+impl DryRunPlan {
+    fn unparsed_date(&mut self, note: &ScrapedNoteInput) {
+        self.report.unparsed_dates.push(DryRunUnparsedDate {
+            title: display_title(&note.title).to_string(),
+            created: note.created_string.trim().to_string(),
+        });
+    }
+
+    fn note(
+        &mut self,
+        folder_dir: Option<&str>,
+        file_name: Option<&str>,
+        output_root: &Path,
+        markdown_len: usize,
+    ) {
+        self.report.markdown_bytes += markdown_len as u64;
+        let Some(file_name) = file_name else {
+            if self.report.files.is_empty() {
+                let name = output_root
+                    .file_name()
+                    .map(|name| name.to_string_lossy().to_string())
+                    .unwrap_or_default();
+                self.report.files.push(name);
+            }
+            return;
+        };
+
+        let path = match folder_dir {
+            Some(folder_dir) => format!("{folder_dir}/{file_name}"),
+            None => file_name.to_string(),
+        };
+        let stem = path.trim_end_matches(".md");
+        let name = stem.rsplit_once('_').map_or(stem, |(name, _)| name);
+        self.names
+            .entry(name.to_lowercase())
+            .or_insert_with(|| DryRunCollision {
+                name: name.to_string(),
+                files: Vec::new(),
+            })
+            .files
+            .push(path.clone());
+        self.report.files.push(path);
+    }
+
+    /// The report with the counters of `export` and the collisions found so far.
+    pub fn report_for(&self, export: &ActiveExportState) -> ExportDryRunReport {
+        ExportDryRunReport {
+            notes_count: export.notes_count,
+            images_count: export.images_count,
+            duplicates_skipped: export.duplicates_skipped,
+            filtered_count: export.filtered_count,
+            collisions: self
+                .names
+                .values()
+                .filter(|collision| collision.files.len() > 1)
+                .cloned()
+                .collect(),
+            ..self.report.clone()
+        }
+    }
+}
+
+pub fn now_utc() -> String {
+    Utc::now().to_rfc3339()
+}
+
+/// Creates the output folders and the running session row for a new export; a dry run
+/// creates neither.
+pub fn begin_export(db: &Database, options: &ExportOptions) -> AppResult<ActiveExportState> {
+    let filter = ExportFilter::compile(&options.filter)?;
+    let session_id = Uuid::new_v4().to_string();
+    let stamp = options
+        .output_timezone
+        .now()
+        .format("%d-%m-%Y_%H-%M-%S")
+        .to_string();
+
+    let output_root = if options.split {
+        options.output_dir.join(format!("exported_notes_{stamp}"))
+    } else {
+        options
+            .output_dir
+            .join(format!("exported_notes_{stamp}.md"))
+    };
+
+    let images_dir = if options.split {
+        output_root.join("images")
+    } else {
+        options.output_dir.join(format!("images_{stamp}"))
+    };
+    let images_dir_name = images_dir
+        .file_name()
+        .and_then(|name| name.to_str())
+        .map(ToString::to_string);
+
+    if !options.dry_run {
+        if options.split {
+            std::fs::create_dir_all(&output_root)?;
+        } else {
+            files::ensure_parent(&output_root)?;
+        }
+        if options.export_images {
+            std::fs::create_dir_all(&images_dir)?;
+        }
+    }
+
+    let session = Session {
+        id: session_id.clone(),
+        domain: options.domain.clone(),
+        started_at: now_utc(),
+        completed_at: None,
+        status: STATUS_RUNNING.to_string(),
+        notes_count: 0,
+        images_count: 0,
+        split_mode: options.split,
+        name_by_title: options.name_by_title,
+        timestamp_format: options.timestamp_format.clone(),
+        images_enabled: options.export_images,
+        output_path: output_root.to_string_lossy().to_string(),
+        images_dir_name,
+        error_message: None,
+        unparsed_dates: 0,
+        pinned: false,
+        label: None,
+        comment: None,
+        account_hint: None,
+        folders: Vec::new(),
+        filtered_count: 0,
+    };
+
+    if !options.dry_run {
+        sessions_db::insert_session(db, &session)?;
+    }
+
+    Ok(ActiveExportState {
+        session_id: session_id.clone(),
+        split: options.split,
+        name_by_title: options.name_by_title,
+        chrono_timestamp_format: markdown::dotnet_to_chrono_format(&options.timestamp_format),
+        chrono_created_date_format: markdown::dotnet_to_chrono_created_date_format(
+            &options.created_date_format,
+        ),
+        date_locale: date_locale::locale_for_code(&date_locale::normalize_notes_locale(
+            &options.notes_locale,
+        )),
+        output_timezone: options.output_timezone,
+        export_images: options.export_images,
+        skip_duplicates: options.skip_duplicates,
+        unparsed_date_policy: options.unparsed_date_policy,
+        output_root,
+        images_dir,
+        total_notes: 0,
+        notes_count: 0,
+        images_count: 0,
+        duplicates_skipped: 0,
+        unparsed_dates: 0,
+        filtered_count: 0,
+        filter,
+        dry_run: options.dry_run.then(DryRunPlan::default),
+        current_folder: None,
+        folders: BTreeSet::new(),
+        seen_content_hashes: HashSet::new(),
+        pending_index_entries: Vec::new(),
+        started_at: None,
+        paused_at: None,
+        paused_for: Duration::ZERO,
+        auth_window_label: scraper::auth_window_label(&session_id),
+    })
+}
+
+pub fn progress_event(
+    export: &ActiveExportState,
+    last_title: &str,
+    log_line: &str,
+) -> ExportProgressEvent {
+    let current = export.notes_count + export.duplicates_skipped + export.filtered_count;
+    let total = export.total_notes.max(current.max(1));
+    ExportProgressEvent {
+        session_id: export.session_id.clone(),
+        current,
+        total,
+        last_title: last_title.to_string(),
+        notes_count: export.notes_count,
+        images_count: export.images_count,
+        filtered_count: export.filtered_count,
+        log_line: log_line.to_string(),
+    }
+}
+
+pub fn set_total(export: &mut ActiveExportState, total: u32) -> String {
+    export.total_notes = total.max(1);
+    if export.started_at.is_none() {
+        export.started_at = Some(Instant::now());
+        // Only pauses after the clock starts count against it.
+        export.paused_for = Duration::ZERO;
+        if export.paused_at.is_some() {
+            export.paused_at = export.started_at;
+        }
+    }
+    format!("Discovered {} notes.", export.total_notes)
+}
+
+fn display_title(title: &str) -> &str {
+    if title.trim().is_empty() {
+        "No title"
+    } else {
+        title.trim()
+    }
+}
+
+fn skip_filtered(export: &mut ActiveExportState, note: &ScrapedNoteInput, reason: &str) -> String {
+    export.filtered_count += 1;
+    format!(
+        "Skipped note filtered by {reason}: {}",
+        display_title(&note.title)
+    )
+}
+
+/// File name a split export gives the note; names only differ by the trailing counter when
+/// titles or timestamps repeat.
+fn note_file_name(
+    export: &ActiveExportState,
+    note: &ScrapedNoteInput,
+    created_at: Option<&DateTime<FixedOffset>>,
+    note_index: u32,
+) -> String {
+    let trimmed_title = note.title.trim();
+    let mut file_name = if export.name_by_title && !trimmed_title.is_empty() {
+        markdown::sanitize_filename(&format!("{}_{:04}.md", trimmed_title, note_index))
+    } else {
+        let stamp = created_at
+            .map(|created_at| {
+                created_at
+                    .format(&export.chrono_timestamp_format)
+                    .to_string()
+            })
+            .unwrap_or_else(|| "undated".to_string());
+        markdown::sanitize_filename(&format!("note_{}_{:04}.md", stamp, note_index))
+    };
+    if !file_name.to_ascii_lowercase().ends_with(".md") {
+        file_name.push_str(".md");
+    }
+    file_name
+}
+
+/// Directory of a folder in a split export; a folder named like the images directory gets a
+/// suffix so its notes stay apart from the images.
+fn folder_dir_name(export: &ActiveExportState, folder: &str) -> String {
+    let name = markdown::sanitize_filename(folder);
+    let images_dir_name = export.images_dir.file_name().and_then(|name| name.to_str());
+    if images_dir_name.is_some_and(|images| images.eq_ignore_ascii_case(&name)) {
+        format!("{name} (folder)")
+    } else {
+        name
+    }
+}
+
+/// Writes one scraped note (and its images) to disk and returns the progress log line.
+/// Notes rejected by the export filter are counted and nothing is written for them; a dry
+/// run records the planned files instead of writing them.
+pub fn append_note(
+    db: &Database,
+    export: &mut ActiveExportState,
+    note: &ScrapedNoteInput,
+    details_timestamps: NoteDetailsTimestamps,
+) -> AppResult<String> {
+    if let Some(reason) = export.filter.rejects_note(note) {
+        return Ok(skip_filtered(export, note, reason));
+    }
+
+    let timezone = export.output_timezone;
+    let modified_at = details_timestamps
+        .modified
+        .or(note.modified_timestamp)
+        .and_then(|timestamp| timezone.datetime_from_unix_timestamp(timestamp));
+
+    let parsed_created_at = match details_timestamps
+        .created
+        .and_then(|timestamp| timezone.datetime_from_unix_timestamp(timestamp))
+    {
+        Some(created_at) => Ok(created_at),
+        None => timezone.parse_created_date(&note.created_string, export.date_locale),
+    };
+    // The date range sees the parsed date only, never the unparsed-date fallback.
+    if let Some(reason) = export
+        .filter
+        .rejects_created(parsed_created_at.as_ref().ok())
+    {
+        return Ok(skip_filtered(export, note, reason));
+    }
+    // Only notes that pass every filter claim their content hash.
+    if export.skip_duplicates && !note.content.trim().is_empty() {
+        let content_hash = converter::sha256_hex(note.content.trim());
+        if !export.seen_content_hashes.insert(content_hash) {
+            export.duplicates_skipped += 1;
+            return Ok(format!(
+                "Skipped duplicate note: {}",
+                display_title(&note.title)
+            ));
+        }
+    }
+
+    let mut unparsed_date = false;
+    let created_at = match parsed_created_at {
+        Ok(created_at) => Some(created_at),
+        Err(error) => {
+            unparsed_date = true;
+            export.unparsed_dates += 1;
+            if let Some(plan) = export.dry_run.as_mut() {
+                plan.unparsed_date(note);
+            }
+            match export.unparsed_date_policy {
+                // A dry run lists every unparsed date instead of stopping at the first.
+                UnparsedDatePolicy::Fail if export.dry_run.is_some() => None,
+                UnparsedDatePolicy::Fail => {
+                    let _ = export.flush_progress(db);
+                    return Err(error);
+                }
+                UnparsedDatePolicy::UseNow => Some(timezone.now()),
+                UnparsedDatePolicy::UseEpoch => Some(timezone.convert(&DateTime::UNIX_EPOCH)),
+                UnparsedDatePolicy::LeaveBlank => None,
+            }
+        }
+    };
+    let note_index = export.notes_count + 1;
+    let folder = note
+        .folder
+        .as_deref()
+        .map(str::trim)
+        .filter(|folder| !folder.is_empty());
+    let folder_dir = match folder {
+        Some(folder) if export.split => Some(folder_dir_name(export, folder)),
+        _ => None,
+    };
+
+    let mut image_links = Vec::new();
+    let mut skipped_images = note.failed_images;
+    if export.export_images {
+        let images_dir_name = export
+            .images_dir
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or("images")
+            .to_string();
+
+        let ts_hash = {
+            let mut hasher = Sha256::new();
+            let hash_source = created_at
+                .map(|created_at| created_at.to_rfc3339())
+                .unwrap_or_else(|| note.created_string.clone());
+            hasher.update(hash_source.as_bytes());
+            let result = hasher.finalize();
+            result
+                .iter()
+                .take(8)
+                .map(|b| format!("{b:02x}"))
+                .collect::<String>()
+        };
+
+        for (index, image) in note.images.iter().enumerate() {
+            if image.data_base64.trim().is_empty() {
+                continue;
+            }
+
+            let mut image_name = format!("note_img_{}_{}_{}.png", note_index, index + 1, ts_hash);
+            if !image_name.to_ascii_lowercase().ends_with(".png") {
+                image_name.push_str(".png");
+            }
+
+            let image_path = export.images_dir.join(&image_name);
+            let saved = match export.dry_run.as_mut() {
+                Some(plan) => files::decoded_base64_len(&image.data_base64)
+                    .map(|len| plan.report.image_bytes += len as u64),
+                None => files::save_base64_image(&image_path, &image.data_base64),
+            };
+            if saved.is_err() {
+                skipped_images += 1;
+                continue;
+            }
+            export.images_count += 1;
+
+            let relative_path = if export.split && folder.is_some() {
+                format!("../images/{image_name}")
+            } else if export.split {
+                format!("images/{image_name}")
+            } else {
+                format!("{images_dir_name}/{image_name}")
+            };
+            image_links.push(format!("![image {}](<{relative_path}>)", index + 1));
+        }
+    }
+
+    let markdown_note = markdown::build_note_markdown(
+        &note.title,
+        &note.content,
+        if note.content_html.trim().is_empty() {
+            None
+        } else {
+            Some(note.content_html.as_str())
+        },
+        &image_links,
+        markdown::NoteTimestamps::new(created_at, modified_at),
+        &export.chrono_created_date_format,
+        note.unsupported,
+    );
+    // Split notes each carry their folder; a single file only heads each run of a folder.
+    let markdown_note = match folder {
+        Some(folder) if export.split || export.current_folder.as_deref() != Some(folder) => {
+            markdown::insert_folder_heading(&markdown_note, folder)
+        }
+        _ => markdown_note,
+    };
+
+    let file_name = export
+        .split
+        .then(|| note_file_name(export, note, created_at.as_ref(), note_index));
+    let note_path = if let Some(plan) = export.dry_run.as_mut() {
+        plan.note(
+            folder_dir.as_deref(),
+            file_name.as_deref(),
+            &export.output_root,
+            markdown_note.len(),
+        );
+        if note.unsupported {
+            plan.report
+                .unsupported_notes
+                .push(display_title(&note.title).to_string());
+        }
+        None
+    } else {
+        Some(match &file_name {
+            Some(file_name) => {
+                let note_dir = match &folder_dir {
+                    Some(folder_dir) => export.output_root.join(folder_dir),
+                    None => export.output_root.clone(),
+                };
+                std::fs::create_dir_all(&note_dir)?;
+                let file_path = note_dir.join(file_name);
+                std::fs::write(&file_path, &markdown_note)?;
+                if let Some(file_time) = modified_at.or(created_at) {
+                    files::set_modified_time(&file_path, file_time.into())?;
+                }
+                file_path
+            }
+            None => {
+                files::append_text(&export.output_root, &markdown_note)?;
+                export.output_root.clone()
+            }
+        })
+    };
+
+    export.notes_count += 1;
+    export.current_folder = folder.map(ToString::to_string);
+    if let Some(folder) = folder {
+        export.folders.insert(folder.to_string());
+    }
+    if let Some(note_path) = note_path {
+        let index_entry = NoteIndexEntry {
+            session_id: Some(export.session_id.clone()),
+            file_path: note_path.to_string_lossy().to_string(),
+            title: note.title.trim().to_string(),
+            content: note.content.trim().to_string(),
+            creation_date: created_at
+                .map(|created_at| search::normalize_index_date(&created_at))
+                .unwrap_or_default(),
+        };
+        export.queue_index_entry(db, index_entry)?;
+    }
+
+    let mut log_line = if note.unsupported {
+        format!("Processed note {} (unsupported type).", export.notes_count)
+    } else {
+        format!(
+            "Processed note {}: {}",
+            export.notes_count,
+            display_title(&note.title)
+        )
+    };
+    if skipped_images > 0 {
+        log_line.push_str(&format!(
+            " ({skipped_images} image(s) skipped due to errors)"
+        ));
+    }
+    if unparsed_date {
+        log_line.push_str(&format!(
+            " (unparsed creation date '{}', {} so far)",
+            note.created_string.trim(),
+            export.unparsed_dates
+        ));
+    }
+
+    Ok(log_line)
+}
+
+/// Flushes pending progress and records the final status of the export session.
+pub fn finish_export(
+    db: &Database,
+    export: &mut ActiveExportState,
+    status: &str,
+    error_message: Option<&str>,
+) -> AppResult<()> {
+    if export.dry_run.is_some() {
+        return Ok(());
+    }
+    export.flush_progress(db)?;
+    if !export.folders.is_empty() {
+        let folders = export.folders.iter().cloned().collect::<Vec<_>>();
+        sessions_db::set_session_folders(db, &export.session_id, &folders)?;
+    }
+    sessions_db::set_session_outcome(
+        db,
+        &export.session_id,
+        status,
+        &now_utc(),
+        export.notes_count,
+        export.images_count,
+        error_message,
+    )
+}
+
+pub fn complete_event(export: &ActiveExportState) -> ExportCompleteEvent {
+    ExportCompleteEvent {
+        session_id: export.session_id.clone(),
+        total: export.notes_count,
+        elapsed_ms: export.active_elapsed().as_millis() as u64,
+        output_path: export.output_root.to_string_lossy().to_string(),
+        dry_run: export.dry_run.as_ref().map(|plan| plan.report_for(export)),
+    }
+}
+
+fn lock_error() -> AppError {
+    AppError::Message("Internal state lock poisoned.".to_string())
+}
+
+fn lock_active_export(state: &AppState) -> AppResult<MutexGuard<'_, Option<ActiveExportState>>> {
+    state.active_export.lock().map_err(|_| lock_error())
+}
+
+fn take_matching_export(state: &AppState, session_id: &str) -> AppResult<ActiveExportState> {
+    let mut guard = lock_active_export(state)?;
+    let taken = guard.take().ok_or(AppError::ExportNotRunning)?;
+    if taken.session_id != session_id {
+        *guard = Some(taken);
+        return Err(AppError::SessionMismatch);
+    }
+    Ok(taken)
+}
+
+fn with_matching_export<T>(
+    state: &AppState,
+    session_id: &str,
+    action: impl FnOnce(&mut ActiveExportState) -> AppResult<T>,
+) -> AppResult<T> {
+    let mut guard = lock_active_export(state)?;
+    let export = guard.as_mut().ok_or(AppError::ExportNotRunning)?;
+    if export.session_id != session_id {
+        return Err(AppError::SessionMismatch);
+    }
+    action(export)
+}
+
+/// Registers a new export as the active one and returns its session id.
+pub fn start_export(state: &AppState, options: &ExportOptions) -> AppResult<String> {
+    let mut guard = lock_active_export(state)?;
+    if guard.is_some() {
+        return Err(AppError::ExportRunning);
+    }
+
+    let export = begin_export(&state.db, options)?;
+    let session_id = export.session_id.clone();
+    *guard = Some(export);
+    Ok(session_id)
+}
+
+pub fn active_session_id(state: &AppState) -> AppResult<Option<String>> {
+    Ok(lock_active_export(state)?
+        .as_ref()
+        .map(|export| export.session_id.clone()))
+}
+
+pub fn export_scope(state: &AppState, session_id: &str) -> AppResult<ExportScope> {
+    with_matching_export(state, session_id, |export| {
+        Ok(ExportScope {
+            export_images: export.export_images,
+            filter: export.filter.clone(),
+        })
+    })
+}
+
+pub fn report_export_total(
+    state: &AppState,
+    session_id: &str,
+    total: u32,
+    events: &dyn ExportEvents,
+) -> AppResult<()> {
+    with_matching_export(state, session_id, |export| {
+        let log_line = set_total(export, total);
+        events.progress(progress_event(export, "", &log_line));
+        Ok(())
+    })
+}
+
+pub fn append_scraped_note(
+    state: &AppState,
+    session_id: &str,
+    note: &ScrapedNoteInput,
+    details_timestamps: NoteDetailsTimestamps,
+    events: &dyn ExportEvents,
+) -> AppResult<()> {
+    with_matching_export(state, session_id, |export| {
+        let log_line = append_note(&state.db, export, note, details_timestamps)?;
+        events.progress(progress_event(export, &note.title, &log_line));
+        Ok(())
+    })
+}
+
+/// Pauses the active export; scrape loops hold before their next note until it resumes.
+pub fn pause_export(state: &AppState, events: &dyn ExportEvents) -> AppResult<()> {
+    let mut guard = lock_active_export(state)?;
+    let export = guard.as_mut().ok_or(AppError::ExportNotRunning)?;
+    if export.paused_at.is_some() {
+        return Ok(());
+    }
+
+    export.paused_at = Some(Instant::now());
+    if export.dry_run.is_none() {
+        export.flush_progress(&state.db)?;
+        sessions_db::set_session_status(&state.db, &export.session_id, STATUS_PAUSED)?;
+    }
+    events.progress(progress_event(export, "", "Export paused."));
+    Ok(())
+}
+
+pub fn resume_export(state: &AppState, events: &dyn ExportEvents) -> AppResult<()> {
+    let mut guard = lock_active_export(state)?;
+    let export = guard.as_mut().ok_or(AppError::ExportNotRunning)?;
+    let Some(paused_at) = export.paused_at.take() else {
+        return Ok(());
+    };
+
+    export.paused_for += paused_at.elapsed();
+    if export.dry_run.is_none() {
+        sessions_db::set_session_status(&state.db, &export.session_id, STATUS_RUNNING)?;
+    }
+    events.progress(progress_event(export, "", "Export resumed."));
+    Ok(())
+}
+
+pub fn is_export_paused(state: &AppState, session_id: &str) -> AppResult<bool> {
+    with_matching_export(state, session_id, |export| Ok(export.paused_at.is_some()))
+}
+
+/// Blocks while the export is paused; fails once it is no longer the active export.
+pub fn wait_while_paused(state: &AppState, session_id: &str) -> AppResult<()> {
+    while is_export_paused(state, session_id)? {
+        thread::sleep(PAUSE_POLL_INTERVAL);
+    }
+    Ok(())
+}
+
+/// Completes the active export and returns its final state.
+pub fn finish_scrape(
+    state: &AppState,
+    session_id: &str,
+    events: &dyn ExportEvents,
+) -> AppResult<ActiveExportState> {
+    let mut export = take_matching_export(state, session_id)?;
+    finish_export(&state.db, &mut export, "completed", None)?;
+    events.complete(complete_event(&export));
+    Ok(export)
+}
+
+pub fn fail_scrape(
+    state: &AppState,
+    session_id: &str,
+    message: &str,
+    events: &dyn ExportEvents,
+) -> AppResult<ActiveExportState> {
+    let mut export = take_matching_export(state, session_id)?;
+    finish_export(&state.db, &mut export, "error", Some(message))?;
+    events.error(ExportErrorEvent {
+        session_id: export.session_id.clone(),
+        message: message.to_string(),
+    });
+    Ok(export)
+}
+
+/// Cancels the active export, if any. With `session_id`, only that export is cancelled;
+/// `reason` is stored on the session and reported instead of the default message.
+pub fn cancel_export(
+    state: &AppState,
+    session_id: Option<&str>,
+    reason: Option<&str>,
+    events: &dyn ExportEvents,
+) -> AppResult<Option<ActiveExportState>> {
+    let taken = {
+        let mut guard = lock_active_export(state)?;
+        let matches = guard
+            .as_ref()
+            .is_some_and(|active| session_id.is_none_or(|id| active.session_id == id));
+        if matches {
+            guard.take()
+        } else {
+            None
+        }
+    };
+    let Some(mut export) = taken else {
+        return Ok(None);
+    };
+
+    finish_export(&state.db, &mut export, "cancelled", reason)?;
+    events.error(ExportErrorEvent {
+        session_id: export.session_id.clone(),
+        message: reason.unwrap_or(CANCELLED_MESSAGE).to_string(),
+    });
+    Ok(Some(export))
+}
@@ -0,0 +1,147 @@
+use chrono::{DateTime, FixedOffset, Utc};
+use regex::Regex;
+
+use crate::{
+    error::{AppError, AppResult},
+    models::{ExportFilterSpec, ExportSource, ScrapedNoteInput, UnsupportedNotesFilter},
+    services::date_parser::parse_filter_date,
+};
+
+/// A validated [`ExportFilterSpec`], evaluated for every scraped note before it is written.
+#[derive(Debug, Clone, Default)]
+pub struct ExportFilter {
+    created_after: Option<DateTime<Utc>>,
+    created_before: Option<DateTime<Utc>>,
+    title: Option<Regex>,
+    body: Option<Regex>,
+    unsupported: UnsupportedNotesFilter,
+    min_length: usize,
+    folders: Vec<String>,
+}
+
+fn parse_bound(value: Option<&str>) -> AppResult<Option<DateTime<Utc>>> {
+    parse_filter_date(value)?
+        .map(|raw| {
+            DateTime::parse_from_rfc3339(&raw)
+                .map(|parsed| parsed.with_timezone(&Utc))
+                .map_err(|error| AppError::Message(error.to_string()))
+        })
+        .transpose()
+}
+
+fn compile_pattern(label: &str, pattern: Option<&str>) -> AppResult<Option<Regex>> {
+    let Some(pattern) = pattern.filter(|pattern| !pattern.trim().is_empty()) else {
+        return Ok(None);
+    };
+    Regex::new(pattern)
+        .map(Some)
+        .map_err(|error| AppError::Message(format!("Invalid {label} pattern '{pattern}': {error}")))
+}
+
+/// Rejects criteria the note source cannot evaluate: the page scraper does not see folders.
+pub fn check_source(spec: &ExportFilterSpec, source: ExportSource) -> AppResult<()> {
+    let has_folders = spec.folders.iter().any(|folder| !folder.trim().is_empty());
+    if source == ExportSource::Dom && has_folders {
+        return Err(AppError::Message(
+            "Folder filters need the Mi Cloud API source; the page scraper does not see folders."
+                .to_string(),
+        ));
+    }
+    Ok(())
+}
+
+impl ExportFilter {
+    pub fn compile(spec: &ExportFilterSpec) -> AppResult<Self> {
+        let created_after = parse_bound(spec.created_after.as_deref())?;
+        let created_before = parse_bound(spec.created_before.as_deref())?;
+        if let (Some(after), Some(before)) = (created_after, created_before) {
+            if after >= before {
+                return Err(AppError::Message(
+                    "The created-after date must be earlier than the created-before date."
+                        .to_string(),
+                ));
+            }
+        }
+
+        Ok(Self {
+            created_after,
+            created_before,
+            title: compile_pattern("title", spec.title_regex.as_deref())?,
+            body: compile_pattern("body", spec.body_regex.as_deref())?,
+            unsupported: spec.unsupported,
+            min_length: spec.min_length.unwrap_or(0) as usize,
+            folders: spec
+                .folders
+                .iter()
+                .map(|folder| folder.trim().to_lowercase())
+                .filter(|folder| !folder.is_empty())
+                .collect(),
+        })
+    }
+
+    /// Whether a note in this folder can pass; an empty selection includes every folder.
+    pub fn includes_folder(&self, folder_id: Option<&str>, folder: Option<&str>) -> bool {
+        self.folders.is_empty()
+            || [folder_id, folder]
+                .into_iter()
+                .flatten()
+                .map(|value| value.trim().to_lowercase())
+                .any(|value| self.folders.contains(&value))
+    }
+
+    /// Returns why the note is filtered out, judged on everything but its creation date.
+    /// Body criteria only apply to supported notes, whose content the exporter can read.
+    pub fn rejects_note(&self, note: &ScrapedNoteInput) -> Option<&'static str> {
+        match self.unsupported {
+            UnsupportedNotesFilter::Exclude if note.unsupported => return Some("unsupported"),
+            UnsupportedNotesFilter::Only if !note.unsupported => return Some("supported"),
+            _ => {}
+        }
+        if !self.includes_folder(note.folder_id.as_deref(), note.folder.as_deref()) {
+            return Some("folder");
+        }
+        if self
+            .title
+            .as_ref()
+            .is_some_and(|title| !title.is_match(note.title.trim()))
+        {
+            return Some("title");
+        }
+        if note.unsupported {
+            return None;
+        }
+        if note.content.trim().chars().count() < self.min_length {
+            return Some("length");
+        }
+        if self
+            .body
+            .as_ref()
+            .is_some_and(|body| !body.is_match(&note.content))
+        {
+            return Some("body");
+        }
+        None
+    }
+
+    /// Returns why a note created at `created_at` is filtered out; notes without a
+    /// creation date never pass a date range.
+    pub fn rejects_created(
+        &self,
+        created_at: Option<&DateTime<FixedOffset>>,
+    ) -> Option<&'static str> {
+        if self.created_after.is_none() && self.created_before.is_none() {
+            return None;
+        }
+        let Some(created_at) = created_at.map(|created_at| created_at.with_timezone(&Utc)) else {
+            return Some("missing creation date");
+        };
+        if self.created_after.is_some_and(|after| created_at < after)
+            || self
+                .created_before
+                .is_some_and(|before| created_at >= before)
+        {
+            return Some("creation date");
+        }
+        None
+    }
+}
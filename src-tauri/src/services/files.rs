@@ -3,6 +3,7 @@ use std::{
     io::{self, Write},
     path::{Path, PathBuf},
     process::Command,
+    time::SystemTime,
 };
 
 use base64::{engine::general_purpose::STANDARD, Engine};
@@ -30,6 +31,12 @@ pub fn append_text(path: &Path, content: &str) -> AppResult<()> {
     Ok(())
 }
 
+pub fn set_modified_time(path: &Path, modified: SystemTime) -> AppResult<()> {
+    let file = fs::OpenOptions::new().write(true).open(path)?;
+    file.set_modified(modified)?;
+    Ok(())
+}
+
 pub fn read_export_file(path: &str) -> AppResult<String> {
     Ok(fs::read_to_string(path)?)
 }
@@ -84,6 +91,78 @@ pub fn save_base64_image(path: &Path, data_base64: &str) -> AppResult<()> {
     Ok(())
 }
 
+/// Size of the image `save_base64_image` would write, without writing it.
+pub fn decoded_base64_len(data_base64: &str) -> AppResult<usize> {
+    Ok(STANDARD.decode(data_base64)?.len())
+}
+
+pub fn replace_path_prefix(path: &str, old_prefix: &str, new_prefix: &str) -> Option<String> {
+    let old_prefix = old_prefix.trim_end_matches(['/', '\\']);
+    let new_prefix = new_prefix.trim_end_matches(['/', '\\']);
+    if old_prefix.is_empty() {
+        return None;
+    }
+
+    let rest = path.strip_prefix(old_prefix)?;
+    if rest.is_empty() || rest.starts_with(['/', '\\']) {
+        Some(format!("{new_prefix}{rest}"))
+    } else {
+        None
+    }
+}
+
+pub fn relative_to_root(path: &str, root: &Path) -> Option<String> {
+    let relative = replace_path_prefix(path, &root.to_string_lossy(), "")?;
+    let relative = relative.trim_start_matches(['/', '\\']);
+    if relative.is_empty() {
+        None
+    } else {
+        Some(relative.to_string())
+    }
+}
+
+pub fn resolve_from_root(path: &str, root: Option<&Path>) -> String {
+    match root {
+        Some(root) if !Path::new(path).is_absolute() => {
+            root.join(path).to_string_lossy().to_string()
+        }
+        _ => path.to_string(),
+    }
+}
+
+pub fn move_path(source: &Path, destination: &Path) -> AppResult<()> {
+    ensure_parent(destination)?;
+    if fs::rename(source, destination).is_ok() {
+        return Ok(());
+    }
+
+    copy_path(source, destination)?;
+    remove_path(source)
+}
+
+fn copy_path(source: &Path, destination: &Path) -> AppResult<()> {
+    if source.is_file() {
+        fs::copy(source, destination)?;
+        return Ok(());
+    }
+
+    for entry in WalkDir::new(source) {
+        let entry = entry.map_err(io::Error::from)?;
+        let relative = entry
+            .path()
+            .strip_prefix(source)
+            .map_err(|error| AppError::Message(error.to_string()))?;
+        let target = destination.join(relative);
+        if entry.file_type().is_dir() {
+            fs::create_dir_all(&target)?;
+        } else {
+            fs::copy(entry.path(), &target)?;
+        }
+    }
+
+    Ok(())
+}
+
 pub fn remove_path(path: &Path) -> AppResult<()> {
     if path.is_file() {
         if let Err(error) = fs::remove_file(path) {
@@ -109,24 +188,60 @@ pub fn remove_export_artifacts(
 ) -> AppResult<()> {
     remove_path(output_path)?;
 
-    if let Some(images_dir_name) = images_dir_name {
-        let trimmed = images_dir_name.trim();
-        if !trimmed.is_empty() {
-            let images_path = if split_mode {
-                output_path.join(trimmed)
-            } else if let Some(parent) = output_path.parent() {
-                parent.join(trimmed)
-            } else {
-                PathBuf::from(trimmed)
-            };
-
-            remove_path(&images_path)?;
-        }
+    if let Some(images_path) = export_images_path(output_path, split_mode, images_dir_name) {
+        remove_path(&images_path)?;
     }
 
     Ok(())
 }
 
+pub fn export_images_path(
+    output_path: &Path,
+    split_mode: bool,
+    images_dir_name: Option<&str>,
+) -> Option<PathBuf> {
+    let trimmed = images_dir_name?.trim();
+    if trimmed.is_empty() {
+        return None;
+    }
+
+    Some(if split_mode {
+        output_path.join(trimmed)
+    } else if let Some(parent) = output_path.parent() {
+        parent.join(trimmed)
+    } else {
+        PathBuf::from(trimmed)
+    })
+}
+
+pub fn export_disk_usage(
+    output_path: &Path,
+    split_mode: bool,
+    images_dir_name: Option<&str>,
+) -> u64 {
+    let mut total = path_size(output_path);
+    if !split_mode {
+        if let Some(images_path) = export_images_path(output_path, split_mode, images_dir_name) {
+            total += path_size(&images_path);
+        }
+    }
+    total
+}
+
+pub fn path_size(path: &Path) -> u64 {
+    if path.is_file() {
+        return fs::metadata(path).map(|meta| meta.len()).unwrap_or(0);
+    }
+
+    WalkDir::new(path)
+        .into_iter()
+        .filter_map(Result::ok)
+        .filter(|entry| entry.file_type().is_file())
+        .filter_map(|entry| entry.metadata().ok())
+        .map(|meta| meta.len())
+        .sum()
+}
+
 pub fn open_in_system_explorer(path: &Path) -> AppResult<()> {
     #[cfg(target_os = "windows")]
     {
@@ -1,10 +1,15 @@
-use chrono::{DateTime, Local};
+use chrono::{
+    format::{Item, StrftimeItems},
+    DateTime, FixedOffset,
+};
 use scraper::node::Node;
 use scraper::{ElementRef, Html, Selector};
 
 const MAX_LIST_DEPTH: usize = 6;
 const ORDERED_LIST_INDENT_SPACES: usize = 4;
 const UNORDERED_LIST_INDENT_SPACES: usize = 2;
+const NOTE_SEPARATOR: &str = "****\n";
+const FOLDER_HEADING_PREFIX: &str = "# Folder:";
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 enum BlockKind {
@@ -90,7 +95,7 @@ impl ParsedBlock {
     }
 }
 
-fn dotnet_to_chrono_with_fallback(input: &str, fallback: &str) -> String {
+fn dotnet_to_chrono_tokens(input: &str) -> String {
     let mut output = input.to_string();
     let replacements = [
         ("yyyy", "%Y"),
@@ -103,6 +108,11 @@ fn dotnet_to_chrono_with_fallback(input: &str, fallback: &str) -> String {
     for (dotnet, chrono) in replacements {
         output = output.replace(dotnet, chrono);
     }
+    output
+}
+
+fn dotnet_to_chrono_with_fallback(input: &str, fallback: &str) -> String {
+    let output = dotnet_to_chrono_tokens(input);
     if output.contains('%') {
         output
     } else {
@@ -118,6 +128,12 @@ pub fn dotnet_to_chrono_created_date_format(input: &str) -> String {
     dotnet_to_chrono_with_fallback(input, "%d/%m/%Y %H:%M")
 }
 
+/// Whether a .NET-style format converts without falling back to the default format.
+pub fn is_valid_dotnet_format(input: &str) -> bool {
+    let output = dotnet_to_chrono_tokens(input);
+    output.contains('%') && !StrftimeItems::new(&output).any(|item| matches!(item, Item::Error))
+}
+
 pub fn sanitize_filename(input: &str) -> String {
     let mut output = String::with_capacity(input.len());
     for character in input.chars() {
@@ -647,12 +663,30 @@ pub(crate) fn to_markdown_from_html(content_html: &str) -> String {
     render_blocks_to_markdown(&blocks)
 }
 
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoteTimestamps {
+    pub created_at: Option<DateTime<FixedOffset>>,
+    pub modified_at: Option<DateTime<FixedOffset>>,
+}
+
+impl NoteTimestamps {
+    pub fn new(
+        created_at: Option<DateTime<FixedOffset>>,
+        modified_at: Option<DateTime<FixedOffset>>,
+    ) -> Self {
+        Self {
+            created_at,
+            modified_at,
+        }
+    }
+}
+
 pub fn build_note_markdown(
     title: &str,
     content: &str,
     content_html: Option<&str>,
     image_links: &[String],
-    created_at: DateTime<Local>,
+    timestamps: NoteTimestamps,
     created_date_format: &str,
     unsupported: bool,
 ) -> String {
@@ -687,10 +721,35 @@ pub fn build_note_markdown(
         markdown.push('\n');
     }
 
-    markdown.push_str(&format!(
-        "*Created at: {}*\n",
-        created_at.format(created_date_format)
-    ));
+    match timestamps.created_at {
+        Some(created_at) => markdown.push_str(&format!(
+            "*Created at: {}*\n",
+            created_at.format(created_date_format)
+        )),
+        None => markdown.push_str("*Created at:*\n"),
+    }
+    if let Some(modified_at) = timestamps.modified_at {
+        markdown.push_str(&format!(
+            "*Modified at: {}*\n",
+            modified_at.format(created_date_format)
+        ));
+    }
 
     markdown
 }
+
+/// Puts a `# Folder:` heading between a note's separator and its title.
+pub fn insert_folder_heading(note_markdown: &str, folder: &str) -> String {
+    let heading = format!("{FOLDER_HEADING_PREFIX} {}\n", folder.trim());
+    match note_markdown.strip_prefix(NOTE_SEPARATOR) {
+        Some(rest) => format!("{NOTE_SEPARATOR}{heading}{rest}"),
+        None => format!("{heading}{note_markdown}"),
+    }
+}
+
+pub fn parse_folder_heading(line: &str) -> Option<&str> {
+    line.trim()
+        .strip_prefix(FOLDER_HEADING_PREFIX)
+        .map(str::trim)
+        .filter(|folder| !folder.is_empty())
+}
@@ -0,0 +1,575 @@
+use std::{collections::HashMap, time::Duration};
+
+use base64::Engine as _;
+use chrono::Utc;
+use reqwest::{
+    blocking::{Client, Response},
+    header, redirect, StatusCode,
+};
+use serde_json::Value;
+use url::Url;
+
+use crate::{
+    error::{AppError, AppResult},
+    models::{ScrapedImageInput, ScrapedNoteInput},
+    services::export::{self, ExportEvents, NoteDetailsTimestamps},
+    state::AppState,
+};
+
+pub const BROWSER_USER_AGENT: &str = "Mozilla/5.0 (Windows NT 10.0; Win64; x64) \
+    AppleWebKit/537.36 (KHTML, like Gecko) Chrome/124.0.0.0 Safari/537.36";
+/// API root used for note detail lookups when the export domain is not known.
+const DEFAULT_BASE_URL: &str = "https://us.i.mi.com/";
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(20);
+pub const PAGE_LIMIT: u32 = 200;
+const MAX_REDIRECTS: usize = 10;
+const LOGIN_COOKIE: &str = "serviceToken";
+/// Upper bound on list pages, guarding against a server that never reports the last page.
+const MAX_PAGES: usize = 1000;
+
+pub fn is_allowed_image_host(host: &str) -> bool {
+    host == "i.mi.com"
+        || host.ends_with(".i.mi.com")
+        || host.ends_with(".xiaomi.net")
+        || host.ends_with(".mi.com")
+}
+
+pub fn default_base_url() -> AppResult<Url> {
+    Ok(Url::parse(DEFAULT_BASE_URL)?)
+}
+
+pub fn domain_base_url(domain: &str) -> AppResult<Url> {
+    Ok(Url::parse(&format!("https://{domain}/"))?)
+}
+
+pub fn read_entry_timestamp(entry: &Value, keys: &[&str]) -> Option<i64> {
+    keys.iter().find_map(|key| {
+        entry.get(*key).and_then(|value| {
+            value
+                .as_i64()
+                .or_else(|| value.as_str().and_then(|raw| raw.parse::<i64>().ok()))
+        })
+    })
+}
+
+fn read_entry_string(entry: &Value, key: &str) -> Option<String> {
+    entry.get(key).and_then(|value| match value {
+        Value::String(raw) => Some(raw.clone()),
+        Value::Number(number) => Some(number.to_string()),
+        _ => None,
+    })
+}
+
+/// Builds a `Cookie` header from the webview's cookies once the Mi Cloud login cookie is present.
+pub fn login_cookie_header<'a>(
+    cookies: impl IntoIterator<Item = (&'a str, &'a str)>,
+) -> Option<String> {
+    let mut logged_in = false;
+    let pairs = cookies
+        .into_iter()
+        .filter(|(name, _)| !name.is_empty())
+        .inspect(|(name, value)| logged_in |= *name == LOGIN_COOKIE && !value.is_empty())
+        .map(|(name, value)| format!("{name}={value}"))
+        .collect::<Vec<_>>();
+    logged_in.then(|| pairs.join("; "))
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NoteSummary {
+    pub id: String,
+    pub folder_id: Option<String>,
+    /// Folder name, resolved once every list page has been read.
+    pub folder: Option<String>,
+    pub created: Option<i64>,
+    pub modified: Option<i64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NotePage {
+    pub notes: Vec<NoteSummary>,
+    /// Folder ids mapped to their names.
+    pub folders: Vec<(String, String)>,
+    pub sync_tag: Option<String>,
+    pub last_page: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NoteEntry {
+    pub id: String,
+    pub title: String,
+    pub content: String,
+    pub image_file_ids: Vec<String>,
+    pub created: Option<i64>,
+    pub modified: Option<i64>,
+    pub unsupported: bool,
+}
+
+impl NoteEntry {
+    pub fn timestamps(&self) -> NoteDetailsTimestamps {
+        NoteDetailsTimestamps {
+            created: self.created,
+            modified: self.modified,
+        }
+    }
+
+    pub fn into_scraped_note(
+        self,
+        summary: NoteSummary,
+        images: Vec<ScrapedImageInput>,
+        failed_images: u32,
+    ) -> ScrapedNoteInput {
+        ScrapedNoteInput {
+            title: self.title,
+            created_string: self
+                .created
+                .map(|created| created.to_string())
+                .unwrap_or_default(),
+            content: self.content,
+            content_html: String::new(),
+            modified_timestamp: self.modified,
+            note_id: Some(self.id),
+            cookie_header: None,
+            unsupported: self.unsupported,
+            folder_id: summary.folder_id,
+            folder: summary.folder,
+            images,
+            failed_images,
+        }
+    }
+}
+
+fn parse_summary(entry: &Value) -> Option<NoteSummary> {
+    Some(NoteSummary {
+        id: read_entry_string(entry, "id")?,
+        // Folder 0 holds notes that are not filed anywhere.
+        folder_id: read_entry_string(entry, "folderId").filter(|id| id != "0" && !id.is_empty()),
+        folder: None,
+        created: read_entry_timestamp(entry, &["createDate", "createdDate"]),
+        modified: read_entry_timestamp(entry, &["modifyDate", "modifiedDate"]),
+    })
+}
+
+pub fn parse_note_page(payload: &Value) -> AppResult<NotePage> {
+    let data = payload
+        .get("data")
+        .ok_or_else(|| AppError::Message("Mi Cloud note list has no data.".to_string()))?;
+    let notes = data
+        .get("entries")
+        .and_then(Value::as_array)
+        .map(|entries| entries.iter().filter_map(parse_summary).collect())
+        .unwrap_or_default();
+    let folders = data
+        .get("folders")
+        .and_then(Value::as_array)
+        .map(|folders| {
+            folders
+                .iter()
+                .filter_map(|folder| {
+                    Some((
+                        read_entry_string(folder, "id")?,
+                        read_entry_string(folder, "subject")?,
+                    ))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    Ok(NotePage {
+        notes,
+        folders,
+        sync_tag: read_entry_string(data, "syncTag").filter(|tag| !tag.is_empty()),
+        last_page: data
+            .get("lastPage")
+            .and_then(Value::as_bool)
+            .unwrap_or(false),
+    })
+}
+
+/// Reads a note detail payload (`data.entry`) into a note ready for the export pipeline.
+pub fn parse_note_entry(payload: &Value) -> AppResult<NoteEntry> {
+    let entry = payload
+        .get("data")
+        .and_then(|data| data.get("entry"))
+        .ok_or_else(|| AppError::Message("Mi Cloud note has no entry.".to_string()))?;
+    let id = read_entry_string(entry, "id")
+        .ok_or_else(|| AppError::Message("Mi Cloud note has no id.".to_string()))?;
+
+    let extra_info = entry
+        .get("extraInfo")
+        .and_then(Value::as_str)
+        .and_then(|raw| serde_json::from_str::<Value>(raw).ok())
+        .unwrap_or(Value::Null);
+    let title = read_entry_string(&extra_info, "title")
+        .or_else(|| read_entry_string(entry, "subject"))
+        .unwrap_or_default();
+    let content_type = read_entry_string(&extra_info, "note_content_type").unwrap_or_default();
+    let raw_content = read_entry_string(entry, "content").unwrap_or_default();
+    let (content, image_file_ids) = render_content(&raw_content);
+
+    Ok(NoteEntry {
+        id,
+        title: title.trim().to_string(),
+        content,
+        image_file_ids,
+        created: read_entry_timestamp(entry, &["createDate", "createdDate"]),
+        modified: read_entry_timestamp(entry, &["modifyDate", "modifiedDate"]),
+        unsupported: matches!(content_type.as_str(), "mind" | "sound"),
+    })
+}
+
+fn tag_attribute(tag: &str, name: &str) -> Option<String> {
+    let start = tag.find(&format!("{name}=\""))? + name.len() + 2;
+    let end = tag[start..].find('"')? + start;
+    Some(tag[start..end].to_string())
+}
+
+fn unescape_entities(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&nbsp;", " ")
+        .replace("&amp;", "&")
+}
+
+fn render_tag(tag: &str, image_file_ids: &mut Vec<String>) -> String {
+    let name = tag
+        .trim_start_matches('/')
+        .split(|c: char| c.is_whitespace() || c == '/')
+        .next()
+        .unwrap_or_default();
+    match name {
+        "img" => {
+            if let Some(file_id) = tag_attribute(tag, "fileid") {
+                image_file_ids.push(file_id);
+            }
+            String::new()
+        }
+        "input" if tag_attribute(tag, "type").as_deref() == Some("checkbox") => {
+            if tag_attribute(tag, "checked").as_deref() == Some("true") {
+                "- [x] ".to_string()
+            } else {
+                "- [ ] ".to_string()
+            }
+        }
+        "bullet" => {
+            let indent = tag_attribute(tag, "indent")
+                .and_then(|raw| raw.parse::<usize>().ok())
+                .unwrap_or(1);
+            format!("{}- ", "  ".repeat(indent.saturating_sub(1)))
+        }
+        "order" => {
+            let number = tag_attribute(tag, "inputNumber")
+                .and_then(|raw| raw.parse::<usize>().ok())
+                .unwrap_or(0);
+            format!("{}. ", number + 1)
+        }
+        "hr" => "---".to_string(),
+        _ => String::new(),
+    }
+}
+
+/// Converts Mi Cloud's XML-like note content to plain text and collects referenced image ids.
+pub fn render_content(raw: &str) -> (String, Vec<String>) {
+    let mut image_file_ids = Vec::new();
+    let mut lines = Vec::new();
+
+    for line in raw.lines() {
+        // Older notes reference images as `☺ <file id><0/><//>` on a line of their own.
+        if let Some(reference) = line.trim().strip_prefix('☺') {
+            let file_id = reference.trim().split('<').next().unwrap_or_default();
+            if !file_id.is_empty() {
+                image_file_ids.push(file_id.to_string());
+            }
+            continue;
+        }
+
+        let mut text = String::new();
+        let mut rest = line;
+        while let Some(start) = rest.find('<') {
+            text.push_str(&rest[..start]);
+            let Some(end) = rest[start..].find('>') else {
+                text.push_str(&rest[start..]);
+                rest = "";
+                break;
+            };
+            text.push_str(&render_tag(
+                &rest[start + 1..start + end],
+                &mut image_file_ids,
+            ));
+            rest = &rest[start + end + 1..];
+        }
+        text.push_str(rest);
+        lines.push(unescape_entities(text.trim_end()));
+    }
+
+    (lines.join("\n").trim().to_string(), image_file_ids)
+}
+
+fn request_error(error: reqwest::Error) -> AppError {
+    AppError::Message(format!("Mi Cloud request failed: {error}"))
+}
+
+pub struct MiCloudClient {
+    base_url: Url,
+    cookie_header: String,
+    http: Client,
+}
+
+impl MiCloudClient {
+    /// Creates a client for an API root such as `https://us.i.mi.com/`.
+    pub fn new(base_url: Url, cookie_header: &str) -> AppResult<Self> {
+        Self::with_timeout(base_url, cookie_header, DEFAULT_TIMEOUT)
+    }
+
+    pub fn with_timeout(base_url: Url, cookie_header: &str, timeout: Duration) -> AppResult<Self> {
+        let base_host = base_url.host_str().unwrap_or_default().to_string();
+        let http = Client::builder()
+            .timeout(timeout)
+            .user_agent(BROWSER_USER_AGENT)
+            .redirect(redirect::Policy::custom(move |attempt| {
+                let trusted = attempt
+                    .url()
+                    .host_str()
+                    .is_some_and(|host| host == base_host || is_allowed_image_host(host));
+                if trusted && attempt.previous().len() < MAX_REDIRECTS {
+                    attempt.follow()
+                } else {
+                    attempt.stop()
+                }
+            }))
+            .build()
+            .map_err(|e| AppError::Message(format!("Failed to initialize HTTP client: {e}")))?;
+        Ok(Self {
+            base_url,
+            cookie_header: cookie_header.trim().to_string(),
+            http,
+        })
+    }
+
+    pub fn for_domain(domain: &str, cookie_header: &str) -> AppResult<Self> {
+        Self::new(domain_base_url(domain)?, cookie_header)
+    }
+
+    pub fn base_url(&self) -> &Url {
+        &self.base_url
+    }
+
+    fn send(&self, url: Url) -> AppResult<Response> {
+        let mut request = self.http.get(url);
+        if !self.cookie_header.is_empty() {
+            request = request.header(header::COOKIE, &self.cookie_header);
+        }
+
+        let response = request.send().map_err(request_error)?;
+        let status = response.status();
+        if status.is_redirection() {
+            // Redirects are only followed within Mi Cloud; anything left here points elsewhere.
+            let target = response
+                .headers()
+                .get(header::LOCATION)
+                .and_then(|location| location.to_str().ok())
+                .and_then(|location| response.url().join(location).ok());
+            let host = target
+                .as_ref()
+                .and_then(Url::host_str)
+                .unwrap_or("unknown host");
+            return Err(AppError::Message(format!(
+                "Redirect host is not allowed: {host}"
+            )));
+        }
+        if status == StatusCode::UNAUTHORIZED || status == StatusCode::FORBIDDEN {
+            return Err(AppError::Message(format!(
+                "Mi Cloud rejected the session (HTTP {status}). Sign in again."
+            )));
+        }
+        if !status.is_success() {
+            return Err(AppError::Message(format!(
+                "Mi Cloud request failed: HTTP {status}"
+            )));
+        }
+        Ok(response)
+    }
+
+    fn get_json(&self, path: &str, query: &[(&str, String)]) -> AppResult<Value> {
+        let mut url = self.base_url.join(path)?;
+        url.query_pairs_mut()
+            .extend_pairs(query)
+            .append_pair("ts", &Utc::now().timestamp_millis().to_string());
+
+        let payload: Value = self.send(url)?.json().map_err(request_error)?;
+        match payload.get("result").and_then(Value::as_str) {
+            Some("ok") | None => Ok(payload),
+            Some(_) => Err(AppError::Message(format!(
+                "Mi Cloud returned an error: {}",
+                read_entry_string(&payload, "description").unwrap_or_else(|| payload.to_string())
+            ))),
+        }
+    }
+
+    pub fn list_page(&self, sync_tag: Option<&str>) -> AppResult<NotePage> {
+        let mut query = vec![("limit", PAGE_LIMIT.to_string())];
+        if let Some(sync_tag) = sync_tag {
+            query.push(("syncTag", sync_tag.to_string()));
+        }
+        parse_note_page(&self.get_json("note/full/page/", &query)?)
+    }
+
+    /// Pages through the note list until the server reports the last page and names each
+    /// note's folder.
+    pub fn list_notes(&self) -> AppResult<Vec<NoteSummary>> {
+        let mut notes = Vec::new();
+        let mut folders = HashMap::new();
+        let mut sync_tag: Option<String> = None;
+        for _ in 0..MAX_PAGES {
+            let page = self.list_page(sync_tag.as_deref())?;
+            let exhausted = page.notes.is_empty();
+            notes.extend(page.notes);
+            folders.extend(page.folders);
+            if page.last_page || exhausted || page.sync_tag.is_none() || page.sync_tag == sync_tag {
+                break;
+            }
+            sync_tag = page.sync_tag;
+        }
+
+        for note in &mut notes {
+            note.folder = note
+                .folder_id
+                .as_ref()
+                .and_then(|id| folders.get(id).cloned());
+        }
+        Ok(notes)
+    }
+
+    pub fn fetch_note(&self, note_id: &str) -> AppResult<NoteEntry> {
+        let path = format!("note/note/{}/", note_id.trim());
+        parse_note_entry(&self.get_json(&path, &[])?)
+    }
+
+    /// Downloads an image from `url`, refusing redirects to hosts outside Mi Cloud.
+    pub fn download_image(&self, url: Url) -> AppResult<String> {
+        let bytes = self
+            .send(url)?
+            .bytes()
+            .map_err(|e| AppError::Message(format!("Failed to read image bytes: {e}")))?;
+        Ok(base64::engine::general_purpose::STANDARD.encode(bytes))
+    }
+
+    pub fn fetch_image(&self, file_id: &str) -> AppResult<String> {
+        let mut url = self.base_url.join("file/full")?;
+        url.query_pairs_mut()
+            .append_pair("type", "note_img")
+            .append_pair("fileid", file_id);
+        self.download_image(url)
+    }
+
+    fn is_trusted_host(&self, host: &str) -> bool {
+        self.base_url.host_str() == Some(host) || is_allowed_image_host(host)
+    }
+}
+
+/// Looks up a note's creation and modification timestamps from its detail entry.
+pub fn fetch_note_timestamps(
+    base_url: Url,
+    note_id: Option<&str>,
+    cookie_header: Option<&str>,
+) -> Option<NoteDetailsTimestamps> {
+    let note_id = note_id?.trim();
+    if note_id.is_empty() {
+        return None;
+    }
+
+    let client = MiCloudClient::new(base_url, cookie_header.unwrap_or_default()).ok()?;
+    client
+        .fetch_note(note_id)
+        .ok()
+        .map(|entry| entry.timestamps())
+}
+
+/// Downloads an image URL found on a note page. Only HTTPS Mi Cloud hosts and the
+/// client's own origin are accepted.
+pub fn download_scrape_image(client: &MiCloudClient, source_url: &str) -> AppResult<String> {
+    let parsed =
+        Url::parse(source_url).map_err(|e| AppError::Message(format!("Invalid image URL: {e}")))?;
+    if parsed.scheme() != "https" && parsed.origin() != client.base_url.origin() {
+        return Err(AppError::Message(
+            "Only HTTPS image URLs are allowed.".to_string(),
+        ));
+    }
+
+    let host = parsed
+        .host_str()
+        .ok_or_else(|| AppError::Message("Image URL does not include a host.".to_string()))?;
+    if !client.is_trusted_host(host) {
+        return Err(AppError::Message(format!("Unsupported image host: {host}")));
+    }
+
+    client.download_image(parsed)
+}
+
+fn export_notes(
+    state: &AppState,
+    session_id: &str,
+    client: &MiCloudClient,
+    events: &dyn ExportEvents,
+) -> AppResult<()> {
+    let scope = export::export_scope(state, session_id)?;
+    let mut notes = client
+        .list_notes()?
+        .into_iter()
+        .filter(|note| {
+            scope
+                .filter
+                .includes_folder(note.folder_id.as_deref(), note.folder.as_deref())
+        })
+        .collect::<Vec<_>>();
+    if notes.is_empty() {
+        return Err(AppError::Message("No notes found for export.".to_string()));
+    }
+    // Keeps each folder's notes together, so single-file exports get one section per folder.
+    notes.sort_by(|left, right| left.folder.cmp(&right.folder));
+    export::report_export_total(state, session_id, notes.len() as u32, events)?;
+
+    for summary in notes {
+        export::wait_while_paused(state, session_id)?;
+        let mut entry = client.fetch_note(&summary.id)?;
+        entry.created = entry.created.or(summary.created);
+        entry.modified = entry.modified.or(summary.modified);
+
+        let mut images = Vec::new();
+        let mut failed_images = 0;
+        if scope.export_images {
+            for file_id in &entry.image_file_ids {
+                // A missing image should not abort the export; the note is kept without it
+                // and the failure is reported in its log line.
+                match client.fetch_image(file_id) {
+                    Ok(data_base64) => images.push(ScrapedImageInput { data_base64 }),
+                    Err(_) => failed_images += 1,
+                }
+            }
+        }
+
+        let timestamps = entry.timestamps();
+        let note = entry.into_scraped_note(summary, images, failed_images);
+        export::append_scraped_note(state, session_id, &note, timestamps, events)?;
+    }
+    Ok(())
+}
+
+/// Runs a started export from the Mi Cloud API and records its outcome. An export
+/// cancelled while running is left as cancelled.
+pub fn run_export(
+    state: &AppState,
+    session_id: &str,
+    client: &MiCloudClient,
+    events: &dyn ExportEvents,
+) -> AppResult<()> {
+    match export_notes(state, session_id, client, events) {
+        Ok(()) => export::finish_scrape(state, session_id, events).map(|_| ()),
+        Err(AppError::ExportNotRunning | AppError::SessionMismatch) => Ok(()),
+        Err(error) => {
+            let _ = export::fail_scrape(state, session_id, &error.to_string(), events);
+            Err(error)
+        }
+    }
+}
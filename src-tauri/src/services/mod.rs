@@ -1,7 +1,21 @@
+pub mod backup;
 pub mod converter;
+pub mod date_locale;
 pub mod date_parser;
+pub mod diff;
+pub mod duplicates;
+pub mod export;
+pub mod export_filter;
 pub mod files;
 pub mod markdown;
+pub mod micloud;
+pub mod queue;
+pub mod reconcile;
+pub mod relocate;
+pub mod retention;
 pub mod scraper;
+pub mod search;
 pub mod settings;
+pub mod statistics;
+pub mod timezone;
 pub mod update;
@@ -0,0 +1,164 @@
+use std::sync::MutexGuard;
+
+#[cfg(feature = "gui")]
+use tauri::{AppHandle, Manager};
+use uuid::Uuid;
+
+use crate::{
+    db::queue as queue_db,
+    error::{AppError, AppResult},
+    models::{ExportJobSettings, ExportQueueStatus, QueuedExport},
+    services::{
+        export,
+        export_filter::{self, ExportFilter},
+        micloud,
+    },
+    state::AppState,
+};
+#[cfg(feature = "gui")]
+use crate::{
+    models::ExportErrorEvent,
+    services::{
+        export::{ExportEvents, TauriEvents},
+        scraper,
+    },
+};
+
+fn lock_queue(state: &AppState) -> AppResult<MutexGuard<'_, ExportQueueStatus>> {
+    state
+        .export_queue
+        .lock()
+        .map_err(|_| AppError::Message("Internal state lock poisoned.".to_string()))
+}
+
+/// Applies `change` to the queued jobs and persists them; the queue is left untouched when
+/// `change` fails.
+fn update_jobs(
+    state: &AppState,
+    change: impl FnOnce(&mut Vec<QueuedExport>) -> AppResult<()>,
+) -> AppResult<ExportQueueStatus> {
+    let mut queue = lock_queue(state)?;
+    let mut jobs = queue.jobs.clone();
+    change(&mut jobs)?;
+    queue_db::save_queue(&state.db, &jobs)?;
+    queue.jobs = jobs;
+    Ok(queue.clone())
+}
+
+fn job_position(jobs: &[QueuedExport], job_id: &str) -> AppResult<usize> {
+    jobs.iter()
+        .position(|job| job.id == job_id)
+        .ok_or_else(|| AppError::Message(format!("Queued export not found: {job_id}")))
+}
+
+/// Restores the jobs persisted by a previous run; the queue starts stopped.
+pub fn load_queue(state: &AppState) -> AppResult<()> {
+    let jobs = queue_db::fetch_queue(&state.db)?;
+    lock_queue(state)?.jobs = jobs;
+    Ok(())
+}
+
+pub fn queue_status(state: &AppState) -> AppResult<ExportQueueStatus> {
+    Ok(lock_queue(state)?.clone())
+}
+
+pub fn enqueue_export(state: &AppState, settings: ExportJobSettings) -> AppResult<QueuedExport> {
+    micloud::domain_base_url(&settings.domain)?;
+    ExportFilter::compile(&settings.filter)?;
+    export_filter::check_source(&settings.filter, settings.source)?;
+
+    let job = QueuedExport {
+        id: Uuid::new_v4().to_string(),
+        enqueued_at: export::now_utc(),
+        settings,
+    };
+    let queued = job.clone();
+    update_jobs(state, move |jobs| {
+        jobs.push(job);
+        Ok(())
+    })?;
+    Ok(queued)
+}
+
+pub fn remove_queued_export(state: &AppState, job_id: &str) -> AppResult<ExportQueueStatus> {
+    update_jobs(state, |jobs| {
+        let position = job_position(jobs, job_id)?;
+        jobs.remove(position);
+        Ok(())
+    })
+}
+
+/// Moves a job to `position`, clamped to the end of the queue.
+pub fn move_queued_export(
+    state: &AppState,
+    job_id: &str,
+    position: usize,
+) -> AppResult<ExportQueueStatus> {
+    update_jobs(state, |jobs| {
+        let job = jobs.remove(job_position(jobs, job_id)?);
+        jobs.insert(position.min(jobs.len()), job);
+        Ok(())
+    })
+}
+
+/// Starts or stops running queued jobs one after another.
+pub fn set_queue_running(state: &AppState, running: bool) -> AppResult<ExportQueueStatus> {
+    let mut queue = lock_queue(state)?;
+    queue.running = running;
+    Ok(queue.clone())
+}
+
+/// Removes and returns the next job when the queue is running and no export is active.
+/// The queue stops once it runs empty.
+pub fn take_next_job(state: &AppState) -> AppResult<Option<QueuedExport>> {
+    if export::active_session_id(state)?.is_some() {
+        return Ok(None);
+    }
+
+    let mut queue = lock_queue(state)?;
+    if !queue.running {
+        return Ok(None);
+    }
+    if queue.jobs.is_empty() {
+        queue.running = false;
+        return Ok(None);
+    }
+
+    let job = queue.jobs.remove(0);
+    if let Err(error) = queue_db::save_queue(&state.db, &queue.jobs) {
+        queue.jobs.insert(0, job);
+        return Err(error);
+    }
+    Ok(Some(job))
+}
+
+/// Puts a job taken by `take_next_job` back at the front, e.g. when another export won the
+/// race to start.
+pub fn requeue_job(state: &AppState, job: QueuedExport) -> AppResult<ExportQueueStatus> {
+    update_jobs(state, move |jobs| {
+        jobs.insert(0, job);
+        Ok(())
+    })
+}
+
+/// Launches queued jobs until one starts; jobs that fail to start are reported and skipped.
+#[cfg(feature = "gui")]
+pub fn advance_queue(app: &AppHandle) {
+    let state = app.state::<AppState>();
+    while let Ok(Some(job)) = take_next_job(&state) {
+        match scraper::launch_export(app, &state, &job.settings) {
+            Ok(_) => return,
+            Err(AppError::ExportRunning) => {
+                let _ = requeue_job(&state, job);
+                return;
+            }
+            Err(error) => TauriEvents(app.clone()).error(ExportErrorEvent {
+                session_id: String::new(),
+                message: format!(
+                    "Queued export for {} failed to start: {error}",
+                    job.settings.domain
+                ),
+            }),
+        }
+    }
+}
@@ -0,0 +1,171 @@
+use std::{fs, path::Path};
+
+use chrono::Utc;
+use walkdir::WalkDir;
+
+use crate::{
+    db::{connection::Database, sessions as sessions_db},
+    error::AppResult,
+    models::{Session, SessionVerification},
+    services::{
+        converter::{is_markdown_file, split_note_sections},
+        files,
+    },
+};
+
+pub const STATUS_COMPLETED: &str = "completed";
+pub const STATUS_INTERRUPTED: &str = "interrupted";
+pub const STATUS_MISSING: &str = "missing";
+const STATUS_RUNNING: &str = "running";
+const STATUS_PAUSED: &str = "paused";
+const INTERRUPTED_MESSAGE: &str = "Export was interrupted before it finished.";
+
+fn count_files(path: &Path, max_depth: usize, filter: impl Fn(&Path) -> bool) -> u32 {
+    WalkDir::new(path)
+        .min_depth(1)
+        .max_depth(max_depth)
+        .into_iter()
+        .filter_map(Result::ok)
+        .filter(|entry| entry.file_type().is_file() && filter(entry.path()))
+        .count() as u32
+}
+
+pub(crate) fn count_exported_notes(output_path: &Path, split_mode: bool) -> u32 {
+    if split_mode {
+        return count_files(output_path, usize::MAX, is_markdown_file);
+    }
+
+    fs::read_to_string(output_path)
+        .map(|content| {
+            split_note_sections(&content)
+                .iter()
+                .filter(|section| !section.is_empty())
+                .count() as u32
+        })
+        .unwrap_or(0)
+}
+
+pub(crate) fn count_exported_images(
+    output_path: &Path,
+    split_mode: bool,
+    images_dir_name: Option<&str>,
+) -> u32 {
+    files::export_images_path(output_path, split_mode, images_dir_name)
+        .filter(|images_path| images_path.is_dir())
+        .map(|images_path| count_files(&images_path, usize::MAX, |_| true))
+        .unwrap_or(0)
+}
+
+/// `before_missing` is the outcome stored when a missing session's output disappeared.
+fn resolve_status(
+    session: &Session,
+    output_exists: bool,
+    before_missing: Option<(String, Option<String>)>,
+) -> (String, Option<String>) {
+    if !output_exists {
+        return (
+            STATUS_MISSING.to_string(),
+            Some(format!(
+                "Export output no longer exists: {}",
+                session.output_path
+            )),
+        );
+    }
+
+    let (status, error_message) = match session.status.as_str() {
+        STATUS_MISSING => before_missing.unwrap_or((STATUS_COMPLETED.to_string(), None)),
+        _ => (session.status.clone(), session.error_message.clone()),
+    };
+    match status.as_str() {
+        STATUS_RUNNING | STATUS_PAUSED => (
+            STATUS_INTERRUPTED.to_string(),
+            Some(INTERRUPTED_MESSAGE.to_string()),
+        ),
+        _ => (status, error_message),
+    }
+}
+
+pub fn verify_session(db: &Database, session: &Session) -> AppResult<SessionVerification> {
+    let output_path = Path::new(&session.output_path);
+    let output_exists = output_path.exists();
+    let before_missing = if output_exists && session.status == STATUS_MISSING {
+        sessions_db::fetch_outcome_before_missing(db, &session.id)?
+    } else {
+        None
+    };
+    let (status, error_message) = resolve_status(session, output_exists, before_missing);
+    let (notes_on_disk, images_on_disk) = if output_exists {
+        (
+            count_exported_notes(output_path, session.split_mode),
+            count_exported_images(
+                output_path,
+                session.split_mode,
+                session.images_dir_name.as_deref(),
+            ),
+        )
+    } else {
+        (session.notes_count, session.images_count)
+    };
+
+    let changed = status != session.status
+        || error_message != session.error_message
+        || notes_on_disk != session.notes_count
+        || images_on_disk != session.images_count;
+    if changed {
+        let completed_at = session
+            .completed_at
+            .clone()
+            .unwrap_or_else(|| Utc::now().to_rfc3339());
+        if status == STATUS_MISSING && session.status != STATUS_MISSING {
+            sessions_db::set_outcome_before_missing(
+                db,
+                &session.id,
+                Some((&session.status, session.error_message.as_deref())),
+            )?;
+        } else if status != STATUS_MISSING && session.status == STATUS_MISSING {
+            sessions_db::set_outcome_before_missing(db, &session.id, None)?;
+        }
+        sessions_db::set_session_outcome(
+            db,
+            &session.id,
+            &status,
+            &completed_at,
+            notes_on_disk,
+            images_on_disk,
+            error_message.as_deref(),
+        )?;
+    }
+
+    let updated =
+        sessions_db::fetch_session_by_id(db, &session.id)?.unwrap_or_else(|| session.clone());
+    Ok(SessionVerification {
+        session: updated,
+        previous_status: session.status.clone(),
+        output_exists,
+        notes_on_disk,
+        images_on_disk,
+        changed,
+    })
+}
+
+pub fn reconcile_sessions(db: &Database) -> AppResult<Vec<SessionVerification>> {
+    let mut repaired = Vec::new();
+    for session in sessions_db::fetch_all_sessions(db)? {
+        let output_exists = Path::new(&session.output_path).exists();
+        let needs_repair = match session.status.as_str() {
+            STATUS_RUNNING | STATUS_PAUSED => true,
+            STATUS_MISSING => output_exists,
+            _ => !output_exists,
+        };
+        if !needs_repair {
+            continue;
+        }
+
+        let verification = verify_session(db, &session)?;
+        if verification.changed {
+            repaired.push(verification);
+        }
+    }
+
+    Ok(repaired)
+}
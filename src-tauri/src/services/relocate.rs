@@ -0,0 +1,110 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    db::{connection::Database, sessions as sessions_db},
+    error::{AppError, AppResult},
+    models::{AppSettings, RelocationReport},
+    services::{files, reconcile},
+};
+
+pub fn path_root(settings: &AppSettings) -> Option<PathBuf> {
+    let export_dir = settings.default_export_dir.trim();
+    (settings.relative_session_paths && !export_dir.is_empty()).then(|| PathBuf::from(export_dir))
+}
+
+/// Points the database at the settings' export root and stores paths under it relatively.
+pub fn sync_path_storage(db: &Database, settings: &AppSettings) -> AppResult<u32> {
+    let root = path_root(settings);
+    db.set_path_root(root.clone());
+    match root {
+        Some(root) => sessions_db::convert_path_storage(db, &root, true),
+        None => Ok(0),
+    }
+}
+
+pub fn update_path_storage(
+    db: &Database,
+    previous: &AppSettings,
+    current: &AppSettings,
+) -> AppResult<u32> {
+    if let Some(previous_root) = path_root(previous) {
+        if !current.relative_session_paths {
+            sessions_db::convert_path_storage(db, &previous_root, false)?;
+        }
+    }
+    sync_path_storage(db, current)
+}
+
+fn ensure_destination_available(destination: &Path) -> AppResult<()> {
+    if !destination.exists() {
+        return Ok(());
+    }
+
+    let is_empty_dir = destination.is_dir() && fs::read_dir(destination)?.next().is_none();
+    if !is_empty_dir {
+        return Err(AppError::Message(format!(
+            "Destination already exists: {}",
+            destination.display()
+        )));
+    }
+
+    fs::remove_dir(destination)?;
+    Ok(())
+}
+
+pub fn relocate_sessions(
+    db: &Database,
+    old_prefix: &str,
+    new_prefix: &str,
+    move_files: bool,
+) -> AppResult<RelocationReport> {
+    let old_prefix = old_prefix.trim();
+    let new_prefix = new_prefix.trim();
+    if old_prefix.is_empty() || new_prefix.is_empty() {
+        return Err(AppError::Message(
+            "Both the old and the new location are required.".to_string(),
+        ));
+    }
+    if files::replace_path_prefix(new_prefix, old_prefix, old_prefix).is_some() {
+        return Err(AppError::Message(
+            "The new location must be outside the old one.".to_string(),
+        ));
+    }
+
+    if move_files {
+        let source = Path::new(old_prefix);
+        if !source.exists() {
+            return Err(AppError::Message(format!(
+                "Folder does not exist: {old_prefix}"
+            )));
+        }
+        let destination = Path::new(new_prefix);
+        ensure_destination_available(destination)?;
+        files::move_path(source, destination)?;
+    }
+
+    let relocated_sessions = sessions_db::relocate_output_paths(db, old_prefix, new_prefix)?;
+
+    let mut verified_sessions = 0;
+    let mut missing_sessions = Vec::new();
+    for session in sessions_db::fetch_all_sessions(db)? {
+        if files::replace_path_prefix(&session.output_path, new_prefix, new_prefix).is_none() {
+            continue;
+        }
+        let verification = reconcile::verify_session(db, &session)?;
+        verified_sessions += 1;
+        if !verification.output_exists {
+            missing_sessions.push(session.id);
+        }
+    }
+
+    Ok(RelocationReport {
+        relocated_sessions,
+        moved_files: move_files,
+        verified_sessions,
+        missing_sessions,
+    })
+}
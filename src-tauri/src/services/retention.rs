@@ -0,0 +1,111 @@
+use std::{collections::BTreeMap, path::Path};
+
+use chrono::{DateTime, Duration, Utc};
+
+use crate::{
+    db::{connection::Database, sessions as sessions_db},
+    error::AppResult,
+    models::{RetentionPolicy, RetentionReport, Session},
+    services::files,
+};
+
+const STATUS_RUNNING: &str = "running";
+const STATUS_PAUSED: &str = "paused";
+
+fn is_young(session: &Session, max_age_days: Option<u32>, now: DateTime<Utc>) -> bool {
+    let Some(days) = max_age_days else {
+        return false;
+    };
+
+    match DateTime::parse_from_rfc3339(&session.started_at) {
+        Ok(started_at) => now - started_at.with_timezone(&Utc) < Duration::days(days as i64),
+        Err(_) => true,
+    }
+}
+
+pub(crate) fn select_expired_sessions(
+    sessions: &[Session],
+    policy: &RetentionPolicy,
+    now: DateTime<Utc>,
+) -> Vec<Session> {
+    if policy.keep_last.is_none() && policy.max_age_days.is_none() {
+        return Vec::new();
+    }
+
+    let mut by_domain = BTreeMap::<String, Vec<&Session>>::new();
+    for session in sessions {
+        by_domain
+            .entry(session.domain.to_ascii_lowercase())
+            .or_default()
+            .push(session);
+    }
+
+    let mut expired = Vec::new();
+    for domain_sessions in by_domain.values_mut() {
+        domain_sessions.sort_by(|left, right| right.started_at.cmp(&left.started_at));
+        for (rank, session) in domain_sessions.iter().enumerate() {
+            if session.pinned || matches!(session.status.as_str(), STATUS_RUNNING | STATUS_PAUSED) {
+                continue;
+            }
+
+            let within_count = policy
+                .keep_last
+                .is_some_and(|keep_last| rank < keep_last as usize);
+            if !within_count && !is_young(session, policy.max_age_days, now) {
+                expired.push((*session).clone());
+            }
+        }
+    }
+
+    expired.sort_by(|left, right| left.started_at.cmp(&right.started_at));
+    expired
+}
+
+pub fn apply_retention(
+    db: &Database,
+    policy: &RetentionPolicy,
+    dry_run: bool,
+) -> AppResult<RetentionReport> {
+    let sessions = sessions_db::fetch_all_sessions(db)?;
+    let expired = select_expired_sessions(&sessions, policy, Utc::now());
+    let freed_bytes = expired
+        .iter()
+        .map(|session| {
+            files::export_disk_usage(
+                Path::new(&session.output_path),
+                session.split_mode,
+                session.images_dir_name.as_deref(),
+            )
+        })
+        .sum();
+
+    let mut failures = Vec::new();
+    if !dry_run {
+        for session in &expired {
+            let removed = match sessions_db::delete_session_by_id(db, &session.id) {
+                Ok(removed) => removed,
+                Err(error) => {
+                    failures.push(format!("{}: {error}", session.id));
+                    continue;
+                }
+            };
+
+            if let Some(removed) = removed {
+                if let Err(error) = files::remove_export_artifacts(
+                    Path::new(&removed.output_path),
+                    removed.split_mode,
+                    removed.images_dir_name.as_deref(),
+                ) {
+                    failures.push(format!("{}: {error}", removed.output_path));
+                }
+            }
+        }
+    }
+
+    Ok(RetentionReport {
+        dry_run,
+        sessions: expired,
+        freed_bytes,
+        failures,
+    })
+}
@@ -1,24 +1,38 @@
-use chrono::Utc;
+#[cfg(feature = "gui")]
+use std::{thread, time::Duration};
+
+#[cfg(feature = "gui")]
 use tauri::{
-    webview::PageLoadEvent, AppHandle, Emitter, Manager, WebviewUrl, WebviewWindowBuilder,
-    WindowEvent,
+    webview::PageLoadEvent, AppHandle, Manager, WebviewUrl, WebviewWindowBuilder, WindowEvent,
 };
 use url::Url;
 
 use crate::{
-    db::sessions as sessions_db, error::AppResult, models::ExportErrorEvent, state::AppState,
+    error::AppResult,
+    services::{date_locale::normalize_notes_locale, micloud},
+};
+#[cfg(feature = "gui")]
+use crate::{
+    models::{ExportJobSettings, ExportProgressEvent, ExportSource},
+    services::{
+        export::{self, ExportEvents, ExportOptions, TauriEvents},
+        export_filter,
+        micloud::MiCloudClient,
+        queue, settings,
+    },
+    state::AppState,
 };
 
 pub const AUTH_WINDOW_LABEL_PREFIX: &str = "auth-export";
-const CHROME_USER_AGENT: &str = "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 \
-                                 (KHTML, like Gecko) Chrome/124.0.0.0 Safari/537.36";
+#[cfg(feature = "gui")]
+const LOGIN_POLL_INTERVAL: Duration = Duration::from_secs(1);
 const SCRAPE_SCRIPT_TEMPLATE: &str = include_str!("scripts/scraper.js");
 
 pub fn auth_window_label(session_id: &str) -> String {
     format!("{AUTH_WINDOW_LABEL_PREFIX}-{session_id}")
 }
 
-pub(crate) fn build_scrape_script(session_id: &str, export_images: bool) -> String {
+pub fn build_scrape_script(session_id: &str, export_images: bool) -> String {
     SCRAPE_SCRIPT_TEMPLATE
         .replace("__SESSION_ID__", session_id)
         .replace(
@@ -27,19 +41,86 @@ pub(crate) fn build_scrape_script(session_id: &str, export_images: bool) -> Stri
         )
 }
 
-pub(crate) fn build_notes_url(domain: &str) -> AppResult<Url> {
-    Ok(Url::parse(&format!(
-        "https://{domain}/note/h5/?_locale=en-US"
-    ))?)
+pub fn build_notes_url(domain: &str, notes_locale: &str) -> AppResult<Url> {
+    build_notes_url_at(&micloud::domain_base_url(domain)?, notes_locale)
+}
+
+/// Builds the notes page URL under an arbitrary Mi Cloud root, such as a local mock server.
+pub fn build_notes_url_at(base_url: &Url, notes_locale: &str) -> AppResult<Url> {
+    let mut url = base_url.join("note/h5/")?;
+    url.query_pairs_mut()
+        .append_pair("_locale", &normalize_notes_locale(notes_locale));
+    Ok(url)
+}
+
+/// Starts an export and opens its shell window; returns the new session id.
+#[cfg(feature = "gui")]
+pub fn launch_export(
+    app: &AppHandle,
+    state: &AppState,
+    job: &ExportJobSettings,
+) -> AppResult<String> {
+    let app_settings = settings::load_settings(&state.settings_path)?;
+    build_notes_url(&job.domain, &app_settings.notes_locale)?;
+    export_filter::check_source(&job.filter, job.source)?;
+
+    let options = ExportOptions::from_job(job, &app_settings);
+    let session_id = export::start_export(state, &options)?;
+
+    let app_handle = app.clone();
+    let state_handle = state.clone();
+    let session_id_for_window = session_id.clone();
+    let domain_for_window = job.domain.clone();
+    let export_images = job.export_images;
+    let source = job.source;
+    tauri::async_runtime::spawn(async move {
+        match create_auth_window(
+            &app_handle,
+            &session_id_for_window,
+            &domain_for_window,
+            &app_settings.notes_locale,
+            export_images,
+            source,
+        ) {
+            Ok(_) => {
+                TauriEvents(app_handle).progress(ExportProgressEvent {
+                    session_id: session_id_for_window,
+                    current: 0,
+                    total: 1,
+                    last_title: String::new(),
+                    notes_count: 0,
+                    images_count: 0,
+                    filtered_count: 0,
+                    log_line: format!(
+                        "Opened shell window for domain {domain_for_window}. Complete login (or wait till session is loaded) to start export."
+                    ),
+                });
+            }
+            Err(error) => {
+                let _ = export::fail_scrape(
+                    &state_handle,
+                    &session_id_for_window,
+                    &format!("Failed to open shell window: {error}"),
+                    &TauriEvents(app_handle.clone()),
+                );
+                queue::advance_queue(&app_handle);
+            }
+        }
+    });
+
+    Ok(session_id)
 }
 
+#[cfg(feature = "gui")]
 pub fn create_auth_window(
     app: &AppHandle,
     session_id: &str,
     domain: &str,
+    notes_locale: &str,
     export_images: bool,
+    source: ExportSource,
 ) -> AppResult<String> {
-    let notes_url = build_notes_url(domain)?;
+    let notes_url = build_notes_url(domain, notes_locale)?;
 
     let window_label = auth_window_label(session_id);
     let session_id_for_close = session_id.to_string();
@@ -49,9 +130,9 @@ pub fn create_auth_window(
         .title("Mi Cloud Shell")
         .inner_size(1200.0, 860.0)
         .closable(true)
-        .user_agent(CHROME_USER_AGENT)
+        .user_agent(micloud::BROWSER_USER_AGENT)
         .on_page_load(move |window, payload| {
-            if payload.event() != PageLoadEvent::Finished {
+            if source != ExportSource::Dom || payload.event() != PageLoadEvent::Finished {
                 return;
             }
 
@@ -68,42 +149,72 @@ pub fn create_auth_window(
         }
 
         let state = app_handle.state::<AppState>();
-        let active_export = {
-            let mut guard = match state.active_export.lock() {
-                Ok(guard) => guard,
-                Err(_) => return,
-            };
+        if let Ok(Some(_)) = export::cancel_export(
+            &state,
+            Some(&session_id_for_close),
+            Some("Mi Cloud access window closed by user."),
+            &TauriEvents(app_handle.clone()),
+        ) {
+            // Closing the window of a queued export stops the queue rather than moving on.
+            let _ = queue::set_queue_running(&state, false);
+        }
+    });
+
+    if source == ExportSource::Api {
+        watch_login(
+            app.clone(),
+            window_label.clone(),
+            session_id.to_string(),
+            domain.to_string(),
+        );
+    }
+
+    Ok(window_label)
+}
+
+/// Waits for the user to sign in to the shell window, then exports through the Mi Cloud API
+/// with the window's cookies. Cookies are read off the main thread, where WebView2 would block.
+#[cfg(feature = "gui")]
+fn watch_login(
+    app: AppHandle,
+    window_label: String,
+    session_id: String,
+    domain: String,
+) {
+    thread::spawn(move || {
+        let Ok(cookie_url) = micloud::domain_base_url(&domain) else {
+            return;
+        };
 
-            if guard
-                .as_ref()
-                .map(|active| active.session_id.as_str() == session_id_for_close.as_str())
-                .unwrap_or(false)
+        let cookie_header = loop {
+            thread::sleep(LOGIN_POLL_INTERVAL);
+            let Some(window) = app.get_webview_window(&window_label) else {
+                return;
+            };
+            let Ok(cookies) = window.cookies_for_url(cookie_url.clone()) else {
+                continue;
+            };
+            if let Some(header) =
+                micloud::login_cookie_header(cookies.iter().map(|c| (c.name(), c.value())))
             {
-                guard.take()
-            } else {
-                None
+                break header;
             }
         };
 
-        if let Some(active) = active_export {
-            let _ = sessions_db::set_session_outcome(
-                &state.db_path,
-                &active.session_id,
-                "cancelled",
-                &Utc::now().to_rfc3339(),
-                active.notes_count,
-                active.images_count,
-                Some("Mi Cloud access window closed by user."),
-            );
-            let _ = app_handle.emit(
-                "export:error",
-                ExportErrorEvent {
-                    session_id: active.session_id,
-                    message: "Mi Cloud access window closed by user.".to_string(),
-                },
-            );
+        let state = app.state::<AppState>();
+        let events = TauriEvents(app.clone());
+        match MiCloudClient::new(cookie_url, &cookie_header) {
+            Ok(client) => {
+                let _ = micloud::run_export(&state, &session_id, &client, &events);
+            }
+            Err(error) => {
+                let _ = export::fail_scrape(&state, &session_id, &error.to_string(), &events);
+            }
         }
-    });
 
-    Ok(window_label)
+        if let Some(window) = app.get_webview_window(&window_label) {
+            let _ = window.close();
+        }
+        queue::advance_queue(&app);
+    });
 }
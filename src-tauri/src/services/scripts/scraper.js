@@ -391,6 +391,10 @@
       let noTargetRetries = 0;
 
       while (processed < total && guard < total * 16) {
+        while (await invoke("is_export_paused", { sessionId })) {
+          await sleep(500);
+        }
+
         const cards = getCards(listContainer);
         const openCard = findOpenCard(listContainer, cards);
         const target = resolveTargetCard(openCard, cards, isFirstIteration);
@@ -0,0 +1,144 @@
+use std::{fs, path::Path};
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use walkdir::WalkDir;
+
+use crate::{
+    db::{
+        connection::Database,
+        search::{self as search_db, NoteIndexEntry, NoteSearchQuery},
+        sessions as sessions_db,
+    },
+    error::{AppError, AppResult},
+    models::{NoteSearchFilters, NoteSearchResult, ReindexReport},
+    services::{
+        converter::{is_markdown_file, parse_single_note, section_title, split_note_sections},
+        date_parser::parse_filter_date,
+        diff::resolve_export_source,
+        timezone::OutputTimezone,
+    },
+};
+
+const DEFAULT_SEARCH_LIMIT: u32 = 50;
+const MAX_SEARCH_LIMIT: u32 = 500;
+
+pub(crate) fn normalize_index_date<Tz: chrono::TimeZone>(value: &DateTime<Tz>) -> String {
+    value
+        .with_timezone(&Utc)
+        .to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+fn normalize_rfc3339_date(value: &str) -> String {
+    DateTime::parse_from_rfc3339(value.trim())
+        .map(|parsed| normalize_index_date(&parsed))
+        .unwrap_or_default()
+}
+
+pub(crate) fn build_match_expression(query: &str) -> Option<String> {
+    let terms = query
+        .split_whitespace()
+        .map(|term| term.replace('"', ""))
+        .filter(|term| !term.is_empty())
+        .map(|term| format!("\"{term}\""))
+        .collect::<Vec<_>>();
+
+    if terms.is_empty() {
+        None
+    } else {
+        Some(terms.join(" "))
+    }
+}
+
+fn collect_markdown_files(source: &Path) -> Vec<std::path::PathBuf> {
+    if source.is_file() {
+        return vec![source.to_path_buf()];
+    }
+
+    WalkDir::new(source)
+        .min_depth(1)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_map(Result::ok)
+        .filter(|entry| entry.file_type().is_file() && is_markdown_file(entry.path()))
+        .map(|entry| entry.into_path())
+        .collect()
+}
+
+pub fn reindex_export(
+    db: &Database,
+    source: &str,
+    timezone: &OutputTimezone,
+) -> AppResult<ReindexReport> {
+    let source_path = resolve_export_source(db, source)?;
+    if !source_path.exists() {
+        return Err(AppError::Message("Source path does not exist.".to_string()));
+    }
+
+    let source_string = source_path.to_string_lossy().to_string();
+    let session_id =
+        sessions_db::fetch_session_by_output_path(db, &source_string)?.map(|session| session.id);
+
+    let files = collect_markdown_files(&source_path);
+    let mut entries = Vec::new();
+    for file in &files {
+        let file_content = fs::read_to_string(file)?;
+        let file_path = file.to_string_lossy().to_string();
+        for section in split_note_sections(&file_content) {
+            let note = parse_single_note(&section, timezone)?;
+            entries.push(NoteIndexEntry {
+                session_id: session_id.clone(),
+                file_path: file_path.clone(),
+                title: section_title(&section),
+                content: note.content,
+                creation_date: normalize_rfc3339_date(&note.creation_date),
+            });
+        }
+    }
+    search_db::replace_path_index(db, &source_string, &entries)?;
+
+    Ok(ReindexReport {
+        source: source_string,
+        session_id,
+        files_count: files.len() as u32,
+        notes_count: entries.len() as u32,
+    })
+}
+
+pub fn search_notes(
+    db: &Database,
+    query: &str,
+    filters: &NoteSearchFilters,
+    timezone: &OutputTimezone,
+) -> AppResult<Vec<NoteSearchResult>> {
+    let match_expression = build_match_expression(query)
+        .ok_or_else(|| AppError::Message("Search query cannot be empty.".to_string()))?;
+    let created_after = parse_filter_date(filters.created_after.as_deref())?;
+    let created_before = parse_filter_date(filters.created_before.as_deref())?;
+    let limit = match filters.limit {
+        Some(0) | None => DEFAULT_SEARCH_LIMIT,
+        Some(limit) => limit.min(MAX_SEARCH_LIMIT),
+    };
+
+    let mut results = search_db::search_notes(
+        db,
+        &NoteSearchQuery {
+            match_expression: &match_expression,
+            session_id: filters
+                .session_id
+                .as_deref()
+                .map(str::trim)
+                .filter(|value| !value.is_empty()),
+            created_after: created_after.as_deref(),
+            created_before: created_before.as_deref(),
+            limit,
+        },
+    )?;
+
+    for result in &mut results {
+        if let Ok(parsed) = DateTime::parse_from_rfc3339(&result.creation_date) {
+            result.creation_date = timezone.format_rfc3339(&parsed);
+        }
+    }
+
+    Ok(results)
+}
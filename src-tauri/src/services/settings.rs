@@ -4,10 +4,17 @@ use std::{
 };
 
 use serde::Deserialize;
+use uuid::Uuid;
 
 use crate::{
     error::{AppError, AppResult},
-    models::AppSettings,
+    models::{AppSettings, ExportProfile, RetentionPolicy},
+    services::{
+        date_locale::{normalize_notes_locale, DEFAULT_NOTES_LOCALE},
+        export_filter::{self, ExportFilter},
+        markdown, micloud,
+        timezone::{normalize_output_timezone, OutputTimezone, LOCAL_TIMEZONE},
+    },
 };
 
 const DEFAULT_EXPORT_DIR_NAME: &str = "Xiaomi Note Exporter";
@@ -23,6 +30,16 @@ struct StoredSettings {
     theme: Option<String>,
     #[serde(default)]
     dark_mode: Option<bool>,
+    #[serde(default)]
+    notes_locale: Option<String>,
+    #[serde(default)]
+    output_timezone: Option<String>,
+    #[serde(default)]
+    retention: Option<RetentionPolicy>,
+    #[serde(default)]
+    relative_session_paths: Option<bool>,
+    #[serde(default)]
+    export_profiles: Option<Vec<ExportProfile>>,
 }
 
 fn normalize_export_dir(dir: &str) -> String {
@@ -46,6 +63,96 @@ fn normalize_theme(theme: &str) -> String {
     }
 }
 
+fn normalize_retention(policy: RetentionPolicy) -> RetentionPolicy {
+    RetentionPolicy {
+        keep_last: policy.keep_last.filter(|keep_last| *keep_last > 0),
+        max_age_days: policy.max_age_days.filter(|max_age_days| *max_age_days > 0),
+    }
+}
+
+fn normalize_export_profile(profile: &ExportProfile) -> AppResult<ExportProfile> {
+    let name = profile.name.trim().to_string();
+    if name.is_empty() {
+        return Err(AppError::Message(
+            "Export profile name cannot be empty.".to_string(),
+        ));
+    }
+    let invalid = |detail: String| AppError::Message(format!("Export profile '{name}': {detail}"));
+
+    let mut settings = profile.settings.clone();
+    settings.domain = settings.domain.trim().to_string();
+    settings.output_dir = normalize_export_dir(&settings.output_dir);
+    settings.timestamp_format = settings.timestamp_format.trim().to_string();
+    settings.created_date_format = settings.created_date_format.trim().to_string();
+
+    micloud::domain_base_url(&settings.domain)
+        .map_err(|_| invalid(format!("invalid domain '{}'.", settings.domain)))?;
+    if !markdown::is_valid_dotnet_format(&settings.timestamp_format) {
+        return Err(invalid(format!(
+            "invalid timestamp format '{}'.",
+            settings.timestamp_format
+        )));
+    }
+    if !markdown::is_valid_dotnet_format(&settings.created_date_format) {
+        return Err(invalid(format!(
+            "invalid created date format '{}'.",
+            settings.created_date_format
+        )));
+    }
+    ExportFilter::compile(&settings.filter)
+        .and_then(|_| export_filter::check_source(&settings.filter, settings.source))
+        .map_err(|error| invalid(error.to_string()))?;
+
+    let id = profile.id.trim();
+    Ok(ExportProfile {
+        id: if id.is_empty() {
+            Uuid::new_v4().to_string()
+        } else {
+            id.to_string()
+        },
+        name,
+        settings,
+    })
+}
+
+fn find_profile_clash(kept: &[ExportProfile], profile: &ExportProfile) -> Option<String> {
+    kept.iter().find_map(|other| {
+        if other.id == profile.id {
+            Some(format!("Duplicate export profile id '{}'.", profile.id))
+        } else if other.name.to_lowercase() == profile.name.to_lowercase() {
+            Some(format!("Duplicate export profile name '{}'.", profile.name))
+        } else {
+            None
+        }
+    })
+}
+
+fn normalize_export_profiles(profiles: &[ExportProfile]) -> AppResult<Vec<ExportProfile>> {
+    let mut normalized: Vec<ExportProfile> = Vec::with_capacity(profiles.len());
+    for profile in profiles {
+        let profile = normalize_export_profile(profile)?;
+        if let Some(clash) = find_profile_clash(&normalized, &profile) {
+            return Err(AppError::Message(clash));
+        }
+        normalized.push(profile);
+    }
+    Ok(normalized)
+}
+
+/// Keeps the stored profiles that still validate, so a hand-edited settings file cannot
+/// block startup.
+fn retain_valid_export_profiles(profiles: &[ExportProfile]) -> Vec<ExportProfile> {
+    let mut kept: Vec<ExportProfile> = Vec::with_capacity(profiles.len());
+    for profile in profiles {
+        if let Ok(profile) = normalize_export_profile(profile) {
+            if find_profile_clash(&kept, &profile).is_none() {
+                kept.push(profile);
+            }
+        }
+    }
+    kept
+}
+
 fn resolve_theme(stored: &StoredSettings) -> String {
     if let Some(theme) = &stored.theme {
         return normalize_theme(theme);
@@ -67,6 +174,11 @@ pub fn bootstrap_settings(settings_path: &Path, documents_dir: &Path) -> AppResu
                 .to_string_lossy()
                 .to_string(),
             theme: THEME_SYSTEM.to_string(),
+            notes_locale: DEFAULT_NOTES_LOCALE.to_string(),
+            output_timezone: LOCAL_TIMEZONE.to_string(),
+            retention: RetentionPolicy::default(),
+            relative_session_paths: false,
+            export_profiles: Vec::new(),
         }
     };
 
@@ -84,6 +196,11 @@ pub fn bootstrap_settings(settings_path: &Path, documents_dir: &Path) -> AppResu
     let normalized_settings = AppSettings {
         default_export_dir: resolved_default_dir.to_string_lossy().to_string(),
         theme: normalize_theme(&settings.theme),
+        notes_locale: normalize_notes_locale(&settings.notes_locale),
+        output_timezone: normalize_output_timezone(&settings.output_timezone),
+        retention: normalize_retention(settings.retention),
+        relative_session_paths: settings.relative_session_paths,
+        export_profiles: retain_valid_export_profiles(&settings.export_profiles),
     };
 
     save_settings(settings_path, &normalized_settings)?;
@@ -103,10 +220,22 @@ pub fn load_settings(settings_path: &Path) -> AppResult<AppSettings> {
     let settings = AppSettings {
         default_export_dir: stored.default_export_dir,
         theme,
+        notes_locale: normalize_notes_locale(stored.notes_locale.as_deref().unwrap_or_default()),
+        output_timezone: normalize_output_timezone(
+            stored.output_timezone.as_deref().unwrap_or_default(),
+        ),
+        retention: normalize_retention(stored.retention.unwrap_or_default()),
+        relative_session_paths: stored.relative_session_paths.unwrap_or_default(),
+        export_profiles: stored.export_profiles.unwrap_or_default(),
     };
     Ok(settings)
 }
 
+pub fn load_output_timezone(settings_path: &Path) -> AppResult<OutputTimezone> {
+    let settings = load_settings(settings_path)?;
+    Ok(OutputTimezone::from_settings(&settings))
+}
+
 pub fn save_settings(settings_path: &Path, settings: &AppSettings) -> AppResult<()> {
     let export_dir = normalize_export_dir(&settings.default_export_dir);
     if export_dir.is_empty() {
@@ -115,6 +244,9 @@ pub fn save_settings(settings_path: &Path, settings: &AppSettings) -> AppResult<
         ));
     }
 
+    let output_timezone = OutputTimezone::parse(&settings.output_timezone)?;
+    let export_profiles = normalize_export_profiles(&settings.export_profiles)?;
+
     let export_path = PathBuf::from(export_dir);
     fs::create_dir_all(&export_path)?;
 
@@ -125,6 +257,11 @@ pub fn save_settings(settings_path: &Path, settings: &AppSettings) -> AppResult<
     let normalized = AppSettings {
         default_export_dir: export_path.to_string_lossy().to_string(),
         theme: normalize_theme(&settings.theme),
+        notes_locale: normalize_notes_locale(&settings.notes_locale),
+        output_timezone: output_timezone.name(),
+        retention: normalize_retention(settings.retention),
+        relative_session_paths: settings.relative_session_paths,
+        export_profiles,
     };
 
     let serialized = serde_json::to_string_pretty(&normalized)?;
@@ -132,3 +269,56 @@ pub fn save_settings(settings_path: &Path, settings: &AppSettings) -> AppResult<
 
     Ok(())
 }
+
+/// Finds a saved export profile by id, or by name ignoring case.
+pub fn find_export_profile<'a>(
+    settings: &'a AppSettings,
+    profile: &str,
+) -> AppResult<&'a ExportProfile> {
+    let wanted = profile.trim();
+    settings
+        .export_profiles
+        .iter()
+        .find(|candidate| candidate.id == wanted)
+        .or_else(|| {
+            settings
+                .export_profiles
+                .iter()
+                .find(|candidate| candidate.name.to_lowercase() == wanted.to_lowercase())
+        })
+        .ok_or_else(|| AppError::Message(format!("Export profile not found: {wanted}")))
+}
+
+/// Adds a profile, or replaces the one with the same id, and returns it as saved.
+pub fn save_export_profile(
+    settings_path: &Path,
+    profile: &ExportProfile,
+) -> AppResult<ExportProfile> {
+    let profile = normalize_export_profile(profile)?;
+    let mut settings = load_settings(settings_path)?;
+    match settings
+        .export_profiles
+        .iter_mut()
+        .find(|existing| existing.id == profile.id)
+    {
+        Some(existing) => *existing = profile.clone(),
+        None => settings.export_profiles.push(profile.clone()),
+    }
+    save_settings(settings_path, &settings)?;
+    Ok(profile)
+}
+
+pub fn delete_export_profile(settings_path: &Path, profile_id: &str) -> AppResult<AppSettings> {
+    let mut settings = load_settings(settings_path)?;
+    let before = settings.export_profiles.len();
+    settings
+        .export_profiles
+        .retain(|profile| profile.id != profile_id);
+    if settings.export_profiles.len() == before {
+        return Err(AppError::Message(format!(
+            "Export profile not found: {profile_id}"
+        )));
+    }
+    save_settings(settings_path, &settings)?;
+    load_settings(settings_path)
+}
@@ -0,0 +1,157 @@
+use std::{cmp::Reverse, collections::BTreeMap, path::Path};
+
+use chrono::DateTime;
+
+use crate::{
+    db::{connection::Database, sessions as sessions_db},
+    error::AppResult,
+    models::{ErrorFrequency, ExportStatistics, Session, SessionStatistics, StatusCount},
+    services::files,
+};
+
+const COMMON_ERRORS_LIMIT: usize = 5;
+const RECENT_SESSIONS_WINDOW: usize = 10;
+const STATUS_ERROR: &str = "error";
+const STATUS_RUNNING: &str = "running";
+const STATUS_PAUSED: &str = "paused";
+
+fn is_finished(session: &Session) -> bool {
+    !matches!(session.status.as_str(), STATUS_RUNNING | STATUS_PAUSED)
+}
+
+fn ratio(part: f64, total: f64) -> f64 {
+    if total > 0.0 {
+        part / total
+    } else {
+        0.0
+    }
+}
+
+fn session_duration_ms(session: &Session) -> Option<i64> {
+    let started_at = DateTime::parse_from_rfc3339(&session.started_at).ok()?;
+    let completed_at = DateTime::parse_from_rfc3339(session.completed_at.as_deref()?).ok()?;
+    let duration = (completed_at - started_at).num_milliseconds();
+    (duration > 0).then_some(duration)
+}
+
+fn notes_per_minute(notes_count: u64, duration_ms: i64) -> f64 {
+    notes_count as f64 / (duration_ms as f64 / 60_000.0)
+}
+
+fn failure_rate<'a>(sessions: impl Iterator<Item = &'a Session>) -> f64 {
+    let mut finished = 0_u32;
+    let mut failed = 0_u32;
+    for session in sessions.filter(|session| is_finished(session)) {
+        finished += 1;
+        if session.status == STATUS_ERROR {
+            failed += 1;
+        }
+    }
+    ratio(failed as f64, finished as f64)
+}
+
+fn summarize_session(session: &Session) -> SessionStatistics {
+    let output_path = Path::new(&session.output_path);
+    let disk_usage_bytes = files::export_disk_usage(
+        output_path,
+        session.split_mode,
+        session.images_dir_name.as_deref(),
+    );
+
+    let duration_ms = session_duration_ms(session);
+    SessionStatistics {
+        session_id: session.id.clone(),
+        started_at: session.started_at.clone(),
+        status: session.status.clone(),
+        notes_count: session.notes_count,
+        images_count: session.images_count,
+        duration_ms,
+        notes_per_minute: duration_ms
+            .map(|duration_ms| notes_per_minute(session.notes_count as u64, duration_ms)),
+        output_exists: output_path.exists(),
+        disk_usage_bytes,
+    }
+}
+
+pub(crate) fn compute_export_statistics(sessions: &[Session]) -> ExportStatistics {
+    let total_sessions = sessions.len() as u32;
+    let total_notes = sessions
+        .iter()
+        .map(|session| session.notes_count as u64)
+        .sum::<u64>();
+    let total_images = sessions
+        .iter()
+        .map(|session| session.images_count as u64)
+        .sum::<u64>();
+
+    let mut status_totals = BTreeMap::<&str, u32>::new();
+    let mut error_totals = BTreeMap::<&str, u32>::new();
+    for session in sessions {
+        *status_totals.entry(session.status.as_str()).or_default() += 1;
+        if let Some(message) = session
+            .error_message
+            .as_deref()
+            .map(str::trim)
+            .filter(|message| !message.is_empty())
+        {
+            *error_totals.entry(message).or_default() += 1;
+        }
+    }
+
+    let status_counts = status_totals
+        .into_iter()
+        .map(|(status, count)| StatusCount {
+            status: status.to_string(),
+            count,
+            rate: ratio(count as f64, total_sessions as f64),
+        })
+        .collect::<Vec<_>>();
+
+    let mut common_errors = error_totals
+        .into_iter()
+        .map(|(message, count)| ErrorFrequency {
+            message: message.to_string(),
+            count,
+        })
+        .collect::<Vec<_>>();
+    common_errors.sort_by_key(|error| Reverse(error.count));
+    common_errors.truncate(COMMON_ERRORS_LIMIT);
+
+    let session_statistics = sessions.iter().map(summarize_session).collect::<Vec<_>>();
+    let (timed_notes, timed_ms) = session_statistics
+        .iter()
+        .filter_map(|stats| stats.duration_ms.map(|ms| (stats.notes_count as u64, ms)))
+        .fold(
+            (0_u64, 0_i64),
+            |(notes, ms), (session_notes, session_ms)| (notes + session_notes, ms + session_ms),
+        );
+
+    ExportStatistics {
+        total_sessions,
+        total_notes,
+        total_images,
+        average_notes_per_session: ratio(total_notes as f64, total_sessions as f64),
+        average_images_per_session: ratio(total_images as f64, total_sessions as f64),
+        average_notes_per_minute: (timed_ms > 0).then(|| notes_per_minute(timed_notes, timed_ms)),
+        failure_rate: failure_rate(sessions.iter()),
+        recent_failure_rate: failure_rate(
+            sessions
+                .iter()
+                .rev()
+                .filter(|session| is_finished(session))
+                .take(RECENT_SESSIONS_WINDOW),
+        ),
+        status_counts,
+        common_errors,
+        total_disk_usage_bytes: session_statistics
+            .iter()
+            .map(|stats| stats.disk_usage_bytes)
+            .sum(),
+        sessions: session_statistics,
+    }
+}
+
+pub fn get_export_statistics(db: &Database) -> AppResult<ExportStatistics> {
+    let sessions = sessions_db::fetch_all_sessions(db)?;
+    Ok(compute_export_statistics(&sessions))
+}
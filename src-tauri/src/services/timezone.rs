@@ -0,0 +1,95 @@
+use std::str::FromStr;
+
+use chrono::{DateTime, FixedOffset, Local, TimeZone, Utc};
+use chrono_tz::Tz;
+
+use crate::{
+    error::{AppError, AppResult},
+    models::AppSettings,
+    services::{date_locale::DateLocale, date_parser::try_parse_created_date_in},
+};
+
+pub const LOCAL_TIMEZONE: &str = "local";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputTimezone {
+    Local,
+    Named(Tz),
+}
+
+impl OutputTimezone {
+    pub fn parse(name: &str) -> AppResult<Self> {
+        let trimmed = name.trim();
+        if trimmed.is_empty() || trimmed.eq_ignore_ascii_case(LOCAL_TIMEZONE) {
+            return Ok(Self::Local);
+        }
+        if trimmed.eq_ignore_ascii_case("utc") || trimmed.eq_ignore_ascii_case("z") {
+            return Ok(Self::Named(Tz::UTC));
+        }
+
+        Tz::from_str(trimmed)
+            .map(Self::Named)
+            .map_err(|_| AppError::Message(format!("Unknown timezone '{trimmed}'.")))
+    }
+
+    pub fn from_settings(settings: &AppSettings) -> Self {
+        Self::parse(&settings.output_timezone).unwrap_or(Self::Local)
+    }
+
+    pub fn name(&self) -> String {
+        match self {
+            Self::Local => LOCAL_TIMEZONE.to_string(),
+            Self::Named(tz) => tz.name().to_string(),
+        }
+    }
+
+    pub fn now(&self) -> DateTime<FixedOffset> {
+        self.convert(&Utc::now())
+    }
+
+    pub fn convert<Z: TimeZone>(&self, value: &DateTime<Z>) -> DateTime<FixedOffset> {
+        match self {
+            Self::Local => value.with_timezone(&Local).fixed_offset(),
+            Self::Named(tz) => value.with_timezone(tz).fixed_offset(),
+        }
+    }
+
+    pub fn datetime_from_unix_timestamp(&self, timestamp: i64) -> Option<DateTime<FixedOffset>> {
+        let utc = if timestamp.abs() >= 1_000_000_000_000 {
+            Utc.timestamp_millis_opt(timestamp).single()
+        } else {
+            Utc.timestamp_opt(timestamp, 0).single()
+        }?;
+        Some(self.convert(&utc))
+    }
+
+    pub fn parse_created_date(
+        &self,
+        input: &str,
+        locale: &DateLocale,
+    ) -> AppResult<DateTime<FixedOffset>> {
+        match self {
+            Self::Local => {
+                try_parse_created_date_in(input, locale, &Local).map(|value| value.fixed_offset())
+            }
+            Self::Named(tz) => {
+                try_parse_created_date_in(input, locale, tz).map(|value| value.fixed_offset())
+            }
+        }
+    }
+
+    // The system timezone is not portable between machines, so RFC 3339 output
+    // stays normalized to UTC unless an explicit zone is configured.
+    pub fn format_rfc3339<Z: TimeZone>(&self, value: &DateTime<Z>) -> String {
+        match self {
+            Self::Local => value.with_timezone(&Utc).to_rfc3339(),
+            Self::Named(tz) => value.with_timezone(tz).to_rfc3339(),
+        }
+    }
+}
+
+pub fn normalize_output_timezone(name: &str) -> String {
+    OutputTimezone::parse(name)
+        .unwrap_or(OutputTimezone::Local)
+        .name()
+}
@@ -1,22 +1,37 @@
 use std::{
+    collections::{BTreeSet, HashSet},
     path::PathBuf,
     sync::{Arc, Mutex},
-    time::Instant,
+    time::{Duration, Instant},
 };
 
+use crate::{
+    db::{connection::Database, search::NoteIndexEntry, sessions as sessions_db},
+    error::AppResult,
+    models::{ExportQueueStatus, UnparsedDatePolicy},
+    services::{
+        date_locale::DateLocale, export::DryRunPlan, export_filter::ExportFilter,
+        timezone::OutputTimezone,
+    },
+};
+
+pub const PROGRESS_BATCH_SIZE: usize = 25;
+
 #[derive(Clone)]
 pub struct AppState {
-    pub db_path: PathBuf,
+    pub db: Database,
     pub settings_path: PathBuf,
     pub active_export: Arc<Mutex<Option<ActiveExportState>>>,
+    pub export_queue: Arc<Mutex<ExportQueueStatus>>,
 }
 
 impl AppState {
-    pub fn new(db_path: PathBuf, settings_path: PathBuf) -> Self {
+    pub fn new(db: Database, settings_path: PathBuf) -> Self {
         Self {
-            db_path,
+            db,
             settings_path,
             active_export: Arc::new(Mutex::new(None)),
+            export_queue: Arc::new(Mutex::new(ExportQueueStatus::default())),
         }
     }
 }
@@ -28,12 +43,64 @@ pub struct ActiveExportState {
     pub name_by_title: bool,
     pub chrono_timestamp_format: String,
     pub chrono_created_date_format: String,
+    pub date_locale: &'static DateLocale,
+    pub output_timezone: OutputTimezone,
     pub export_images: bool,
+    pub skip_duplicates: bool,
+    pub unparsed_date_policy: UnparsedDatePolicy,
     pub output_root: PathBuf,
     pub images_dir: PathBuf,
     pub total_notes: u32,
     pub notes_count: u32,
     pub images_count: u32,
+    pub duplicates_skipped: u32,
+    pub unparsed_dates: u32,
+    pub filter: ExportFilter,
+    pub filtered_count: u32,
+    pub dry_run: Option<DryRunPlan>,
+    pub current_folder: Option<String>,
+    pub folders: BTreeSet<String>,
+    pub seen_content_hashes: HashSet<String>,
+    pub pending_index_entries: Vec<NoteIndexEntry>,
     pub started_at: Option<Instant>,
+    pub paused_at: Option<Instant>,
+    pub paused_for: Duration,
     pub auth_window_label: String,
 }
+
+impl ActiveExportState {
+    pub fn flush_progress(&mut self, db: &Database) -> AppResult<()> {
+        sessions_db::update_session_progress(
+            db,
+            &self.session_id,
+            self.notes_count,
+            self.images_count,
+            self.unparsed_dates,
+            self.filtered_count,
+            &self.pending_index_entries,
+        )?;
+        self.pending_index_entries.clear();
+        Ok(())
+    }
+
+    /// Time spent exporting since the total was reported, excluding pauses.
+    pub fn active_elapsed(&self) -> Duration {
+        let Some(started_at) = self.started_at else {
+            return Duration::ZERO;
+        };
+        let paused = self.paused_for
+            + self
+                .paused_at
+                .map(|paused_at| paused_at.elapsed())
+                .unwrap_or_default();
+        started_at.elapsed().saturating_sub(paused)
+    }
+
+    pub fn queue_index_entry(&mut self, db: &Database, entry: NoteIndexEntry) -> AppResult<()> {
+        self.pending_index_entries.push(entry);
+        if self.pending_index_entries.len() >= PROGRESS_BATCH_SIZE {
+            self.flush_progress(db)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,224 @@
+use std::path::{Path, PathBuf};
+
+use crate::{
+    db::{
+        connection::{latest_schema_version, Database},
+        search::{replace_path_index, NoteIndexEntry},
+        sessions::{fetch_all_sessions, init_db, insert_session},
+    },
+    models::{AppSettings, ExportJobSettings, RetentionPolicy, Session},
+    services::{
+        backup::{export_app_data, import_app_data},
+        export::{DEFAULT_CREATED_DATE_FORMAT, DEFAULT_TIMESTAMP_FORMAT},
+        files::replace_path_prefix,
+        queue::{enqueue_export, queue_status},
+        settings,
+    },
+    state::AppState,
+    tests::{session_fixture, TestDir},
+};
+
+fn write_settings(settings_path: &Path, export_root: &Path) {
+    settings::save_settings(
+        settings_path,
+        &AppSettings {
+            default_export_dir: export_root.to_string_lossy().to_string(),
+            theme: "dark".to_string(),
+            notes_locale: "de-DE".to_string(),
+            output_timezone: "UTC".to_string(),
+            retention: RetentionPolicy {
+                keep_last: Some(5),
+                max_age_days: None,
+            },
+            relative_session_paths: false,
+            export_profiles: Vec::new(),
+        },
+    )
+    .expect("settings should be saved");
+}
+
+fn seed_source(test_dir: &TestDir) -> (Database, PathBuf, PathBuf) {
+    let export_root = test_dir.path.join("old_exports");
+    let settings_path = test_dir.path.join("source/settings.json");
+    write_settings(&settings_path, &export_root);
+
+    let db = init_db(&test_dir.path.join("source.db")).expect("source database should initialize");
+    let output_path = export_root.join("exported_notes_01.md");
+    insert_session(
+        &db,
+        &Session {
+            completed_at: Some("2026-01-01T10:05:00+00:00".to_string()),
+            output_path: output_path.to_string_lossy().to_string(),
+            pinned: true,
+            label: Some("Work phone".to_string()),
+            ..session_fixture("session-1")
+        },
+    )
+    .expect("session should be inserted");
+    replace_path_index(
+        &db,
+        &output_path.to_string_lossy(),
+        &[NoteIndexEntry {
+            session_id: Some("session-1".to_string()),
+            file_path: output_path.to_string_lossy().to_string(),
+            title: "Groceries".to_string(),
+            content: "Buy tomatoes".to_string(),
+            creation_date: "2026-01-01T00:00:00Z".to_string(),
+        }],
+    )
+    .expect("note should be indexed");
+
+    (db, settings_path, export_root)
+}
+
+fn target_state(test_dir: &TestDir) -> AppState {
+    let db = init_db(&test_dir.path.join("target.db")).expect("target database should initialize");
+    AppState::new(db, test_dir.path.join("target/settings.json"))
+}
+
+fn indexed_paths(db: &Database) -> Vec<String> {
+    let conn = db.connection().expect("connection should lock");
+    let mut stmt = conn
+        .prepare("SELECT file_path FROM note_search")
+        .expect("statement should prepare");
+    let rows = stmt
+        .query_map([], |row| row.get::<_, String>(0))
+        .expect("rows should load");
+    rows.map(|row| row.expect("row should decode")).collect()
+}
+
+#[test]
+fn backup_round_trip_restores_sessions_settings_and_relocates_paths() {
+    let test_dir = TestDir::new("round_trip");
+    let (source_db, source_settings, old_root) = seed_source(&test_dir);
+    let archive_path = test_dir.path.join("backup/app-data.zip");
+
+    let manifest = export_app_data(&source_db, &source_settings, &archive_path, true)
+        .expect("backup should be created");
+    assert_eq!(manifest.schema_version, latest_schema_version());
+    assert_eq!(manifest.export_root, old_root.to_string_lossy());
+    assert!(archive_path.is_file());
+
+    let target = target_state(&test_dir);
+    let new_root = test_dir.path.join("new_exports");
+    let report = import_app_data(&target, &archive_path, Some(&new_root.to_string_lossy()))
+        .expect("backup should be restored");
+
+    assert_eq!(report.sessions_count, 1);
+    assert_eq!(report.relocated_sessions, 1);
+    let sessions = fetch_all_sessions(&target.db).expect("sessions should load");
+    let expected_path = new_root
+        .join("exported_notes_01.md")
+        .to_string_lossy()
+        .to_string();
+    assert_eq!(sessions[0].output_path, expected_path);
+    assert_eq!(sessions[0].label.as_deref(), Some("Work phone"));
+    assert!(sessions[0].pinned);
+    assert_eq!(indexed_paths(&target.db), [expected_path]);
+
+    let restored = settings::load_settings(&target.settings_path).expect("settings should load");
+    assert_eq!(restored.default_export_dir, new_root.to_string_lossy());
+    assert_eq!(restored.notes_locale, "de-DE");
+    assert_eq!(restored.retention.keep_last, Some(5));
+}
+
+#[test]
+fn backup_without_search_index_leaves_index_empty() {
+    let test_dir = TestDir::new("no_index");
+    let (source_db, source_settings, _) = seed_source(&test_dir);
+    let archive_path = test_dir.path.join("app-data.zip");
+
+    let manifest = export_app_data(&source_db, &source_settings, &archive_path, false)
+        .expect("backup should be created");
+    assert!(!manifest.includes_search_index);
+    assert_eq!(indexed_paths(&source_db).len(), 1);
+
+    let target = target_state(&test_dir);
+    import_app_data(&target, &archive_path, None).expect("backup should be restored");
+
+    assert!(indexed_paths(&target.db).is_empty());
+    assert_eq!(
+        fetch_all_sessions(&target.db)
+            .expect("sessions should load")
+            .len(),
+        1
+    );
+}
+
+#[test]
+fn import_reloads_the_export_queue_from_the_backup() {
+    let test_dir = TestDir::new("queue");
+    let (source_db, source_settings, _) = seed_source(&test_dir);
+    let archive_path = test_dir.path.join("app-data.zip");
+    export_app_data(&source_db, &source_settings, &archive_path, true)
+        .expect("backup should be created");
+
+    let target = target_state(&test_dir);
+    enqueue_export(
+        &target,
+        ExportJobSettings {
+            domain: "us.i.mi.com".to_string(),
+            output_dir: test_dir.path.join("queued").to_string_lossy().into_owned(),
+            split: false,
+            name_by_title: false,
+            timestamp_format: DEFAULT_TIMESTAMP_FORMAT.to_string(),
+            created_date_format: DEFAULT_CREATED_DATE_FORMAT.to_string(),
+            export_images: false,
+            skip_duplicates: true,
+            unparsed_date_policy: Default::default(),
+            source: Default::default(),
+            filter: Default::default(),
+            dry_run: false,
+        },
+    )
+    .expect("job should be queued");
+
+    import_app_data(&target, &archive_path, None).expect("backup should be restored");
+
+    assert!(queue_status(&target)
+        .expect("queue should load")
+        .jobs
+        .is_empty());
+}
+
+#[test]
+fn import_rejects_backup_from_newer_schema() {
+    let test_dir = TestDir::new("newer_schema");
+    let (source_db, source_settings, _) = seed_source(&test_dir);
+    source_db
+        .connection()
+        .expect("connection should lock")
+        .execute(
+            "INSERT INTO schema_migrations (version, name) VALUES (?1, 'future')",
+            [latest_schema_version() + 1],
+        )
+        .expect("future migration should be recorded");
+    let archive_path = test_dir.path.join("app-data.zip");
+    export_app_data(&source_db, &source_settings, &archive_path, true)
+        .expect("backup should be created");
+
+    let target = target_state(&test_dir);
+    let error =
+        import_app_data(&target, &archive_path, None).expect_err("newer schema should be rejected");
+
+    assert!(error.to_string().contains("schema version"));
+    assert!(fetch_all_sessions(&target.db)
+        .expect("sessions should load")
+        .is_empty());
+}
+
+#[test]
+fn replace_path_prefix_matches_whole_components() {
+    assert_eq!(
+        replace_path_prefix("/data/exports/notes.md", "/data/exports/", "/mnt/notes"),
+        Some("/mnt/notes/notes.md".to_string())
+    );
+    assert_eq!(
+        replace_path_prefix("C:\\Exports\\a.md", "C:\\Exports", "D:\\Backup"),
+        Some("D:\\Backup\\a.md".to_string())
+    );
+    assert_eq!(
+        replace_path_prefix("/data/exports-old/notes.md", "/data/exports", "/mnt"),
+        None
+    );
+}
@@ -1,39 +1,18 @@
 use std::{
     fs,
     path::{Path, PathBuf},
-    time::{SystemTime, UNIX_EPOCH},
 };
 
-use crate::services::converter::{
-    convert_to_json, is_markdown_file, parse_notes_from_markdown, parse_single_note,
+use crate::{
+    services::{
+        converter::{
+            convert_to_json, is_markdown_file, parse_notes_from_markdown, parse_single_note,
+        },
+        timezone::OutputTimezone,
+    },
+    tests::TestDir,
 };
 
-struct TestDir {
-    path: PathBuf,
-}
-
-impl TestDir {
-    fn new(name: &str) -> Self {
-        let unique = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .expect("system time should be after unix epoch")
-            .as_nanos();
-        let path = std::env::temp_dir().join(format!(
-            "xiaomi_note_exporter_converter_{name}_{}_{}",
-            std::process::id(),
-            unique
-        ));
-        fs::create_dir_all(&path).expect("temp test directory should be created");
-        Self { path }
-    }
-}
-
-impl Drop for TestDir {
-    fn drop(&mut self) {
-        let _ = fs::remove_dir_all(&self.path);
-    }
-}
-
 fn note_section(title: &str, body: &str, created: &str) -> String {
     format!("****\n## {title}\n{body}\n*Created at: {created}*\n")
 }
@@ -50,21 +29,57 @@ fn markdown_extension_detection_is_case_insensitive() {
 fn parse_single_note_extracts_body_and_created_date() {
     let raw = note_section("Title", "line 1\nline 2", "22/02/2026 10:30");
 
-    let note = parse_single_note(&raw).expect("single note should parse");
+    let note = parse_single_note(&raw, &OutputTimezone::Local).expect("single note should parse");
 
     assert_eq!(note.content, "line 1\nline 2");
     assert!(!note.id.is_empty());
     assert_eq!(note.creation_date, note.last_modified);
 }
 
+#[test]
+fn parse_single_note_reads_modified_date_after_created_line() {
+    let raw = format!(
+        "{}*Modified at: 25/02/2026 18:05*\n",
+        note_section("Title", "body", "22/02/2026 10:30")
+    );
+
+    let note = parse_single_note(&raw, &OutputTimezone::Local)
+        .expect("note with modified date should parse");
+
+    assert_eq!(note.content, "body");
+    assert_ne!(note.creation_date, note.last_modified);
+    assert!(note.creation_date.starts_with("2026-02-22"));
+    assert!(note.last_modified.starts_with("2026-02-25"));
+}
+
 #[test]
 fn parse_single_note_fails_without_created_line() {
-    let error =
-        parse_single_note("## Title\nBody only\n").expect_err("created line should be required");
+    let error = parse_single_note("## Title\nBody only\n", &OutputTimezone::Local)
+        .expect_err("created line should be required");
 
     assert!(error.to_string().contains("missing created date line"));
 }
 
+#[test]
+fn parse_single_note_leaves_unparseable_created_date_blank() {
+    let raw = note_section("Title", "body", "sometime last spring");
+
+    let note = parse_single_note(&raw, &OutputTimezone::Local)
+        .expect("note with unparseable date should parse");
+
+    assert_eq!(note.content, "body");
+    assert!(note.creation_date.is_empty());
+}
+
+#[test]
+fn parse_single_note_accepts_blank_created_line() {
+    let note = parse_single_note("## Title\nbody\n*Created at:*\n", &OutputTimezone::Local)
+        .expect("note with blank created date should parse");
+
+    assert_eq!(note.content, "body");
+    assert!(note.creation_date.is_empty());
+}
+
 #[test]
 fn parse_notes_from_markdown_handles_multiple_sections() {
     let content = format!(
@@ -73,7 +88,8 @@ fn parse_notes_from_markdown_handles_multiple_sections() {
         note_section("Second", "beta", "22/02/2026 10:31"),
     );
 
-    let notes = parse_notes_from_markdown(&content).expect("multiple notes should parse");
+    let notes = parse_notes_from_markdown(&content, &OutputTimezone::Local)
+        .expect("multiple notes should parse");
 
     assert_eq!(notes.len(), 2);
     assert_eq!(notes[0].content, "alpha");
@@ -101,6 +117,7 @@ fn convert_to_json_reads_only_markdown_files_from_directory() {
     let written_path = convert_to_json(
         source.to_string_lossy().as_ref(),
         output.to_string_lossy().as_ref(),
+        &OutputTimezone::Local,
     )
     .expect("conversion should succeed");
 
@@ -127,8 +144,19 @@ fn convert_to_json_errors_when_no_markdown_files_exist() {
     let error = convert_to_json(
         source.to_string_lossy().as_ref(),
         output.to_string_lossy().as_ref(),
+        &OutputTimezone::Local,
     )
     .expect_err("conversion should fail without markdown inputs");
 
     assert!(error.to_string().contains("No markdown notes were found"));
 }
+
+#[test]
+fn parse_single_note_renders_dates_in_configured_timezone() {
+    let raw = note_section("Title", "body", "22/02/2026 10:30");
+    let timezone = OutputTimezone::parse("Asia/Tokyo").expect("timezone should parse");
+
+    let note = parse_single_note(&raw, &timezone).expect("note should parse");
+
+    assert_eq!(note.creation_date, "2026-02-22T10:30:00+09:00");
+}
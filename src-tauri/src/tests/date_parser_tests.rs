@@ -1,7 +1,18 @@
 use chrono::{DateTime, Datelike, Local, Timelike, Utc};
 
-use crate::services::date_parser::{
-    parse_created_date, parse_created_line_to_rfc3339, parse_markdown_created_line,
+use url::Url;
+
+use crate::{
+    error::AppError,
+    services::{
+        date_locale::{locale_for_code, locale_from_notes_url, CHINESE},
+        date_parser::{
+            parse_created_date, parse_created_date_with_locale, parse_created_line_to_rfc3339,
+            parse_markdown_created_line, try_parse_created_date,
+            try_parse_created_date_with_locale,
+        },
+        timezone::OutputTimezone,
+    },
 };
 
 #[test]
@@ -21,8 +32,9 @@ fn parses_rfc3339_created_line_without_changing_instant() {
 
 #[test]
 fn parses_markdown_created_line_markers() {
-    let output = parse_markdown_created_line("*Created at: 22/02/2026 11:45*")
-        .expect("markdown created line should parse");
+    let output =
+        parse_markdown_created_line("*Created at: 22/02/2026 11:45*", &OutputTimezone::Local)
+            .expect("markdown created line should parse");
     let parsed_local = DateTime::parse_from_rfc3339(&output)
         .expect("valid output rfc3339")
         .with_timezone(&Local);
@@ -36,8 +48,8 @@ fn parses_markdown_created_line_markers() {
 
 #[test]
 fn rejects_markdown_created_line_without_value() {
-    let error =
-        parse_markdown_created_line("*Created at:*").expect_err("empty value should be rejected");
+    let error = parse_markdown_created_line("*Created at:*", &OutputTimezone::Local)
+        .expect_err("empty value should be rejected");
 
     assert!(error.to_string().contains("missing created date value"));
 }
@@ -62,3 +74,248 @@ fn parses_relative_time_hours_ago() {
         "expected ~7200s delta, got {seconds}"
     );
 }
+
+fn assert_seconds_ago(parsed: DateTime<Local>, expected_seconds: i64) {
+    let seconds = (Local::now() - parsed).num_seconds();
+    assert!(
+        (expected_seconds - 120..=expected_seconds + 120).contains(&seconds),
+        "expected ~{expected_seconds}s delta, got {seconds}"
+    );
+}
+
+fn assert_days_ago_at(parsed: DateTime<Local>, days: i64, hour: u32, minute: u32) {
+    let expected_date = (Local::now() - chrono::Duration::days(days)).date_naive();
+    assert_eq!(parsed.date_naive(), expected_date);
+    assert_eq!(parsed.hour(), hour);
+    assert_eq!(parsed.minute(), minute);
+}
+
+fn assert_ymd_hm(parsed: DateTime<Local>, ymd: (i32, u32, u32), hm: (u32, u32)) {
+    assert_eq!((parsed.year(), parsed.month(), parsed.day()), ymd);
+    assert_eq!((parsed.hour(), parsed.minute()), hm);
+}
+
+#[test]
+fn locale_lookup_uses_language_prefix_and_falls_back_to_english() {
+    assert_eq!(locale_for_code("zh-CN").language, "zh");
+    assert_eq!(locale_for_code("ru_RU").language, "ru");
+    assert_eq!(locale_for_code("PL").language, "pl");
+    assert_eq!(locale_for_code("fr-FR").language, "en");
+    assert_eq!(locale_for_code("").language, "en");
+}
+
+#[test]
+fn locale_is_selected_from_notes_url() {
+    let url = Url::parse("https://i.mi.com/note/h5/?_locale=de-DE").expect("valid url");
+    assert_eq!(locale_from_notes_url(&url).language, "de");
+
+    let without_locale = Url::parse("https://i.mi.com/note/h5/").expect("valid url");
+    assert_eq!(locale_from_notes_url(&without_locale).language, "en");
+}
+
+#[test]
+fn parses_english_fixtures() {
+    let locale = locale_for_code("en-US");
+
+    assert_seconds_ago(parse_created_date_with_locale("3 minutes ago", locale), 180);
+    assert_seconds_ago(parse_created_date_with_locale("an hour ago", locale), 3600);
+    assert_days_ago_at(
+        parse_created_date_with_locale("Yesterday 10:23", locale),
+        1,
+        10,
+        23,
+    );
+    assert_ymd_hm(
+        parse_created_date_with_locale("Mar 12, 2024 10:23", locale),
+        (2024, 3, 12),
+        (10, 23),
+    );
+}
+
+#[test]
+fn parses_chinese_fixtures() {
+    let locale = locale_for_code("zh-CN");
+
+    assert_seconds_ago(parse_created_date_with_locale("刚刚", locale), 0);
+    assert_seconds_ago(parse_created_date_with_locale("3 分钟前", locale), 180);
+    assert_seconds_ago(parse_created_date_with_locale("2小时前", locale), 7200);
+    assert_days_ago_at(
+        parse_created_date_with_locale("昨天 10:23", locale),
+        1,
+        10,
+        23,
+    );
+    assert_days_ago_at(
+        parse_created_date_with_locale("前天 08:05", locale),
+        2,
+        8,
+        5,
+    );
+    assert_ymd_hm(
+        parse_created_date_with_locale("2024年3月12日 10:23", locale),
+        (2024, 3, 12),
+        (10, 23),
+    );
+
+    let short = parse_created_date_with_locale("3月12日 10:23", locale);
+    assert_eq!((short.month(), short.day()), (3, 12));
+}
+
+#[test]
+fn parses_russian_fixtures() {
+    let locale = locale_for_code("ru-RU");
+
+    assert_seconds_ago(parse_created_date_with_locale("только что", locale), 0);
+    assert_seconds_ago(parse_created_date_with_locale("5 минут назад", locale), 300);
+    assert_seconds_ago(parse_created_date_with_locale("час назад", locale), 3600);
+    assert_seconds_ago(
+        parse_created_date_with_locale("2 дня назад", locale),
+        2 * 86400,
+    );
+    assert_days_ago_at(
+        parse_created_date_with_locale("вчера, 10:23", locale),
+        1,
+        10,
+        23,
+    );
+    assert_days_ago_at(
+        parse_created_date_with_locale("позавчера 07:00", locale),
+        2,
+        7,
+        0,
+    );
+    assert_ymd_hm(
+        parse_created_date_with_locale("12 марта 2024 г., 10:23", locale),
+        (2024, 3, 12),
+        (10, 23),
+    );
+    assert_ymd_hm(
+        parse_created_date_with_locale("05.04.2024 18:45", locale),
+        (2024, 4, 5),
+        (18, 45),
+    );
+}
+
+#[test]
+fn parses_polish_fixtures() {
+    let locale = locale_for_code("pl-PL");
+
+    assert_seconds_ago(parse_created_date_with_locale("przed chwilą", locale), 0);
+    assert_seconds_ago(parse_created_date_with_locale("3 minuty temu", locale), 180);
+    assert_seconds_ago(parse_created_date_with_locale("godzinę temu", locale), 3600);
+    assert_days_ago_at(
+        parse_created_date_with_locale("wczoraj 21:10", locale),
+        1,
+        21,
+        10,
+    );
+    assert_days_ago_at(
+        parse_created_date_with_locale("przedwczoraj 09:30", locale),
+        2,
+        9,
+        30,
+    );
+    assert_ymd_hm(
+        parse_created_date_with_locale("12 marca 2024 10:23", locale),
+        (2024, 3, 12),
+        (10, 23),
+    );
+}
+
+#[test]
+fn parses_spanish_fixtures() {
+    let locale = locale_for_code("es-ES");
+
+    assert_seconds_ago(parse_created_date_with_locale("ahora", locale), 0);
+    assert_seconds_ago(
+        parse_created_date_with_locale("hace 3 minutos", locale),
+        180,
+    );
+    assert_seconds_ago(parse_created_date_with_locale("hace un día", locale), 86400);
+    assert_days_ago_at(
+        parse_created_date_with_locale("ayer 10:23", locale),
+        1,
+        10,
+        23,
+    );
+    assert_ymd_hm(
+        parse_created_date_with_locale("12 de marzo de 2024 10:23", locale),
+        (2024, 3, 12),
+        (10, 23),
+    );
+    assert_ymd_hm(
+        parse_created_date_with_locale("05/04/2024 18:45", locale),
+        (2024, 4, 5),
+        (18, 45),
+    );
+}
+
+#[test]
+fn parses_german_fixtures() {
+    let locale = locale_for_code("de-DE");
+
+    assert_seconds_ago(parse_created_date_with_locale("gerade eben", locale), 0);
+    assert_seconds_ago(parse_created_date_with_locale("vor 5 Minuten", locale), 300);
+    assert_seconds_ago(
+        parse_created_date_with_locale("vor einer Stunde", locale),
+        3600,
+    );
+    assert_days_ago_at(
+        parse_created_date_with_locale("gestern 10:23", locale),
+        1,
+        10,
+        23,
+    );
+    assert_days_ago_at(
+        parse_created_date_with_locale("vorgestern 06:15", locale),
+        2,
+        6,
+        15,
+    );
+    assert_ymd_hm(
+        parse_created_date_with_locale("12. März 2024, 10:23", locale),
+        (2024, 3, 12),
+        (10, 23),
+    );
+    assert_ymd_hm(
+        parse_created_date_with_locale("05.04.2024 18:45", locale),
+        (2024, 4, 5),
+        (18, 45),
+    );
+}
+
+#[test]
+fn try_parse_created_date_reports_raw_input_on_failure() {
+    let error = try_parse_created_date("sometime last spring")
+        .expect_err("unrecognized input should not parse");
+
+    assert!(
+        matches!(&error, AppError::UnparsedDate { raw } if raw == "sometime last spring"),
+        "unexpected error: {error:?}"
+    );
+    assert!(error.to_string().contains("sometime last spring"));
+}
+
+#[test]
+fn try_parse_created_date_rejects_blank_input() {
+    assert!(matches!(
+        try_parse_created_date("   "),
+        Err(AppError::UnparsedDate { .. })
+    ));
+}
+
+#[test]
+fn try_parse_created_date_with_locale_parses_known_format() {
+    let output = try_parse_created_date_with_locale("2026年3月5日 09:15", &CHINESE)
+        .expect("chinese date should parse");
+
+    assert_eq!((output.year(), output.month(), output.day()), (2026, 3, 5));
+}
+
+#[test]
+fn parse_created_line_to_rfc3339_fails_instead_of_substituting_now() {
+    let error = parse_created_line_to_rfc3339("not a date at all")
+        .expect_err("garbage should not become the current time");
+
+    assert!(matches!(error, AppError::UnparsedDate { .. }));
+}
@@ -0,0 +1,162 @@
+use std::fs;
+
+use crate::{
+    db::sessions::init_db,
+    models::NoteDto,
+    services::{
+        converter::parse_notes_from_markdown,
+        diff::{diff_exports, diff_notes, note_preview, render_markdown_summary},
+        timezone::OutputTimezone,
+    },
+    tests::TestDir,
+};
+
+fn note_section(title: &str, body: &str, created: &str) -> String {
+    format!("****\n## {title}\n{body}\n*Created at: {created}*\n")
+}
+
+fn parse(sections: &[String]) -> Vec<NoteDto> {
+    parse_notes_from_markdown(&sections.join("\n"), &OutputTimezone::Local)
+        .expect("test notes should parse")
+}
+
+#[test]
+fn diff_notes_reports_added_removed_and_unchanged_notes() {
+    let left = parse(&[
+        note_section("Keep", "same body", "22/02/2026 10:30"),
+        note_section("Gone", "removed body", "22/02/2026 10:31"),
+    ]);
+    let right = parse(&[
+        note_section("Keep", "same body", "22/02/2026 10:30"),
+        note_section("New", "added body", "23/02/2026 09:00"),
+    ]);
+
+    let report = diff_notes("left", "right", &left, &right);
+
+    assert_eq!(report.unchanged_count, 1);
+    assert_eq!(report.added.len(), 1);
+    assert_eq!(report.added[0].preview, "added body");
+    assert_eq!(report.removed.len(), 1);
+    assert_eq!(report.removed[0].preview, "removed body");
+    assert!(report.modified.is_empty());
+}
+
+#[test]
+fn diff_notes_pairs_modified_notes_by_creation_date() {
+    let left = parse(&[note_section(
+        "Shopping",
+        "milk\neggs\nbread",
+        "22/02/2026 10:30",
+    )]);
+    let right = parse(&[note_section(
+        "Shopping",
+        "milk\neggs\nbutter",
+        "22/02/2026 10:30",
+    )]);
+
+    let report = diff_notes("left", "right", &left, &right);
+
+    assert!(report.added.is_empty());
+    assert!(report.removed.is_empty());
+    assert_eq!(report.modified.len(), 1);
+    let diff = &report.modified[0].diff;
+    assert!(
+        diff.contains("-bread"),
+        "diff should remove old line: {diff}"
+    );
+    assert!(diff.contains("+butter"), "diff should add new line: {diff}");
+    assert!(diff.contains(" eggs"), "diff should keep context: {diff}");
+}
+
+#[test]
+fn diff_notes_keeps_dissimilar_notes_with_the_same_date_apart() {
+    let left = parse(&[note_section("Groceries", "milk\neggs", "22/02/2026 10:30")]);
+    let right = parse(&[note_section(
+        "Meeting",
+        "agenda\nbudget review",
+        "22/02/2026 10:30",
+    )]);
+
+    let report = diff_notes("left", "right", &left, &right);
+
+    assert!(report.modified.is_empty());
+    assert_eq!(report.removed.len(), 1);
+    assert_eq!(report.added.len(), 1);
+}
+
+#[test]
+fn diff_notes_does_not_pair_notes_without_a_creation_date() {
+    let undated = |content: &str| NoteDto {
+        id: content.to_string(),
+        content: content.to_string(),
+        creation_date: String::new(),
+        last_modified: String::new(),
+        folder: None,
+    };
+    let left = [undated("milk\neggs\nbread")];
+    let right = [undated("milk\neggs\nbutter")];
+
+    let report = diff_notes("left", "right", &left, &right);
+
+    assert!(report.modified.is_empty());
+    assert_eq!(report.removed.len(), 1);
+    assert_eq!(report.added.len(), 1);
+}
+
+#[test]
+fn note_preview_uses_first_non_empty_line_and_truncates() {
+    assert_eq!(note_preview("\n\n  first line \nsecond"), "first line");
+
+    let long_line = "x".repeat(200);
+    let preview = note_preview(&long_line);
+    assert!(preview.ends_with("..."));
+    assert_eq!(preview.chars().count(), 83);
+}
+
+#[test]
+fn render_markdown_summary_lists_counts_and_diffs() {
+    let left = parse(&[note_section("Note", "intro\nbefore", "22/02/2026 10:30")]);
+    let right = parse(&[note_section("Note", "intro\nafter", "22/02/2026 10:30")]);
+
+    let summary = render_markdown_summary(&diff_notes("a", "b", &left, &right));
+
+    assert!(summary.contains("Added: 0, removed: 0, modified: 1, unchanged: 0"));
+    assert!(summary.contains("```diff"));
+}
+
+#[test]
+fn diff_exports_compares_folders_and_writes_html_summary() {
+    let test_dir = TestDir::new("folders");
+    let db_path = test_dir.path.join("sessions.db");
+    let db = init_db(&db_path).expect("test database should initialize");
+
+    let left_dir = test_dir.path.join("left");
+    let right_dir = test_dir.path.join("right");
+    fs::create_dir_all(&left_dir).expect("left directory should exist");
+    fs::create_dir_all(&right_dir).expect("right directory should exist");
+    fs::write(
+        left_dir.join("a.md"),
+        note_section("A", "intro\n<b>first</b>", "22/02/2026 10:30"),
+    )
+    .expect("left note should be written");
+    fs::write(
+        right_dir.join("a.md"),
+        note_section("A", "intro\n<b>second</b>", "22/02/2026 10:30"),
+    )
+    .expect("right note should be written");
+
+    let summary_path = test_dir.path.join("out").join("summary.html");
+    let report = diff_exports(
+        &db,
+        left_dir.to_string_lossy().as_ref(),
+        right_dir.to_string_lossy().as_ref(),
+        Some(summary_path.to_string_lossy().as_ref()),
+        &OutputTimezone::Local,
+    )
+    .expect("diff should succeed");
+
+    assert_eq!(report.modified.len(), 1);
+    let html = fs::read_to_string(summary_path).expect("summary should be written");
+    assert!(html.contains("<h2>Modified</h2>"));
+    assert!(html.contains("&lt;b&gt;second&lt;/b&gt;"));
+}
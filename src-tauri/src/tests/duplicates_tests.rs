@@ -0,0 +1,90 @@
+use crate::{
+    db::sessions::init_db,
+    models::NoteDto,
+    services::{
+        converter::parse_notes_from_markdown,
+        duplicates::{
+            estimate_similarity, find_duplicate_notes, find_duplicates, minhash_signature,
+            normalize_note_text, shingles,
+        },
+        timezone::OutputTimezone,
+    },
+    tests::TestDir,
+};
+
+fn note_section(body: &str, created: &str) -> String {
+    format!("****\n## Title\n{body}\n*Created at: {created}*\n")
+}
+
+fn parse(sections: &[String]) -> Vec<NoteDto> {
+    parse_notes_from_markdown(&sections.join("\n"), &OutputTimezone::Local)
+        .expect("test notes should parse")
+}
+
+const LONG_NOTE: &str = "Remember to renew the car insurance before the end of March \
+                         and compare offers from at least three providers this year";
+
+#[test]
+fn normalize_note_text_lowercases_and_collapses_punctuation() {
+    assert_eq!(
+        normalize_note_text("  Hello,   WORLD!\nSecond\tline. "),
+        "hello world second line"
+    );
+}
+
+#[test]
+fn shingles_of_short_text_fall_back_to_whole_text() {
+    let result = shingles("abc");
+
+    assert_eq!(result.len(), 1);
+    assert!(result.contains("abc"));
+}
+
+#[test]
+fn minhash_similarity_is_one_for_identical_and_low_for_unrelated_text() {
+    let first = minhash_signature(&shingles(&normalize_note_text(LONG_NOTE)));
+    let same = minhash_signature(&shingles(&normalize_note_text(LONG_NOTE)));
+    let other = minhash_signature(&shingles(&normalize_note_text(
+        "Grocery list: apples, oranges, bananas, yoghurt, bread and coffee beans",
+    )));
+
+    assert_eq!(estimate_similarity(&first, &same), 1.0);
+    assert!(estimate_similarity(&first, &other) < 0.3);
+}
+
+#[test]
+fn find_duplicates_groups_exact_and_near_duplicates() {
+    let notes = parse(&[
+        note_section(LONG_NOTE, "22/02/2026 10:30"),
+        note_section(LONG_NOTE, "23/02/2026 10:30"),
+        note_section(&format!("{LONG_NOTE}!"), "24/02/2026 10:30"),
+        note_section(&LONG_NOTE.replace("three", "four"), "25/02/2026 10:30"),
+        note_section("Completely unrelated shopping list", "26/02/2026 10:30"),
+    ]);
+
+    let report = find_duplicates("source", &notes, 0.7);
+
+    assert_eq!(report.notes_count, 5);
+    assert_eq!(report.exact_clusters.len(), 1);
+    assert_eq!(report.exact_clusters[0].notes.len(), 2);
+    assert_eq!(report.near_clusters.len(), 1);
+    assert_eq!(report.near_clusters[0].notes.len(), 3);
+    assert!(report.near_clusters[0].similarity >= 0.7);
+}
+
+#[test]
+fn find_duplicate_notes_rejects_out_of_range_threshold() {
+    let test_dir = TestDir::new("threshold");
+    let db_path = test_dir.path.join("sessions.db");
+    let db = init_db(&db_path).expect("test database should initialize");
+
+    let error = find_duplicate_notes(
+        &db,
+        test_dir.path.to_string_lossy().as_ref(),
+        Some(1.5),
+        &OutputTimezone::Local,
+    )
+    .expect_err("threshold above one should be rejected");
+
+    assert!(error.to_string().contains("between 0 and 1"));
+}
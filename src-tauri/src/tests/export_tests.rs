@@ -0,0 +1,655 @@
+use std::{
+    fs,
+    path::PathBuf,
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    db::sessions::{fetch_session_by_id, init_db},
+    error::AppError,
+    models::{
+        ExportFilterSpec, ScrapedImageInput, ScrapedNoteInput, UnparsedDatePolicy,
+        UnsupportedNotesFilter,
+    },
+    services::{
+        export::{
+            append_note, append_scraped_note, begin_export, cancel_export, fail_scrape,
+            finish_export, finish_scrape, is_export_paused, pause_export, report_export_total,
+            resume_export, set_total, start_export, wait_while_paused, ExportEvent, ExportOptions,
+            MemoryEvents, NoteDetailsTimestamps, DEFAULT_CREATED_DATE_FORMAT,
+            DEFAULT_TIMESTAMP_FORMAT,
+        },
+        timezone::OutputTimezone,
+    },
+    state::AppState,
+    tests::TestDir,
+};
+
+fn options(output_dir: PathBuf, policy: UnparsedDatePolicy) -> ExportOptions {
+    ExportOptions {
+        domain: "us.i.mi.com".to_string(),
+        output_dir,
+        split: false,
+        name_by_title: false,
+        timestamp_format: DEFAULT_TIMESTAMP_FORMAT.to_string(),
+        created_date_format: DEFAULT_CREATED_DATE_FORMAT.to_string(),
+        export_images: false,
+        skip_duplicates: true,
+        unparsed_date_policy: policy,
+        notes_locale: "en-US".to_string(),
+        output_timezone: OutputTimezone::parse("UTC").expect("UTC should parse"),
+        filter: Default::default(),
+        dry_run: false,
+    }
+}
+
+fn note(title: &str, content: &str, created: &str) -> ScrapedNoteInput {
+    serde_json::from_value(serde_json::json!({
+        "title": title,
+        "content": content,
+        "createdString": created,
+    }))
+    .expect("note dump entry should deserialize")
+}
+
+#[test]
+fn export_from_dump_writes_notes_and_skips_duplicates() {
+    let test_dir = TestDir::new("dump");
+    let db = init_db(&test_dir.path.join("sessions.db")).expect("test database should initialize");
+    let mut export = begin_export(
+        &db,
+        &options(test_dir.path.join("out"), UnparsedDatePolicy::UseNow),
+    )
+    .expect("export should start");
+    set_total(&mut export, 3);
+
+    let notes = [
+        note("Garden", "Plant tomatoes", "2021-04-10 09:00"),
+        note("Copy", "Plant tomatoes", "2021-04-11 09:00"),
+        note("Shopping", "Buy basil", "2021-04-12 09:00"),
+    ];
+    let log_lines = notes
+        .iter()
+        .map(|note| {
+            append_note(&db, &mut export, note, NoteDetailsTimestamps::default())
+                .expect("note should be written")
+        })
+        .collect::<Vec<_>>();
+    finish_export(&db, &mut export, "completed", None).expect("export should finish");
+
+    assert_eq!(log_lines[1], "Skipped duplicate note: Copy");
+    let content = fs::read_to_string(&export.output_root).expect("export should be readable");
+    assert!(content.contains("## Title: Garden"));
+    assert!(content.contains("## Title: Shopping"));
+    assert!(!content.contains("## Title: Copy"));
+
+    let session = fetch_session_by_id(&db, &export.session_id)
+        .expect("session should load")
+        .expect("session should exist");
+    assert_eq!(session.status, "completed");
+    assert_eq!(session.notes_count, 2);
+}
+
+#[test]
+fn fail_policy_rejects_unparsed_dates_and_keeps_progress() {
+    let test_dir = TestDir::new("fail");
+    let db = init_db(&test_dir.path.join("sessions.db")).expect("test database should initialize");
+    let mut export = begin_export(
+        &db,
+        &options(test_dir.path.join("out"), UnparsedDatePolicy::Fail),
+    )
+    .expect("export should start");
+
+    append_note(
+        &db,
+        &mut export,
+        &note("Garden", "Plant tomatoes", "2021-04-10 09:00"),
+        NoteDetailsTimestamps::default(),
+    )
+    .expect("first note should be written");
+    let error = append_note(
+        &db,
+        &mut export,
+        &note("Broken", "Unknown date", "sometime"),
+        NoteDetailsTimestamps::default(),
+    )
+    .expect_err("unparsed date should fail the export");
+
+    assert!(error.to_string().contains("sometime"));
+    let session = fetch_session_by_id(&db, &export.session_id)
+        .expect("session should load")
+        .expect("session should exist");
+    assert_eq!(session.notes_count, 1);
+    assert_eq!(session.unparsed_dates, 1);
+}
+
+const PIXEL_PNG: &str =
+    "iVBORw0KGgoAAAANSUhEUgAAAAEAAAABCAQAAAC1HAwCAAAAC0lEQVR42mNkYAAAAAYAAjCB0C8AAAAASUVORK5CYII=";
+
+fn note_with_images(title: &str, created: &str, images: usize) -> ScrapedNoteInput {
+    let mut note = note(title, &format!("Body of {title}"), created);
+    note.images = (0..images)
+        .map(|_| ScrapedImageInput {
+            data_base64: PIXEL_PNG.to_string(),
+        })
+        .collect();
+    note
+}
+
+fn state(test_dir: &TestDir) -> AppState {
+    let db = init_db(&test_dir.path.join("sessions.db")).expect("test database should initialize");
+    AppState::new(db, test_dir.path.join("settings.json"))
+}
+
+#[test]
+fn full_split_export_with_images_emits_progress_and_completion() {
+    let test_dir = TestDir::new("full");
+    let state = state(&test_dir);
+    let events = MemoryEvents::default();
+    let mut export_options = options(test_dir.path.join("out"), UnparsedDatePolicy::UseNow);
+    export_options.split = true;
+    export_options.export_images = true;
+
+    let session_id = start_export(&state, &export_options).expect("export should start");
+    assert!(matches!(
+        start_export(&state, &export_options),
+        Err(AppError::ExportRunning)
+    ));
+    report_export_total(&state, &session_id, 3, &events).expect("total should be reported");
+    for (index, images) in [2, 0, 1].into_iter().enumerate() {
+        let note = note_with_images(
+            &format!("Note {index}"),
+            &format!("2021-04-1{index} 09:00"),
+            images,
+        );
+        append_scraped_note(
+            &state,
+            &session_id,
+            &note,
+            NoteDetailsTimestamps::default(),
+            &events,
+        )
+        .expect("note should be appended");
+    }
+    let export = finish_scrape(&state, &session_id, &events).expect("export should finish");
+
+    assert!(state
+        .active_export
+        .lock()
+        .expect("state lock should be available")
+        .is_none());
+    let markdown_files = fs::read_dir(&export.output_root)
+        .expect("output folder should exist")
+        .filter_map(Result::ok)
+        .filter(|entry| entry.path().extension().is_some_and(|ext| ext == "md"))
+        .count();
+    assert_eq!(markdown_files, 3);
+    let image_files = fs::read_dir(export.output_root.join("images"))
+        .expect("images folder should exist")
+        .count();
+    assert_eq!(image_files, 3);
+
+    let session = fetch_session_by_id(&state.db, &session_id)
+        .expect("session should load")
+        .expect("session should exist");
+    assert_eq!(session.status, "completed");
+    assert_eq!(session.notes_count, 3);
+    assert_eq!(session.images_count, 3);
+
+    let recorded = events.events();
+    assert_eq!(recorded.len(), 5);
+    let progress = recorded
+        .iter()
+        .filter_map(|event| match event {
+            ExportEvent::Progress(progress) => Some(progress),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(progress[0].log_line, "Discovered 3 notes.");
+    assert_eq!(
+        progress
+            .iter()
+            .map(|event| event.current)
+            .collect::<Vec<_>>(),
+        vec![0, 1, 2, 3]
+    );
+    assert_eq!(progress[3].images_count, 3);
+    match recorded.last() {
+        Some(ExportEvent::Complete(complete)) => {
+            assert_eq!(complete.session_id, session_id);
+            assert_eq!(complete.total, 3);
+            assert_eq!(complete.output_path, session.output_path);
+        }
+        other => panic!("expected completion event, got {other:?}"),
+    }
+}
+
+#[test]
+fn pipeline_rejects_mismatched_sessions_and_reports_failures() {
+    let test_dir = TestDir::new("mismatch");
+    let state = state(&test_dir);
+    let events = MemoryEvents::default();
+    let session_id = start_export(
+        &state,
+        &options(test_dir.path.join("out"), UnparsedDatePolicy::Fail),
+    )
+    .expect("export should start");
+
+    assert!(matches!(
+        report_export_total(&state, "other-session", 2, &events),
+        Err(AppError::SessionMismatch)
+    ));
+    assert!(matches!(
+        finish_scrape(&state, "other-session", &events),
+        Err(AppError::SessionMismatch)
+    ));
+
+    let error = append_scraped_note(
+        &state,
+        &session_id,
+        &note("Broken", "Unknown date", "sometime"),
+        NoteDetailsTimestamps::default(),
+        &events,
+    )
+    .expect_err("unparsed date should fail");
+    fail_scrape(&state, &session_id, &error.to_string(), &events).expect("failure should record");
+
+    let session = fetch_session_by_id(&state.db, &session_id)
+        .expect("session should load")
+        .expect("session should exist");
+    assert_eq!(session.status, "error");
+    assert_eq!(session.error_message, Some(error.to_string()));
+    assert!(matches!(
+        events.events().as_slice(),
+        [ExportEvent::Error(event)] if event.session_id == session_id
+    ));
+}
+
+#[test]
+fn cancel_export_only_stops_the_requested_session() {
+    let test_dir = TestDir::new("cancel");
+    let state = state(&test_dir);
+    let events = MemoryEvents::default();
+    let session_id = start_export(
+        &state,
+        &options(test_dir.path.join("out"), UnparsedDatePolicy::UseNow),
+    )
+    .expect("export should start");
+
+    let untouched =
+        cancel_export(&state, Some("other-session"), None, &events).expect("cancel should succeed");
+    assert!(untouched.is_none());
+
+    let cancelled = cancel_export(
+        &state,
+        Some(&session_id),
+        Some("Mi Cloud access window closed by user."),
+        &events,
+    )
+    .expect("cancel should succeed");
+    assert!(cancelled.is_some());
+
+    let session = fetch_session_by_id(&state.db, &session_id)
+        .expect("session should load")
+        .expect("session should exist");
+    assert_eq!(session.status, "cancelled");
+    assert_eq!(
+        session.error_message.as_deref(),
+        Some("Mi Cloud access window closed by user.")
+    );
+    assert!(cancel_export(&state, None, None, &events)
+        .expect("cancel without an export should succeed")
+        .is_none());
+}
+
+#[test]
+fn export_filter_skips_notes_before_writing_and_counts_them() {
+    let test_dir = TestDir::new("filter");
+    let state = state(&test_dir);
+    let events = MemoryEvents::default();
+    let mut export_options = options(test_dir.path.join("out"), UnparsedDatePolicy::LeaveBlank);
+    export_options.split = true;
+    export_options.filter = ExportFilterSpec {
+        created_after: Some("2021-04-01".to_string()),
+        created_before: Some("2021-05-01".to_string()),
+        title_regex: Some("(?i)^garden".to_string()),
+        unsupported: UnsupportedNotesFilter::Exclude,
+        min_length: Some(5),
+        ..ExportFilterSpec::default()
+    };
+
+    let session_id = start_export(&state, &export_options).expect("export should start");
+    report_export_total(&state, &session_id, 6, &events).expect("total should be reported");
+    let mut unsupported = note("Garden map", "", "2021-04-10 09:00");
+    unsupported.unsupported = true;
+    let notes = [
+        note("Garden beds", "Plant tomatoes", "2021-04-10 09:00"),
+        note("Garden early", "Plant peas", "2021-03-10 09:00"),
+        note("Garden undated", "Plant beans", "sometime"),
+        note("Shopping", "Buy basil", "2021-04-12 09:00"),
+        note("Garden short", "Dig", "2021-04-13 09:00"),
+        unsupported,
+    ];
+    for note in &notes {
+        append_scraped_note(
+            &state,
+            &session_id,
+            note,
+            NoteDetailsTimestamps::default(),
+            &events,
+        )
+        .expect("note should be handled");
+    }
+    let export = finish_scrape(&state, &session_id, &events).expect("export should finish");
+
+    let written = fs::read_dir(&export.output_root)
+        .expect("output folder should exist")
+        .filter_map(Result::ok)
+        .filter(|entry| entry.path().extension().is_some_and(|ext| ext == "md"))
+        .count();
+    assert_eq!(written, 1);
+
+    let session = fetch_session_by_id(&state.db, &session_id)
+        .expect("session should load")
+        .expect("session should exist");
+    assert_eq!(session.notes_count, 1);
+    assert_eq!(session.filtered_count, 5);
+
+    let progress = events
+        .events()
+        .into_iter()
+        .filter_map(|event| match event {
+            ExportEvent::Progress(progress) => Some(progress),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    let last = progress.last().expect("progress should be reported");
+    assert_eq!(
+        (last.current, last.notes_count, last.filtered_count),
+        (6, 1, 5)
+    );
+    assert_eq!(
+        progress
+            .iter()
+            .skip(1)
+            .map(|event| event.log_line.as_str())
+            .collect::<Vec<_>>(),
+        vec![
+            "Processed note 1: Garden beds",
+            "Skipped note filtered by creation date: Garden early",
+            "Skipped note filtered by missing creation date: Garden undated",
+            "Skipped note filtered by title: Shopping",
+            "Skipped note filtered by length: Garden short",
+            "Skipped note filtered by unsupported: Garden map",
+        ]
+    );
+}
+
+#[test]
+fn date_range_ignores_the_unparsed_date_fallback() {
+    let test_dir = TestDir::new("filter_fallback");
+    let state = state(&test_dir);
+    let events = MemoryEvents::default();
+    let mut export_options = options(test_dir.path.join("out"), UnparsedDatePolicy::UseNow);
+    export_options.dry_run = true;
+    export_options.filter.created_after = Some("2021-01-01".to_string());
+
+    let session_id = start_export(&state, &export_options).expect("dry run should start");
+    report_export_total(&state, &session_id, 2, &events).expect("total should be reported");
+    for note in [
+        note("Dated", "Plant tomatoes", "2021-04-10 09:00"),
+        note("Undated", "Plant beans", "sometime"),
+    ] {
+        append_scraped_note(
+            &state,
+            &session_id,
+            &note,
+            NoteDetailsTimestamps::default(),
+            &events,
+        )
+        .expect("note should be handled");
+    }
+    finish_scrape(&state, &session_id, &events).expect("dry run should finish");
+
+    let report = match events.events().last() {
+        Some(ExportEvent::Complete(complete)) => {
+            complete.dry_run.clone().expect("dry run should report")
+        }
+        other => panic!("expected completion event, got {other:?}"),
+    };
+    assert_eq!(report.notes_count, 1);
+    assert_eq!(report.filtered_count, 1);
+    assert!(report.unparsed_dates.is_empty());
+}
+
+#[test]
+fn filtered_note_does_not_mark_its_content_as_seen() {
+    let test_dir = TestDir::new("filter_duplicates");
+    let db = init_db(&test_dir.path.join("sessions.db")).expect("test database should initialize");
+    let mut export_options = options(test_dir.path.join("out"), UnparsedDatePolicy::UseNow);
+    export_options.filter.created_after = Some("2021-04-01".to_string());
+    let mut export = begin_export(&db, &export_options).expect("export should start");
+    set_total(&mut export, 2);
+
+    let log_lines = [
+        note("Early", "Plant tomatoes", "2021-03-10 09:00"),
+        note("Late", "Plant tomatoes", "2021-04-10 09:00"),
+    ]
+    .iter()
+    .map(|note| {
+        append_note(&db, &mut export, note, NoteDetailsTimestamps::default())
+            .expect("note should be handled")
+    })
+    .collect::<Vec<_>>();
+    finish_export(&db, &mut export, "completed", None).expect("export should finish");
+
+    assert_eq!(
+        log_lines,
+        vec![
+            "Skipped note filtered by creation date: Early",
+            "Processed note 1: Late",
+        ]
+    );
+    assert_eq!(export.duplicates_skipped, 0);
+    let content = fs::read_to_string(&export.output_root).expect("export should be readable");
+    assert!(content.contains("## Title: Late"));
+    assert!(!content.contains("## Title: Early"));
+}
+
+#[test]
+fn invalid_export_filter_is_rejected_before_the_export_starts() {
+    let test_dir = TestDir::new("filter_invalid");
+    let state = state(&test_dir);
+    let mut export_options = options(test_dir.path.join("out"), UnparsedDatePolicy::UseNow);
+    export_options.filter.body_regex = Some("(unclosed".to_string());
+
+    let error = start_export(&state, &export_options).expect_err("bad pattern should fail");
+    assert!(error.to_string().contains("Invalid body pattern"));
+    assert!(!test_dir.path.join("out").exists());
+    assert!(state
+        .active_export
+        .lock()
+        .expect("state lock should be available")
+        .is_none());
+
+    export_options.filter = ExportFilterSpec {
+        created_after: Some("2021-05-01".to_string()),
+        created_before: Some("2021-04-01".to_string()),
+        ..ExportFilterSpec::default()
+    };
+    assert!(start_export(&state, &export_options).is_err());
+}
+
+#[test]
+fn dry_run_reports_the_plan_without_writing_or_recording() {
+    let test_dir = TestDir::new("dry_run");
+    let state = state(&test_dir);
+    let events = MemoryEvents::default();
+    let mut export_options = options(test_dir.path.join("out"), UnparsedDatePolicy::Fail);
+    export_options.split = true;
+    export_options.name_by_title = true;
+    export_options.export_images = true;
+    export_options.dry_run = true;
+
+    let session_id = start_export(&state, &export_options).expect("dry run should start");
+    let mut unsupported = note("Mind map", "", "2021-04-12 09:00");
+    unsupported.unsupported = true;
+    let mut in_folder = note_with_images("todo", "2021-04-13 09:00", 1);
+    in_folder.folder = Some("Work".to_string());
+    let notes = [
+        note_with_images("Todo", "2021-04-10 09:00", 2),
+        note("Todo", "Second list", "someday"),
+        unsupported,
+        in_folder,
+    ];
+    report_export_total(&state, &session_id, notes.len() as u32, &events)
+        .expect("total should be reported");
+    for note in &notes {
+        append_scraped_note(
+            &state,
+            &session_id,
+            note,
+            NoteDetailsTimestamps::default(),
+            &events,
+        )
+        .expect("dry run should not fail on unparsed dates");
+    }
+    finish_scrape(&state, &session_id, &events).expect("dry run should finish");
+
+    assert!(!test_dir.path.join("out").exists());
+    assert!(fetch_session_by_id(&state.db, &session_id)
+        .expect("session lookup should succeed")
+        .is_none());
+
+    let report = match events.events().last() {
+        Some(ExportEvent::Complete(complete)) => {
+            complete.dry_run.clone().expect("dry run should report")
+        }
+        other => panic!("expected completion event, got {other:?}"),
+    };
+    assert_eq!(report.notes_count, 4);
+    assert_eq!(report.images_count, 3);
+    assert_eq!(report.image_bytes, 3 * 68);
+    assert!(report.markdown_bytes > 0);
+    assert_eq!(
+        report.files,
+        vec![
+            "Todo_0001.md",
+            "Todo_0002.md",
+            "Mind map_0003.md",
+            "Work/todo_0004.md"
+        ]
+    );
+    assert_eq!(report.collisions.len(), 1);
+    assert_eq!(report.collisions[0].name, "Todo");
+    assert_eq!(
+        report.collisions[0].files,
+        vec!["Todo_0001.md", "Todo_0002.md"]
+    );
+    assert_eq!(report.unsupported_notes, vec!["Mind map"]);
+    assert_eq!(report.unparsed_dates.len(), 1);
+    assert_eq!(report.unparsed_dates[0].created, "someday");
+}
+
+#[test]
+fn split_folder_named_like_the_images_directory_is_kept_apart() {
+    let test_dir = TestDir::new("images_folder");
+    let state = state(&test_dir);
+    let events = MemoryEvents::default();
+    let mut export_options = options(test_dir.path.join("out"), UnparsedDatePolicy::UseNow);
+    export_options.split = true;
+    export_options.name_by_title = true;
+    export_options.export_images = true;
+    export_options.dry_run = true;
+
+    let session_id = start_export(&state, &export_options).expect("dry run should start");
+    let mut in_folder = note_with_images("Screenshots", "2021-04-13 09:00", 1);
+    in_folder.folder = Some("Images".to_string());
+    report_export_total(&state, &session_id, 1, &events).expect("total should be reported");
+    append_scraped_note(
+        &state,
+        &session_id,
+        &in_folder,
+        NoteDetailsTimestamps::default(),
+        &events,
+    )
+    .expect("note should be handled");
+    finish_scrape(&state, &session_id, &events).expect("dry run should finish");
+
+    let report = match events.events().last() {
+        Some(ExportEvent::Complete(complete)) => {
+            complete.dry_run.clone().expect("dry run should report")
+        }
+        other => panic!("expected completion event, got {other:?}"),
+    };
+    assert_eq!(report.files, vec!["Images (folder)/Screenshots_0001.md"]);
+}
+
+#[test]
+fn paused_export_holds_the_loop_and_excludes_the_pause_from_elapsed_time() {
+    let test_dir = TestDir::new("pause");
+    let state = state(&test_dir);
+    let events = MemoryEvents::default();
+    let session_id = start_export(
+        &state,
+        &options(test_dir.path.join("out"), UnparsedDatePolicy::UseNow),
+    )
+    .expect("export should start");
+    report_export_total(&state, &session_id, 1, &events).expect("total should be reported");
+
+    pause_export(&state, &events).expect("export should pause");
+    let stored = fetch_session_by_id(&state.db, &session_id)
+        .expect("session should load")
+        .expect("session should exist");
+    assert_eq!(stored.status, "paused");
+    assert!(is_export_paused(&state, &session_id).expect("pause state should load"));
+
+    let waiter = {
+        let state = state.clone();
+        let session_id = session_id.clone();
+        thread::spawn(move || {
+            let started = Instant::now();
+            wait_while_paused(&state, &session_id).map(|()| started.elapsed())
+        })
+    };
+    thread::sleep(Duration::from_millis(400));
+    resume_export(&state, &events).expect("export should resume");
+    let waited = waiter
+        .join()
+        .expect("waiting thread should finish")
+        .expect("wait should end on resume");
+    assert!(waited >= Duration::from_millis(400));
+    let stored = fetch_session_by_id(&state.db, &session_id)
+        .expect("session should load")
+        .expect("session should exist");
+    assert_eq!(stored.status, "running");
+
+    append_scraped_note(
+        &state,
+        &session_id,
+        &note("Garden", "Plant tomatoes", "2021-04-10 09:00"),
+        NoteDetailsTimestamps::default(),
+        &events,
+    )
+    .expect("note should be appended");
+    finish_scrape(&state, &session_id, &events).expect("export should finish");
+    assert!(matches!(
+        wait_while_paused(&state, &session_id),
+        Err(AppError::ExportNotRunning)
+    ));
+
+    let recorded = events.events();
+    let log_lines = recorded
+        .iter()
+        .filter_map(|event| match event {
+            ExportEvent::Progress(progress) => Some(progress.log_line.as_str()),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(log_lines[1..3], ["Export paused.", "Export resumed."]);
+    match recorded.last() {
+        Some(ExportEvent::Complete(complete)) => assert!(complete.elapsed_ms < 400),
+        other => panic!("expected completion event, got {other:?}"),
+    }
+}
@@ -1,39 +1,13 @@
-use std::{
-    fs,
-    path::PathBuf,
-    time::{SystemTime, UNIX_EPOCH},
+use std::fs;
+
+use crate::{
+    services::files::{
+        append_text, list_export_files, remove_export_artifacts, save_base64_image,
+        write_export_file,
+    },
+    tests::TestDir,
 };
 
-use crate::services::files::{
-    append_text, list_export_files, remove_export_artifacts, save_base64_image, write_export_file,
-};
-
-struct TestDir {
-    path: PathBuf,
-}
-
-impl TestDir {
-    fn new(name: &str) -> Self {
-        let unique = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .expect("system time should be after unix epoch")
-            .as_nanos();
-        let path = std::env::temp_dir().join(format!(
-            "xiaomi_note_exporter_files_{name}_{}_{}",
-            std::process::id(),
-            unique
-        ));
-        fs::create_dir_all(&path).expect("temp test directory should be created");
-        Self { path }
-    }
-}
-
-impl Drop for TestDir {
-    fn drop(&mut self) {
-        let _ = fs::remove_dir_all(&self.path);
-    }
-}
-
 #[test]
 fn append_text_creates_parent_and_appends_content() {
     let test_dir = TestDir::new("append_text");
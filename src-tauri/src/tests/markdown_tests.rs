@@ -2,7 +2,7 @@ use chrono::{Local, TimeZone};
 
 use crate::services::markdown::{
     build_note_markdown, dotnet_to_chrono_created_date_format, dotnet_to_chrono_format,
-    sanitize_filename, to_markdown_from_html,
+    sanitize_filename, to_markdown_from_html, NoteTimestamps,
 };
 
 fn make_split_filename(formatted_date: &str, note_index: u32) -> String {
@@ -81,7 +81,8 @@ fn build_note_markdown_uses_default_created_date_format() {
     let created_at = Local
         .with_ymd_and_hms(2026, 5, 5, 13, 42, 0)
         .single()
-        .expect("valid local datetime");
+        .expect("valid local datetime")
+        .fixed_offset();
     let chrono_format = dotnet_to_chrono_created_date_format("dd/MM/yyyy HH:mm");
 
     let output = build_note_markdown(
@@ -89,7 +90,7 @@ fn build_note_markdown_uses_default_created_date_format() {
         "body",
         None,
         &[],
-        created_at,
+        NoteTimestamps::new(Some(created_at), None),
         &chrono_format,
         false,
     );
@@ -105,7 +106,8 @@ fn build_note_markdown_honors_custom_created_date_format() {
     let created_at = Local
         .with_ymd_and_hms(2026, 5, 5, 13, 42, 0)
         .single()
-        .expect("valid local datetime");
+        .expect("valid local datetime")
+        .fixed_offset();
     let chrono_format = dotnet_to_chrono_created_date_format("yyyy-MM-dd HH:mm:ss");
 
     let output = build_note_markdown(
@@ -113,7 +115,7 @@ fn build_note_markdown_honors_custom_created_date_format() {
         "body",
         None,
         &[],
-        created_at,
+        NoteTimestamps::new(Some(created_at), None),
         &chrono_format,
         false,
     );
@@ -129,7 +131,8 @@ fn build_note_markdown_falls_back_to_default_when_format_is_empty() {
     let created_at = Local
         .with_ymd_and_hms(2026, 5, 5, 13, 42, 0)
         .single()
-        .expect("valid local datetime");
+        .expect("valid local datetime")
+        .fixed_offset();
     let chrono_format = dotnet_to_chrono_created_date_format("");
 
     let output = build_note_markdown(
@@ -137,7 +140,7 @@ fn build_note_markdown_falls_back_to_default_when_format_is_empty() {
         "body",
         None,
         &[],
-        created_at,
+        NoteTimestamps::new(Some(created_at), None),
         &chrono_format,
         false,
     );
@@ -153,7 +156,8 @@ fn build_note_markdown_falls_back_to_default_when_format_is_unrecognized() {
     let created_at = Local
         .with_ymd_and_hms(2026, 5, 5, 13, 42, 0)
         .single()
-        .expect("valid local datetime");
+        .expect("valid local datetime")
+        .fixed_offset();
     let chrono_format = dotnet_to_chrono_created_date_format("not-a-format");
 
     let output = build_note_markdown(
@@ -161,7 +165,7 @@ fn build_note_markdown_falls_back_to_default_when_format_is_unrecognized() {
         "body",
         None,
         &[],
-        created_at,
+        NoteTimestamps::new(Some(created_at), None),
         &chrono_format,
         false,
     );
@@ -177,14 +181,29 @@ fn build_note_markdown_default_and_custom_formats_differ() {
     let created_at = Local
         .with_ymd_and_hms(2026, 5, 5, 13, 42, 0)
         .single()
-        .expect("valid local datetime");
+        .expect("valid local datetime")
+        .fixed_offset();
     let default_format = dotnet_to_chrono_created_date_format("dd/MM/yyyy HH:mm");
     let custom_format = dotnet_to_chrono_created_date_format("yyyy-MM-dd");
 
-    let default_output =
-        build_note_markdown("Title", "body", None, &[], created_at, &default_format, false);
-    let custom_output =
-        build_note_markdown("Title", "body", None, &[], created_at, &custom_format, false);
+    let default_output = build_note_markdown(
+        "Title",
+        "body",
+        None,
+        &[],
+        NoteTimestamps::new(Some(created_at), None),
+        &default_format,
+        false,
+    );
+    let custom_output = build_note_markdown(
+        "Title",
+        "body",
+        None,
+        &[],
+        NoteTimestamps::new(Some(created_at), None),
+        &custom_format,
+        false,
+    );
 
     assert_ne!(default_output, custom_output);
 }
@@ -353,3 +372,50 @@ fn adds_blank_line_after_blockquote_before_following_text() {
 
     assert_eq!(output, "> Quoted\n\nAfter quote");
 }
+
+#[test]
+fn build_note_markdown_leaves_created_date_blank_when_missing() {
+    let output = build_note_markdown(
+        "Title",
+        "body",
+        None,
+        &[],
+        NoteTimestamps::default(),
+        "%d/%m/%Y %H:%M",
+        false,
+    );
+
+    assert!(
+        output.ends_with("*Created at:*\n"),
+        "output did not end with blank footer: {output:?}"
+    );
+}
+
+#[test]
+fn build_note_markdown_appends_modified_date_after_created_date() {
+    let created_at = Local
+        .with_ymd_and_hms(2026, 5, 5, 13, 42, 0)
+        .single()
+        .expect("valid local datetime")
+        .fixed_offset();
+    let modified_at = Local
+        .with_ymd_and_hms(2026, 6, 1, 8, 5, 0)
+        .single()
+        .expect("valid local datetime")
+        .fixed_offset();
+
+    let output = build_note_markdown(
+        "Title",
+        "body",
+        None,
+        &[],
+        NoteTimestamps::new(Some(created_at), Some(modified_at)),
+        "%d/%m/%Y %H:%M",
+        false,
+    );
+
+    assert!(
+        output.ends_with("*Created at: 05/05/2026 13:42*\n*Modified at: 01/06/2026 08:05*\n"),
+        "output did not end with created and modified footer: {output:?}"
+    );
+}
@@ -0,0 +1,511 @@
+use std::{fs, path::PathBuf, time::Duration};
+
+use serde_json::json;
+
+use crate::{
+    db::sessions::{fetch_session_by_id, init_db},
+    error::AppResult,
+    models::Session,
+    services::{
+        converter::parse_notes_from_markdown,
+        export::{self, ExportEvent, ExportOptions, MemoryEvents},
+        micloud::{
+            download_scrape_image, fetch_note_timestamps, login_cookie_header, parse_note_entry,
+            parse_note_page, render_content, run_export, MiCloudClient,
+        },
+        timezone::OutputTimezone,
+    },
+    state::AppState,
+    tests::{
+        mock_micloud::{MockMiCloud, MockNote, SESSION_COOKIE},
+        TestDir,
+    },
+};
+
+const PIXEL_PNG: &[u8] = &[
+    0x89, 0x50, 0x4e, 0x47, 0x0d, 0x0a, 0x1a, 0x0a, 0x00, 0x00, 0x00, 0x0d,
+];
+
+fn note_detail(id: &str, title: &str, content: &str) -> serde_json::Value {
+    json!({
+        "result": "ok",
+        "data": {
+            "entry": {
+                "id": id,
+                "content": content,
+                "createDate": 1_618_304_400_000_i64,
+                "modifyDate": 1_618_390_800_000_i64,
+                "extraInfo": json!({ "title": title, "note_content_type": "common" }).to_string(),
+            }
+        }
+    })
+}
+
+#[test]
+fn render_content_converts_markup_and_collects_images() {
+    let raw = "<text indent=\"1\">Shopping &amp; errands</text>\n\
+               <input type=\"checkbox\" indent=\"1\" level=\"3\" checked=\"true\" />milk\n\
+               <input type=\"checkbox\" indent=\"1\" level=\"3\" />bread\n\
+               <bullet indent=\"2\" /><b>bold</b> item\n\
+               <order indent=\"1\" inputNumber=\"1\" />second\n\
+               <hr />\n\
+               <img fileid=\"1234.new\" imgshow=\"0\" imgdes=\"\" />\n\
+               ☺ 5678.old<0/><//>";
+
+    let (content, images) = render_content(raw);
+
+    assert_eq!(
+        content,
+        "Shopping & errands\n- [x] milk\n- [ ] bread\n  - bold item\n2. second\n---"
+    );
+    assert_eq!(images, vec!["1234.new".to_string(), "5678.old".to_string()]);
+}
+
+#[test]
+fn parse_note_entry_reads_title_timestamps_and_content_type() {
+    let mut payload = note_detail("42", " Trip ", "Pack bags");
+    let entry = parse_note_entry(&payload).expect("entry should parse");
+    assert_eq!(entry.id, "42");
+    assert_eq!(entry.title, "Trip");
+    assert_eq!(entry.content, "Pack bags");
+    assert_eq!(entry.created, Some(1_618_304_400_000));
+    assert!(!entry.unsupported);
+
+    payload["data"]["entry"]["extraInfo"] = json!(r#"{"note_content_type":"mind"}"#);
+    payload["data"]["entry"]["subject"] = json!("Mind map");
+    let entry = parse_note_entry(&payload).expect("entry should parse");
+    assert_eq!(entry.title, "Mind map");
+    assert!(entry.unsupported);
+
+    assert!(parse_note_entry(&json!({ "data": {} })).is_err());
+}
+
+#[test]
+fn parse_note_page_reads_entries_and_paging_state() {
+    let page = parse_note_page(&json!({
+        "data": {
+            "entries": [{ "id": 1, "createDate": "1618304400000" }, { "snippet": "no id" }],
+            "syncTag": "tag-2",
+            "lastPage": false
+        }
+    }))
+    .expect("page should parse");
+
+    assert_eq!(page.notes.len(), 1);
+    assert_eq!(page.notes[0].id, "1");
+    assert_eq!(page.notes[0].created, Some(1_618_304_400_000));
+    assert_eq!(page.sync_tag.as_deref(), Some("tag-2"));
+    assert!(!page.last_page);
+}
+
+#[test]
+fn login_cookie_header_requires_service_token() {
+    assert_eq!(
+        login_cookie_header([("userId", "7"), ("serviceToken", "abc")]),
+        Some("userId=7; serviceToken=abc".to_string())
+    );
+    assert_eq!(login_cookie_header([("userId", "7")]), None);
+    assert_eq!(login_cookie_header([("serviceToken", "")]), None);
+}
+
+fn client(server: &MockMiCloud) -> MiCloudClient {
+    MiCloudClient::new(server.base_url(), SESSION_COOKIE).expect("client should build")
+}
+
+/// Starts an export into `test_dir`, runs it against `client` and returns the result and session.
+fn export_from(test_dir: &TestDir, client: &MiCloudClient) -> (AppResult<()>, Session) {
+    export_with(test_dir, client, &MemoryEvents::default(), |_| {})
+}
+
+fn export_with(
+    test_dir: &TestDir,
+    client: &MiCloudClient,
+    events: &MemoryEvents,
+    configure: impl FnOnce(&mut ExportOptions),
+) -> (AppResult<()>, Session) {
+    let db = init_db(&test_dir.path.join("sessions.db")).expect("test database should initialize");
+    let state = AppState::new(db, test_dir.path.join("settings.json"));
+    let mut options = ExportOptions {
+        domain: "us.i.mi.com".to_string(),
+        output_dir: test_dir.path.join("out"),
+        split: false,
+        name_by_title: false,
+        timestamp_format: export::DEFAULT_TIMESTAMP_FORMAT.to_string(),
+        created_date_format: export::DEFAULT_CREATED_DATE_FORMAT.to_string(),
+        export_images: true,
+        skip_duplicates: false,
+        unparsed_date_policy: Default::default(),
+        notes_locale: "en-US".to_string(),
+        output_timezone: OutputTimezone::parse("UTC").expect("UTC should parse"),
+        filter: Default::default(),
+        dry_run: false,
+    };
+    configure(&mut options);
+    let session_id = export::start_export(&state, &options).expect("export should start");
+
+    let result = run_export(&state, &session_id, client, events);
+    let session = fetch_session_by_id(&state.db, &session_id)
+        .expect("session should load")
+        .expect("session should exist");
+    (result, session)
+}
+
+#[test]
+fn list_notes_follows_sync_tags_across_pages() {
+    let server = MockMiCloud::start(
+        (1..=5)
+            .map(|id| MockNote::new(&id.to_string(), "Note", "Body"))
+            .collect(),
+    );
+    server.page_size(2);
+
+    let ids = client(&server)
+        .list_notes()
+        .expect("notes should list")
+        .into_iter()
+        .map(|note| note.id)
+        .collect::<Vec<_>>();
+
+    assert_eq!(ids, vec!["1", "2", "3", "4", "5"]);
+    let page_requests = server
+        .requests()
+        .into_iter()
+        .filter(|target| target.starts_with("/note/full/page/"))
+        .collect::<Vec<_>>();
+    assert_eq!(page_requests.len(), 3);
+    assert!(page_requests[2].contains("syncTag=page-4"));
+}
+
+#[test]
+fn run_export_writes_api_notes_with_images() {
+    let test_dir = TestDir::new("api");
+    let server = MockMiCloud::start(vec![
+        MockNote::new("1", "First", "Hello").with_image("img.1", PIXEL_PNG),
+        MockNote::new("2", "Second", "<b>World</b>"),
+    ]);
+
+    let (result, session) = export_from(&test_dir, &client(&server));
+
+    result.expect("API export should finish");
+    assert_eq!(session.status, "completed");
+    assert_eq!(session.notes_count, 2);
+    assert_eq!(session.images_count, 1);
+
+    let markdown = fs::read_to_string(&session.output_path).expect("export should be written");
+    assert!(markdown.contains("## Title: First\nHello"));
+    assert!(markdown.contains("## Title: Second\nWorld"));
+    assert!(markdown.contains("*Created at: 13/04/2021 09:00*"));
+    assert!(markdown.contains("![image 1]"));
+}
+
+#[test]
+fn expired_session_fails_the_export_with_a_sign_in_hint() {
+    let test_dir = TestDir::new("expired");
+    let server = MockMiCloud::start(vec![MockNote::new("1", "First", "Hello")]);
+    server.expire_session();
+
+    let (result, session) = export_from(&test_dir, &client(&server));
+
+    let error = result.expect_err("expired cookies should fail the export");
+    assert!(error.to_string().contains("401"));
+    assert!(error.to_string().contains("Sign in again"));
+    assert_eq!(session.status, "error");
+    assert_eq!(session.error_message, Some(error.to_string()));
+}
+
+#[test]
+fn missing_cookie_is_rejected_like_an_expired_one() {
+    let server = MockMiCloud::start(vec![MockNote::new("1", "First", "Hello")]);
+    let anonymous = MiCloudClient::new(server.base_url(), "").expect("client should build");
+
+    let error = anonymous
+        .fetch_note("1")
+        .expect_err("request without cookie should fail");
+
+    assert!(error.to_string().contains("Sign in again"));
+}
+
+#[test]
+fn failing_note_detail_fails_the_export() {
+    let test_dir = TestDir::new("detail_failure");
+    let server = MockMiCloud::start(vec![
+        MockNote::new("1", "First", "Hello"),
+        MockNote::new("2", "Second", "World"),
+    ]);
+    server.fail("/note/note/2/", 503);
+
+    let (result, session) = export_from(&test_dir, &client(&server));
+
+    assert!(result.is_err());
+    assert_eq!(session.status, "error");
+    assert_eq!(session.notes_count, 1);
+}
+
+#[test]
+fn redirect_to_disallowed_host_is_not_followed() {
+    let server = MockMiCloud::start(vec![
+        MockNote::new("1", "First", "Hello").with_image("img.1", PIXEL_PNG)
+    ]);
+    server.redirect("/file/full", &server.foreign_url("/raw/escaped.png"));
+
+    let error = client(&server)
+        .fetch_image("img.1")
+        .expect_err("redirect off Mi Cloud should fail");
+
+    assert_eq!(error.to_string(), "Redirect host is not allowed: localhost");
+    assert!(!server
+        .requests()
+        .iter()
+        .any(|target| target.starts_with("/raw/")));
+}
+
+#[test]
+fn redirect_within_the_same_host_is_followed() {
+    let server = MockMiCloud::start(Vec::new());
+    let same_host = server
+        .base_url()
+        .join("raw/moved.png")
+        .expect("URL should join");
+    server.redirect("/old.png", same_host.as_str());
+    let source = server.base_url().join("old.png").expect("URL should join");
+
+    let image = download_scrape_image(&client(&server), source.as_str())
+        .expect("same-host redirect should download");
+
+    assert_eq!(image, "L3Jhdy9tb3ZlZC5wbmc=");
+}
+
+#[test]
+fn download_scrape_image_rejects_foreign_and_plain_http_urls() {
+    let server = MockMiCloud::start(Vec::new());
+    let client = client(&server);
+
+    let plain = download_scrape_image(&client, "http://cdn.i.mi.com/a.png")
+        .expect_err("plain HTTP should be rejected");
+    assert_eq!(plain.to_string(), "Only HTTPS image URLs are allowed.");
+
+    let foreign = download_scrape_image(&client, "https://example.com/a.png")
+        .expect_err("unknown host should be rejected");
+    assert_eq!(foreign.to_string(), "Unsupported image host: example.com");
+    assert!(server.requests().is_empty());
+}
+
+#[test]
+fn slow_images_are_downloaded_within_the_timeout() {
+    let test_dir = TestDir::new("slow_image");
+    let server = MockMiCloud::start(vec![
+        MockNote::new("1", "First", "Hello").with_image("img.1", PIXEL_PNG)
+    ]);
+    server.delay("/file/full", Duration::from_millis(300));
+
+    let (result, session) = export_from(&test_dir, &client(&server));
+
+    result.expect("slow image should not fail the export");
+    assert_eq!(session.images_count, 1);
+}
+
+#[test]
+fn images_past_the_timeout_are_skipped_without_failing_the_export() {
+    let test_dir = TestDir::new("timed_out_image");
+    let server = MockMiCloud::start(vec![
+        MockNote::new("1", "First", "Hello").with_image("img.1", PIXEL_PNG)
+    ]);
+    server.delay("/file/full", Duration::from_secs(2));
+    let client = MiCloudClient::with_timeout(
+        server.base_url(),
+        SESSION_COOKIE,
+        Duration::from_millis(300),
+    )
+    .expect("client should build");
+
+    let (result, session) = export_from(&test_dir, &client);
+
+    result.expect("timed out image should not fail the export");
+    assert_eq!(session.status, "completed");
+    assert_eq!(session.notes_count, 1);
+    assert_eq!(session.images_count, 0);
+}
+
+#[test]
+fn failed_image_downloads_are_reported_in_the_note_log_line() {
+    let test_dir = TestDir::new("failed_image");
+    let server = MockMiCloud::start(vec![MockNote::new("1", "First", "Hello")
+        .with_image("img.1", PIXEL_PNG)
+        .with_image("img.2", PIXEL_PNG)]);
+    server.fail("/file/full?type=note_img&fileid=img.2", 500);
+    let events = MemoryEvents::default();
+
+    let (result, session) = export_with(&test_dir, &client(&server), &events, |_| {});
+
+    result.expect("failed image should not fail the export");
+    assert_eq!(session.status, "completed");
+    assert_eq!(session.images_count, 1);
+    let log_lines = events
+        .events()
+        .into_iter()
+        .filter_map(|event| match event {
+            ExportEvent::Progress(progress) => Some(progress.log_line),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    assert!(log_lines
+        .contains(&"Processed note 1: First (1 image(s) skipped due to errors)".to_string()));
+}
+
+#[test]
+fn fetch_note_timestamps_reads_detail_entry() {
+    let server = MockMiCloud::start(vec![MockNote::new("9", "Dated", "Body")]);
+
+    let timestamps = fetch_note_timestamps(server.base_url(), Some(" 9 "), Some(SESSION_COOKIE))
+        .expect("timestamps should load");
+
+    assert_eq!(timestamps.created, Some(1_618_304_400_000));
+    assert_eq!(timestamps.modified, Some(1_618_390_800_000));
+    assert!(fetch_note_timestamps(server.base_url(), Some("404"), Some(SESSION_COOKIE)).is_none());
+    assert!(fetch_note_timestamps(server.base_url(), None, Some(SESSION_COOKIE)).is_none());
+}
+
+fn folder_notes() -> Vec<MockNote> {
+    vec![
+        MockNote::new("1", "Groceries", "Milk")
+            .in_folder("11", "Home")
+            .with_image("img.1", PIXEL_PNG),
+        MockNote::new("2", "Standup", "Notes").in_folder("22", "Work"),
+        MockNote::new("3", "Loose", "Unfiled"),
+        MockNote::new("4", "Chores", "Dishes").in_folder("11", "Home"),
+    ]
+}
+
+#[test]
+fn list_notes_names_folders_listed_on_the_first_page() {
+    let server = MockMiCloud::start(folder_notes());
+    server.page_size(2);
+
+    let folders = client(&server)
+        .list_notes()
+        .expect("notes should list")
+        .into_iter()
+        .map(|note| (note.id, note.folder_id, note.folder))
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        folders,
+        vec![
+            (
+                "1".to_string(),
+                Some("11".to_string()),
+                Some("Home".to_string())
+            ),
+            (
+                "2".to_string(),
+                Some("22".to_string()),
+                Some("Work".to_string())
+            ),
+            ("3".to_string(), None, None),
+            (
+                "4".to_string(),
+                Some("11".to_string()),
+                Some("Home".to_string())
+            ),
+        ]
+    );
+}
+
+#[test]
+fn split_export_writes_folders_as_subdirectories() {
+    let test_dir = TestDir::new("split_folders");
+    let server = MockMiCloud::start(folder_notes());
+
+    let (result, session) = export_with(
+        &test_dir,
+        &client(&server),
+        &MemoryEvents::default(),
+        |options| {
+            options.split = true;
+            options.name_by_title = true;
+        },
+    );
+
+    result.expect("API export should finish");
+    let root = PathBuf::from(&session.output_path);
+    let home_note = fs::read_to_string(root.join("Home").join("Groceries_0002.md"))
+        .expect("home note should be in its folder");
+    assert!(home_note.starts_with("****\n# Folder: Home\n## Title: Groceries"));
+    assert!(home_note.contains("](<../images/"));
+    assert!(root.join("Home").join("Chores_0003.md").is_file());
+    assert!(root.join("Work").join("Standup_0004.md").is_file());
+    assert!(root.join("Loose_0001.md").is_file());
+    assert_eq!(session.notes_count, 4);
+    assert_eq!(
+        session.folders,
+        vec!["Home".to_string(), "Work".to_string()]
+    );
+}
+
+#[test]
+fn single_file_export_groups_folders_into_sections() {
+    let test_dir = TestDir::new("single_folders");
+    let server = MockMiCloud::start(folder_notes());
+
+    let (result, session) = export_from(&test_dir, &client(&server));
+
+    result.expect("API export should finish");
+    let markdown = fs::read_to_string(&session.output_path).expect("export should be written");
+    assert_eq!(markdown.matches("# Folder: Home").count(), 1);
+    assert_eq!(markdown.matches("# Folder: Work").count(), 1);
+    let loose = markdown
+        .find("Loose")
+        .expect("unfiled note should be exported");
+    let home = markdown.find("# Folder: Home").expect("home section");
+    let chores = markdown.find("Chores").expect("second home note");
+    let work = markdown.find("# Folder: Work").expect("work section");
+    assert!(loose < home && home < chores && chores < work);
+
+    let notes = parse_notes_from_markdown(
+        &markdown,
+        &OutputTimezone::parse("UTC").expect("UTC should parse"),
+    )
+    .expect("export should convert");
+    let folders = notes
+        .iter()
+        .map(|note| note.folder.as_deref())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        folders,
+        vec![None, Some("Home"), Some("Home"), Some("Work")]
+    );
+}
+
+#[test]
+fn folder_filter_exports_only_selected_folders() {
+    let test_dir = TestDir::new("folder_filter");
+    let server = MockMiCloud::start(folder_notes());
+
+    let (result, session) = export_with(
+        &test_dir,
+        &client(&server),
+        &MemoryEvents::default(),
+        |options| {
+            options.filter.folders = vec!["home".to_string(), "22".to_string()];
+        },
+    );
+
+    result.expect("API export should finish");
+    assert_eq!(session.notes_count, 3);
+    assert_eq!(
+        session.folders,
+        vec!["Home".to_string(), "Work".to_string()]
+    );
+    assert!(!server
+        .requests()
+        .iter()
+        .any(|target| target.starts_with("/note/note/3/")));
+
+    let stored = fetch_session_by_id(
+        &init_db(&test_dir.path.join("sessions.db")).expect("database should reopen"),
+        &session.id,
+    )
+    .expect("session should load")
+    .expect("session should exist");
+    assert_eq!(stored.folders, session.folders);
+}
@@ -0,0 +1,350 @@
+//! Local stand-in for the Mi Cloud notes API, serving the note list, note details, images and
+//! the notes page with configurable latency, failures and redirects.
+
+use std::{
+    collections::HashMap,
+    io::{Read, Write},
+    net::{TcpListener, TcpStream},
+    sync::{Arc, Mutex},
+    thread,
+    time::Duration,
+};
+
+use serde_json::{json, Value};
+use url::Url;
+
+pub const SESSION_COOKIE: &str = "userId=7; serviceToken=mock-token";
+
+#[derive(Debug, Clone)]
+pub struct MockNote {
+    pub id: String,
+    pub title: String,
+    pub content: String,
+    pub created: i64,
+    pub modified: i64,
+    pub folder: Option<(String, String)>,
+    pub images: Vec<(String, Vec<u8>)>,
+}
+
+impl MockNote {
+    pub fn new(id: &str, title: &str, content: &str) -> Self {
+        Self {
+            id: id.to_string(),
+            title: title.to_string(),
+            content: content.to_string(),
+            created: 1_618_304_400_000,
+            modified: 1_618_390_800_000,
+            folder: None,
+            images: Vec::new(),
+        }
+    }
+
+    pub fn in_folder(mut self, folder_id: &str, name: &str) -> Self {
+        self.folder = Some((folder_id.to_string(), name.to_string()));
+        self
+    }
+
+    fn folder_id(&self) -> &str {
+        self.folder.as_ref().map_or("0", |(id, _)| id.as_str())
+    }
+
+    pub fn with_image(mut self, file_id: &str, bytes: &[u8]) -> Self {
+        self.images.push((file_id.to_string(), bytes.to_vec()));
+        self
+    }
+
+    fn detail(&self) -> Value {
+        let mut content = self.content.clone();
+        for (file_id, _) in &self.images {
+            content.push_str(&format!("\n<img fileid=\"{file_id}\" imgshow=\"0\" />"));
+        }
+        json!({
+            "result": "ok",
+            "data": {
+                "entry": {
+                    "id": self.id,
+                    "content": content,
+                    "createDate": self.created,
+                    "modifyDate": self.modified,
+                    "folderId": self.folder_id(),
+                    "extraInfo": json!({ "title": self.title, "note_content_type": "common" })
+                        .to_string(),
+                }
+            }
+        })
+    }
+}
+
+#[derive(Default)]
+struct MockState {
+    notes: Vec<MockNote>,
+    page_size: usize,
+    expired: bool,
+    latency: Vec<(String, Duration)>,
+    failures: Vec<(String, u16)>,
+    redirects: HashMap<String, String>,
+    requests: Vec<String>,
+}
+
+struct MockResponse {
+    status: u16,
+    location: Option<String>,
+    body: Vec<u8>,
+}
+
+impl MockResponse {
+    fn status(status: u16) -> Self {
+        Self {
+            status,
+            location: None,
+            body: Vec::new(),
+        }
+    }
+
+    fn json(value: Value) -> Self {
+        Self {
+            status: 200,
+            location: None,
+            body: value.to_string().into_bytes(),
+        }
+    }
+
+    fn bytes(body: Vec<u8>) -> Self {
+        Self {
+            status: 200,
+            location: None,
+            body,
+        }
+    }
+
+    fn with_status(mut self, status: u16) -> Self {
+        self.status = status;
+        self
+    }
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        302 => "Found",
+        401 => "Unauthorized",
+        404 => "Not Found",
+        500 => "Internal Server Error",
+        503 => "Service Unavailable",
+        _ => "Status",
+    }
+}
+
+#[derive(Clone)]
+pub struct MockMiCloud {
+    base_url: Url,
+    state: Arc<Mutex<MockState>>,
+}
+
+impl MockMiCloud {
+    /// Starts the server on an ephemeral port; it lives until the test process exits.
+    pub fn start(notes: Vec<MockNote>) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("mock listener should bind");
+        let address = listener
+            .local_addr()
+            .expect("mock listener should expose local address");
+        let server = Self {
+            base_url: Url::parse(&format!("http://{address}/")).expect("mock URL should parse"),
+            state: Arc::new(Mutex::new(MockState {
+                notes,
+                page_size: 200,
+                ..MockState::default()
+            })),
+        };
+
+        let handler = server.clone();
+        thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let handler = handler.clone();
+                thread::spawn(move || handler.serve(stream));
+            }
+        });
+        server
+    }
+
+    pub fn base_url(&self) -> Url {
+        self.base_url.clone()
+    }
+
+    /// The same server addressed by another host name, which Mi Cloud clients must not trust.
+    pub fn foreign_url(&self, path: &str) -> String {
+        let port = self.base_url.port().expect("mock URL should have a port");
+        format!("http://localhost:{port}/{}", path.trim_start_matches('/'))
+    }
+
+    fn with_state(&self, action: impl FnOnce(&mut MockState)) -> &Self {
+        action(&mut self.state.lock().expect("mock state lock"));
+        self
+    }
+
+    pub fn page_size(&self, page_size: usize) -> &Self {
+        self.with_state(|state| state.page_size = page_size.max(1))
+    }
+
+    /// Answers every API request with 401, like an expired `serviceToken`.
+    pub fn expire_session(&self) -> &Self {
+        self.with_state(|state| state.expired = true)
+    }
+
+    pub fn delay(&self, path_prefix: &str, latency: Duration) -> &Self {
+        self.with_state(|state| state.latency.push((path_prefix.to_string(), latency)))
+    }
+
+    pub fn fail(&self, path_prefix: &str, status: u16) -> &Self {
+        self.with_state(|state| state.failures.push((path_prefix.to_string(), status)))
+    }
+
+    pub fn redirect(&self, path: &str, location: &str) -> &Self {
+        self.with_state(|state| {
+            state
+                .redirects
+                .insert(path.to_string(), location.to_string());
+        })
+    }
+
+    pub fn requests(&self) -> Vec<String> {
+        self.state.lock().expect("mock state lock").requests.clone()
+    }
+
+    fn serve(&self, mut stream: TcpStream) {
+        let mut buffer = [0_u8; 8192];
+        let read = stream.read(&mut buffer).unwrap_or(0);
+        let request = String::from_utf8_lossy(&buffer[..read]).to_string();
+        let target = request.split_whitespace().nth(1).unwrap_or("/").to_string();
+        let has_cookie = request.lines().any(|line| {
+            line.to_ascii_lowercase().starts_with("cookie:") && line.contains("serviceToken=")
+        });
+
+        let (latency, response) = {
+            let mut state = self.state.lock().expect("mock state lock");
+            state.requests.push(target.clone());
+            let latency = state
+                .latency
+                .iter()
+                .find(|(prefix, _)| target.starts_with(prefix.as_str()))
+                .map(|(_, latency)| *latency);
+            (latency, route(&state, &target, has_cookie))
+        };
+        if let Some(latency) = latency {
+            thread::sleep(latency);
+        }
+
+        let mut head = format!(
+            "HTTP/1.1 {} {}\r\nContent-Length: {}\r\nConnection: close\r\n",
+            response.status,
+            reason(response.status),
+            response.body.len()
+        );
+        if let Some(location) = response.location {
+            head.push_str(&format!("Location: {location}\r\n"));
+        }
+        head.push_str("\r\n");
+        let _ = stream.write_all(head.as_bytes());
+        let _ = stream.write_all(&response.body);
+        let _ = stream.flush();
+    }
+}
+
+fn query_value(target: &str, key: &str) -> Option<String> {
+    let url = Url::parse(&format!("http://mock{target}")).ok()?;
+    url.query_pairs()
+        .find(|(name, _)| name == key)
+        .map(|(_, value)| value.to_string())
+}
+
+fn route(state: &MockState, target: &str, has_cookie: bool) -> MockResponse {
+    if let Some((_, status)) = state
+        .failures
+        .iter()
+        .find(|(prefix, _)| target.starts_with(prefix.as_str()))
+    {
+        return MockResponse::status(*status);
+    }
+    let path = target.split('?').next().unwrap_or(target);
+    if let Some(location) = state.redirects.get(path) {
+        return MockResponse {
+            status: 302,
+            location: Some(location.clone()),
+            body: Vec::new(),
+        };
+    }
+
+    if path.starts_with("/note/h5/") {
+        return MockResponse::bytes(b"<html><body>Mi Cloud notes</body></html>".to_vec());
+    }
+    if path.starts_with("/raw/") {
+        return MockResponse::bytes(path.as_bytes().to_vec());
+    }
+    if state.expired || !has_cookie {
+        return MockResponse::json(json!({ "result": "error", "code": 401 })).with_status(401);
+    }
+
+    if path == "/note/full/page/" {
+        let start = query_value(target, "syncTag")
+            .and_then(|tag| tag.strip_prefix("page-").and_then(|raw| raw.parse().ok()))
+            .unwrap_or(0_usize);
+        let limit = query_value(target, "limit")
+            .and_then(|raw| raw.parse::<usize>().ok())
+            .unwrap_or(state.page_size)
+            .min(state.page_size);
+        let end = (start + limit).min(state.notes.len());
+        let entries = state.notes[start.min(end)..end]
+            .iter()
+            .map(|note| {
+                json!({
+                    "id": note.id,
+                    "folderId": note.folder_id(),
+                    "createDate": note.created,
+                    "modifyDate": note.modified,
+                })
+            })
+            .collect::<Vec<_>>();
+        // Like Mi Cloud, folders are only listed with the first page.
+        let mut folders = Vec::new();
+        if start == 0 {
+            for (id, name) in state.notes.iter().filter_map(|note| note.folder.as_ref()) {
+                let folder = json!({ "id": id, "subject": name, "type": "folder" });
+                if !folders.contains(&folder) {
+                    folders.push(folder);
+                }
+            }
+        }
+        return MockResponse::json(json!({
+            "result": "ok",
+            "data": {
+                "entries": entries,
+                "folders": folders,
+                "syncTag": format!("page-{end}"),
+                "lastPage": end >= state.notes.len(),
+            }
+        }));
+    }
+
+    if let Some(id) = path
+        .strip_prefix("/note/note/")
+        .map(|rest| rest.trim_end_matches('/'))
+    {
+        return match state.notes.iter().find(|note| note.id == id) {
+            Some(note) => MockResponse::json(note.detail()),
+            None => MockResponse::status(404),
+        };
+    }
+
+    if path == "/file/full" {
+        let file_id = query_value(target, "fileid").unwrap_or_default();
+        return state
+            .notes
+            .iter()
+            .flat_map(|note| note.images.iter())
+            .find(|(id, _)| *id == file_id)
+            .map(|(_, bytes)| MockResponse::bytes(bytes.clone()))
+            .unwrap_or_else(|| MockResponse::status(404));
+    }
+
+    MockResponse::status(404)
+}
@@ -1,6 +1,83 @@
+mod backup_tests;
 mod converter_tests;
 mod date_parser_tests;
+mod diff_tests;
+mod duplicates_tests;
+mod export_tests;
 mod files_tests;
 mod markdown_tests;
+mod micloud_tests;
+mod mock_micloud;
+mod queue_tests;
+mod reconcile_tests;
+mod relocate_tests;
+mod retention_tests;
 mod scraper_tests;
+mod search_tests;
+mod sessions_tests;
+mod settings_tests;
+mod statistics_tests;
+mod timezone_tests;
 mod update_tests;
+
+use std::{
+    fs,
+    path::PathBuf,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::models::Session;
+
+/// A completed session with neutral defaults; tests override fields with struct update syntax.
+pub(crate) fn session_fixture(id: &str) -> Session {
+    Session {
+        id: id.to_string(),
+        domain: "us.i.mi.com".to_string(),
+        started_at: "2026-01-01T10:00:00+00:00".to_string(),
+        completed_at: None,
+        status: "completed".to_string(),
+        notes_count: 1,
+        images_count: 0,
+        split_mode: false,
+        name_by_title: false,
+        timestamp_format: "dd-MM-yyyy_HH-mm-ss".to_string(),
+        images_enabled: false,
+        output_path: format!("/nonexistent/{id}.md"),
+        images_dir_name: None,
+        error_message: None,
+        unparsed_dates: 0,
+        pinned: false,
+        label: None,
+        comment: None,
+        account_hint: None,
+        folders: Vec::new(),
+        filtered_count: 0,
+    }
+}
+
+/// A unique temporary directory that is removed when the test finishes.
+pub(crate) struct TestDir {
+    pub path: PathBuf,
+}
+
+impl TestDir {
+    pub fn new(name: &str) -> Self {
+        let unique = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("system time should be after unix epoch")
+            .as_nanos();
+        let path = std::env::temp_dir().join(format!(
+            "xiaomi_note_exporter_{name}_{}_{}",
+            std::process::id(),
+            unique
+        ));
+        fs::create_dir_all(&path).expect("temp test directory should be created");
+        Self { path }
+    }
+}
+
+impl Drop for TestDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.path);
+    }
+}
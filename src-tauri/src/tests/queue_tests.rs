@@ -0,0 +1,142 @@
+use std::path::PathBuf;
+
+use crate::{
+    db::sessions::init_db,
+    models::{ExportFilterSpec, ExportJobSettings, ExportSource},
+    services::{
+        export::{
+            finish_scrape, start_export, ExportOptions, MemoryEvents, DEFAULT_CREATED_DATE_FORMAT,
+            DEFAULT_TIMESTAMP_FORMAT,
+        },
+        queue::{
+            enqueue_export, load_queue, move_queued_export, queue_status, remove_queued_export,
+            requeue_job, set_queue_running, take_next_job,
+        },
+        settings::bootstrap_settings,
+    },
+    state::AppState,
+    tests::TestDir,
+};
+
+fn state(test_dir: &TestDir) -> AppState {
+    let db = init_db(&test_dir.path.join("sessions.db")).expect("test database should initialize");
+    AppState::new(db, test_dir.path.join("settings.json"))
+}
+
+fn job(test_dir: &TestDir, name: &str) -> ExportJobSettings {
+    ExportJobSettings {
+        domain: "us.i.mi.com".to_string(),
+        output_dir: test_dir.path.join(name).to_string_lossy().into_owned(),
+        split: false,
+        name_by_title: false,
+        timestamp_format: DEFAULT_TIMESTAMP_FORMAT.to_string(),
+        created_date_format: DEFAULT_CREATED_DATE_FORMAT.to_string(),
+        export_images: false,
+        skip_duplicates: true,
+        unparsed_date_policy: Default::default(),
+        source: Default::default(),
+        filter: Default::default(),
+        dry_run: false,
+    }
+}
+
+fn queued_dirs(state: &AppState) -> Vec<String> {
+    queue_status(state)
+        .expect("queue should load")
+        .jobs
+        .into_iter()
+        .map(|job| {
+            PathBuf::from(job.settings.output_dir)
+                .file_name()
+                .expect("output dir should have a name")
+                .to_string_lossy()
+                .into_owned()
+        })
+        .collect()
+}
+
+#[test]
+fn queued_jobs_can_be_reordered_removed_and_survive_a_restart() {
+    let test_dir = TestDir::new("persist");
+    let state = state(&test_dir);
+    let first = enqueue_export(&state, job(&test_dir, "first")).expect("job should queue");
+    enqueue_export(&state, job(&test_dir, "second")).expect("job should queue");
+    let third = enqueue_export(&state, job(&test_dir, "third")).expect("job should queue");
+
+    move_queued_export(&state, &third.id, 0).expect("job should move");
+    move_queued_export(&state, &first.id, 10).expect("job should move to the end");
+    assert_eq!(queued_dirs(&state), ["third", "second", "first"]);
+
+    let status = remove_queued_export(&state, &third.id).expect("job should be removed");
+    assert_eq!(status.jobs.len(), 2);
+    assert!(remove_queued_export(&state, &third.id).is_err());
+
+    set_queue_running(&state, true).expect("queue should start");
+    let restarted = AppState::new(state.db.clone(), state.settings_path.clone());
+    load_queue(&restarted).expect("queue should be restored");
+    let restored = queue_status(&restarted).expect("queue should load");
+    assert!(!restored.running);
+    assert_eq!(queued_dirs(&restarted), ["second", "first"]);
+    assert_eq!(restored.jobs[1], first);
+}
+
+#[test]
+fn running_queue_hands_out_jobs_one_export_at_a_time() {
+    let test_dir = TestDir::new("run");
+    let state = state(&test_dir);
+    let events = MemoryEvents::default();
+    enqueue_export(&state, job(&test_dir, "first")).expect("job should queue");
+    enqueue_export(&state, job(&test_dir, "second")).expect("job should queue");
+
+    assert!(take_next_job(&state).expect("queue should load").is_none());
+    set_queue_running(&state, true).expect("queue should start");
+    let settings = bootstrap_settings(&state.settings_path, &test_dir.path)
+        .expect("settings should bootstrap");
+
+    let next = take_next_job(&state)
+        .expect("queue should load")
+        .expect("first job should be taken");
+    let session_id = start_export(&state, &ExportOptions::from_job(&next.settings, &settings))
+        .expect("queued export should start");
+    assert!(take_next_job(&state).expect("queue should load").is_none());
+    assert_eq!(queued_dirs(&state), ["second"]);
+
+    finish_scrape(&state, &session_id, &events).expect("export should finish");
+    let next = take_next_job(&state)
+        .expect("queue should load")
+        .expect("second job should be taken");
+    requeue_job(&state, next).expect("job should be put back");
+    assert_eq!(queued_dirs(&state), ["second"]);
+
+    take_next_job(&state)
+        .expect("queue should load")
+        .expect("second job should be taken");
+    assert!(take_next_job(&state).expect("queue should load").is_none());
+    assert!(!queue_status(&state).expect("queue should load").running);
+}
+
+#[test]
+fn invalid_jobs_are_rejected_when_queued() {
+    let test_dir = TestDir::new("invalid");
+    let state = state(&test_dir);
+
+    let mut bad_domain = job(&test_dir, "out");
+    bad_domain.domain = "not a domain".to_string();
+    assert!(enqueue_export(&state, bad_domain).is_err());
+
+    let mut bad_filter = job(&test_dir, "out");
+    bad_filter.filter = ExportFilterSpec {
+        title_regex: Some("(".to_string()),
+        ..Default::default()
+    };
+    assert!(enqueue_export(&state, bad_filter).is_err());
+
+    let mut page_folders = job(&test_dir, "out");
+    page_folders.source = ExportSource::Dom;
+    page_folders.filter.folders = vec!["Work".to_string()];
+    assert!(enqueue_export(&state, page_folders).is_err());
+    assert!(queue_status(&state)
+        .expect("queue should load")
+        .jobs
+        .is_empty());
+}
@@ -0,0 +1,140 @@
+use std::{fs, path::Path};
+
+use crate::{
+    db::{
+        connection::Database,
+        sessions::{fetch_session_by_id, init_db, insert_session},
+    },
+    models::Session,
+    services::reconcile::{
+        count_exported_notes, reconcile_sessions, verify_session, STATUS_COMPLETED,
+        STATUS_INTERRUPTED, STATUS_MISSING,
+    },
+    tests::{session_fixture, TestDir},
+};
+
+fn session(id: &str, status: &str, output_path: &Path, split_mode: bool) -> Session {
+    Session {
+        status: status.to_string(),
+        split_mode,
+        images_enabled: true,
+        output_path: output_path.to_string_lossy().to_string(),
+        images_dir_name: Some("images".to_string()),
+        ..session_fixture(id)
+    }
+}
+
+fn load(db: &Database, id: &str) -> Session {
+    fetch_session_by_id(db, id)
+        .expect("session should load")
+        .expect("session should exist")
+}
+
+#[test]
+fn reconcile_marks_running_sessions_interrupted_and_recounts_files() {
+    let test_dir = TestDir::new("running");
+    let db = init_db(&test_dir.path.join("sessions.db")).expect("test database should initialize");
+    let export_dir = test_dir.path.join("exported_notes");
+    fs::create_dir_all(export_dir.join("images")).expect("images dir should be created");
+    for index in 1..=3 {
+        fs::write(export_dir.join(format!("note_{index}.md")), "## Note\n")
+            .expect("note should be written");
+    }
+    fs::write(export_dir.join("images/note_img_1.png"), [0_u8]).expect("image should be written");
+    insert_session(&db, &session("crashed", "running", &export_dir, true))
+        .expect("session should be inserted");
+    insert_session(&db, &session("paused", "paused", &export_dir, true))
+        .expect("session should be inserted");
+
+    let repaired = reconcile_sessions(&db).expect("reconcile should succeed");
+
+    assert_eq!(repaired.len(), 2);
+    assert_eq!(repaired[0].previous_status, "running");
+    assert_eq!(load(&db, "paused").status, STATUS_INTERRUPTED);
+    let stored = load(&db, "crashed");
+    assert_eq!(stored.status, STATUS_INTERRUPTED);
+    assert_eq!(stored.notes_count, 3);
+    assert_eq!(stored.images_count, 1);
+    assert!(stored.completed_at.is_some());
+}
+
+#[test]
+fn reconcile_flags_missing_output_and_leaves_healthy_sessions() {
+    let test_dir = TestDir::new("missing");
+    let db = init_db(&test_dir.path.join("sessions.db")).expect("test database should initialize");
+    let healthy_path = test_dir.path.join("healthy.md");
+    fs::write(&healthy_path, "## A\n*Created at: 2026-01-01*\n").expect("export should exist");
+    insert_session(&db, &session("healthy", "completed", &healthy_path, false))
+        .expect("session should be inserted");
+    insert_session(
+        &db,
+        &session("gone", "completed", &test_dir.path.join("gone.md"), false),
+    )
+    .expect("session should be inserted");
+
+    let repaired = reconcile_sessions(&db).expect("reconcile should succeed");
+
+    assert_eq!(repaired.len(), 1);
+    assert_eq!(repaired[0].session.id, "gone");
+    assert!(!repaired[0].output_exists);
+    assert_eq!(load(&db, "gone").status, STATUS_MISSING);
+    assert_eq!(load(&db, "healthy").status, STATUS_COMPLETED);
+}
+
+#[test]
+fn verify_session_restores_missing_session_when_output_returns() {
+    let test_dir = TestDir::new("restore");
+    let db = init_db(&test_dir.path.join("sessions.db")).expect("test database should initialize");
+    let output_path = test_dir.path.join("notes.md");
+    insert_session(&db, &session("restored", "completed", &output_path, false))
+        .expect("session should be inserted");
+    reconcile_sessions(&db).expect("reconcile should succeed");
+    assert_eq!(load(&db, "restored").status, STATUS_MISSING);
+
+    fs::write(
+        &output_path,
+        "## A\n*Created at: 2026-01-01*\n\n****\n\n## B\n*Created at: 2026-01-02*\n",
+    )
+    .expect("export should be restored");
+    let verification = verify_session(&db, &load(&db, "restored")).expect("verify should succeed");
+
+    assert!(verification.changed);
+    assert_eq!(verification.notes_on_disk, 2);
+    assert_eq!(verification.session.status, STATUS_COMPLETED);
+    assert_eq!(verification.session.error_message, None);
+}
+
+#[test]
+fn failed_session_keeps_its_outcome_when_missing_output_returns() {
+    let test_dir = TestDir::new("restore_error");
+    let db = init_db(&test_dir.path.join("sessions.db")).expect("test database should initialize");
+    let output_path = test_dir.path.join("notes.md");
+    let mut failed = session("failed", "error", &output_path, false);
+    failed.error_message = Some("Mi Cloud returned 401.".to_string());
+    insert_session(&db, &failed).expect("session should be inserted");
+
+    reconcile_sessions(&db).expect("reconcile should succeed");
+    reconcile_sessions(&db).expect("reconcile should be repeatable");
+    assert_eq!(load(&db, "failed").status, STATUS_MISSING);
+
+    fs::write(&output_path, "## A\n*Created at: 2026-01-01*\n").expect("export should return");
+    let repaired = reconcile_sessions(&db).expect("reconcile should succeed");
+
+    assert_eq!(repaired.len(), 1);
+    assert_eq!(repaired[0].previous_status, STATUS_MISSING);
+    let restored = load(&db, "failed");
+    assert_eq!(restored.status, "error");
+    assert_eq!(
+        restored.error_message.as_deref(),
+        Some("Mi Cloud returned 401.")
+    );
+}
+
+#[test]
+fn count_exported_notes_ignores_empty_single_file() {
+    let test_dir = TestDir::new("empty");
+    let output_path = test_dir.path.join("empty.md");
+    fs::write(&output_path, "").expect("empty export should be written");
+
+    assert_eq!(count_exported_notes(&output_path, false), 0);
+}
@@ -0,0 +1,247 @@
+use std::{fs, path::Path};
+
+use crate::{
+    db::{
+        connection::Database,
+        search::{replace_path_index, NoteIndexEntry},
+        sessions::{fetch_session_by_id, fetch_session_by_output_path, init_db, insert_session},
+    },
+    models::{AppSettings, RetentionPolicy, Session},
+    services::{
+        files::{relative_to_root, resolve_from_root},
+        reconcile::STATUS_MISSING,
+        relocate::{relocate_sessions, sync_path_storage, update_path_storage},
+    },
+    tests::{session_fixture, TestDir},
+};
+
+fn session(id: &str, output_path: &Path) -> Session {
+    Session {
+        completed_at: Some("2026-01-01T10:05:00+00:00".to_string()),
+        output_path: output_path.to_string_lossy().to_string(),
+        ..session_fixture(id)
+    }
+}
+
+fn settings(export_root: &Path, relative_session_paths: bool) -> AppSettings {
+    AppSettings {
+        default_export_dir: export_root.to_string_lossy().to_string(),
+        theme: "system".to_string(),
+        notes_locale: "en-US".to_string(),
+        output_timezone: "UTC".to_string(),
+        retention: RetentionPolicy::default(),
+        relative_session_paths,
+        export_profiles: Vec::new(),
+    }
+}
+
+fn load(db: &Database, id: &str) -> Session {
+    fetch_session_by_id(db, id)
+        .expect("session should load")
+        .expect("session should exist")
+}
+
+fn stored_path(db: &Database, id: &str) -> String {
+    db.connection()
+        .expect("connection should be available")
+        .query_row(
+            "SELECT output_path FROM sessions WHERE id = ?1",
+            [id],
+            |row| row.get(0),
+        )
+        .expect("stored path should load")
+}
+
+fn write_note(path: &Path) {
+    fs::create_dir_all(path.parent().expect("note should have a parent"))
+        .expect("export directory should be created");
+    fs::write(path, "****\n## Title: Garden\nPlant tomatoes\n").expect("note should be written");
+}
+
+#[test]
+fn relocate_sessions_moves_files_and_rewrites_paths() {
+    let test_dir = TestDir::new("move");
+    let db = init_db(&test_dir.path.join("sessions.db")).expect("test database should initialize");
+    let old_root = test_dir.path.join("old_exports");
+    let new_root = test_dir.path.join("drive/new_exports");
+    let output_path = old_root.join("exported_notes_01.md");
+    write_note(&output_path);
+    insert_session(&db, &session("moved", &output_path)).expect("session should be inserted");
+    replace_path_index(
+        &db,
+        &output_path.to_string_lossy(),
+        &[NoteIndexEntry {
+            session_id: Some("moved".to_string()),
+            file_path: output_path.to_string_lossy().to_string(),
+            title: "Garden".to_string(),
+            content: "Plant tomatoes".to_string(),
+            creation_date: "2021-04-10T09:00:00Z".to_string(),
+        }],
+    )
+    .expect("note should be indexed");
+
+    let report = relocate_sessions(
+        &db,
+        &old_root.to_string_lossy(),
+        &new_root.to_string_lossy(),
+        true,
+    )
+    .expect("relocation should succeed");
+
+    let new_output = new_root.join("exported_notes_01.md");
+    assert_eq!(report.relocated_sessions, 1);
+    assert!(report.moved_files);
+    assert_eq!(report.verified_sessions, 1);
+    assert!(report.missing_sessions.is_empty());
+    assert!(!old_root.exists());
+    assert!(new_output.is_file());
+    assert_eq!(
+        load(&db, "moved").output_path,
+        new_output.to_string_lossy().to_string()
+    );
+    let indexed_path: String = db
+        .connection()
+        .expect("connection should be available")
+        .query_row("SELECT file_path FROM note_search", [], |row| row.get(0))
+        .expect("indexed path should load");
+    assert_eq!(indexed_path, new_output.to_string_lossy().to_string());
+}
+
+#[test]
+fn relocate_sessions_reports_sessions_missing_at_the_new_location() {
+    let test_dir = TestDir::new("missing");
+    let db = init_db(&test_dir.path.join("sessions.db")).expect("test database should initialize");
+    let old_root = test_dir.path.join("old_exports");
+    let new_root = test_dir.path.join("new_exports");
+    let moved_output = old_root.join("exported_notes_01.md");
+    let lost_output = old_root.join("exported_notes_02.md");
+    write_note(&new_root.join("exported_notes_01.md"));
+    insert_session(&db, &session("moved", &moved_output)).expect("session should be inserted");
+    insert_session(&db, &session("lost", &lost_output)).expect("session should be inserted");
+
+    let report = relocate_sessions(
+        &db,
+        &old_root.to_string_lossy(),
+        &new_root.to_string_lossy(),
+        false,
+    )
+    .expect("relocation should succeed");
+
+    assert_eq!(report.relocated_sessions, 2);
+    assert!(!report.moved_files);
+    assert_eq!(report.missing_sessions, vec!["lost".to_string()]);
+    assert_eq!(load(&db, "moved").status, "completed");
+    assert_eq!(load(&db, "lost").status, STATUS_MISSING);
+}
+
+#[test]
+fn relocate_sessions_rejects_nested_or_occupied_destinations() {
+    let test_dir = TestDir::new("invalid");
+    let db = init_db(&test_dir.path.join("sessions.db")).expect("test database should initialize");
+    let old_root = test_dir.path.join("old_exports");
+    let occupied = test_dir.path.join("occupied");
+    write_note(&old_root.join("exported_notes_01.md"));
+    write_note(&occupied.join("other.md"));
+
+    let nested = old_root.join("nested");
+    assert!(relocate_sessions(
+        &db,
+        &old_root.to_string_lossy(),
+        &nested.to_string_lossy(),
+        true
+    )
+    .is_err());
+    assert!(relocate_sessions(
+        &db,
+        &old_root.to_string_lossy(),
+        &occupied.to_string_lossy(),
+        true
+    )
+    .is_err());
+    assert!(old_root.join("exported_notes_01.md").is_file());
+}
+
+#[test]
+fn relative_paths_follow_the_default_export_directory() {
+    let test_dir = TestDir::new("relative");
+    let db = init_db(&test_dir.path.join("sessions.db")).expect("test database should initialize");
+    let old_root = test_dir.path.join("old_exports");
+    let new_root = test_dir.path.join("new_exports");
+    let output_path = old_root.join("exported_notes_01.md");
+    let outside_path = test_dir.path.join("elsewhere/exported_notes_02.md");
+    insert_session(&db, &session("inside", &output_path)).expect("session should be inserted");
+    insert_session(&db, &session("outside", &outside_path)).expect("session should be inserted");
+
+    let absolute = settings(&old_root, false);
+    let relative = settings(&old_root, true);
+    update_path_storage(&db, &absolute, &relative).expect("relative storage should apply");
+    assert_eq!(stored_path(&db, "inside"), "exported_notes_01.md");
+    assert_eq!(
+        stored_path(&db, "outside"),
+        outside_path.to_string_lossy().to_string()
+    );
+    assert_eq!(
+        load(&db, "inside").output_path,
+        output_path.to_string_lossy().to_string()
+    );
+    assert!(
+        fetch_session_by_output_path(&db, &output_path.to_string_lossy())
+            .expect("lookup should succeed")
+            .is_some()
+    );
+
+    let moved = settings(&new_root, true);
+    update_path_storage(&db, &relative, &moved).expect("new root should apply");
+    let moved_output = new_root.join("exported_notes_01.md");
+    assert_eq!(
+        load(&db, "inside").output_path,
+        moved_output.to_string_lossy().to_string()
+    );
+
+    let restored = settings(&new_root, false);
+    update_path_storage(&db, &moved, &restored).expect("absolute storage should apply");
+    assert_eq!(
+        stored_path(&db, "inside"),
+        moved_output.to_string_lossy().to_string()
+    );
+    assert_eq!(db.path_root(), None);
+}
+
+#[test]
+fn new_sessions_are_stored_relative_when_enabled() {
+    let test_dir = TestDir::new("insert");
+    let db = init_db(&test_dir.path.join("sessions.db")).expect("test database should initialize");
+    let root = test_dir.path.join("exports");
+    sync_path_storage(&db, &settings(&root, true)).expect("relative storage should apply");
+
+    let output_path = root.join("split/exported_notes_01");
+    insert_session(&db, &session("split", &output_path)).expect("session should be inserted");
+
+    assert_eq!(
+        stored_path(&db, "split"),
+        Path::new("split")
+            .join("exported_notes_01")
+            .to_string_lossy()
+            .to_string()
+    );
+    assert_eq!(
+        load(&db, "split").output_path,
+        output_path.to_string_lossy().to_string()
+    );
+}
+
+#[test]
+fn relative_to_root_matches_whole_components() {
+    let root = Path::new("/exports/notes");
+    assert_eq!(
+        relative_to_root("/exports/notes/a.md", root),
+        Some("a.md".to_string())
+    );
+    assert_eq!(relative_to_root("/exports/notes-old/a.md", root), None);
+    assert_eq!(relative_to_root("/exports/notes", root), None);
+    assert_eq!(resolve_from_root("/other/a.md", Some(root)), "/other/a.md");
+    assert_eq!(
+        resolve_from_root("a.md", Some(root)),
+        Path::new("/exports/notes/a.md").to_string_lossy()
+    );
+}
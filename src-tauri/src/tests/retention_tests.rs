@@ -0,0 +1,131 @@
+use std::fs;
+
+use chrono::{DateTime, Duration, Utc};
+
+use crate::{
+    db::sessions::{fetch_all_sessions, init_db, insert_session, set_session_pinned},
+    models::{RetentionPolicy, Session},
+    services::retention::{apply_retention, select_expired_sessions},
+    tests::{session_fixture, TestDir},
+};
+
+fn now() -> DateTime<Utc> {
+    DateTime::parse_from_rfc3339("2026-03-01T12:00:00Z")
+        .expect("fixed timestamp should parse")
+        .with_timezone(&Utc)
+}
+
+fn session(id: &str, domain: &str, days_ago: i64) -> Session {
+    Session {
+        domain: domain.to_string(),
+        started_at: (now() - Duration::days(days_ago)).to_rfc3339(),
+        ..session_fixture(id)
+    }
+}
+
+fn ids(sessions: &[Session]) -> Vec<&str> {
+    sessions.iter().map(|session| session.id.as_str()).collect()
+}
+
+#[test]
+fn keep_last_applies_per_domain_and_skips_pinned_and_running() {
+    let mut pinned = session("us-pinned", "us.i.mi.com", 40);
+    pinned.pinned = true;
+    let mut running = session("us-running", "us.i.mi.com", 50);
+    running.status = "running".to_string();
+    let sessions = vec![
+        session("us-new", "us.i.mi.com", 1),
+        session("us-mid", "us.i.mi.com", 10),
+        session("us-old", "us.i.mi.com", 30),
+        pinned,
+        running,
+        session("cn-old", "i.mi.com", 90),
+    ];
+
+    let expired = select_expired_sessions(
+        &sessions,
+        &RetentionPolicy {
+            keep_last: Some(2),
+            max_age_days: None,
+        },
+        now(),
+    );
+
+    assert_eq!(ids(&expired), ["us-old"]);
+}
+
+#[test]
+fn max_age_keeps_recent_sessions_beyond_keep_last() {
+    let sessions = vec![
+        session("newest", "us.i.mi.com", 1),
+        session("recent", "us.i.mi.com", 5),
+        session("stale", "us.i.mi.com", 20),
+        session("ancient", "us.i.mi.com", 200),
+    ];
+
+    let combined = select_expired_sessions(
+        &sessions,
+        &RetentionPolicy {
+            keep_last: Some(1),
+            max_age_days: Some(7),
+        },
+        now(),
+    );
+    assert_eq!(ids(&combined), ["ancient", "stale"]);
+
+    let age_only = select_expired_sessions(
+        &sessions,
+        &RetentionPolicy {
+            keep_last: None,
+            max_age_days: Some(30),
+        },
+        now(),
+    );
+    assert_eq!(ids(&age_only), ["ancient"]);
+
+    let disabled = select_expired_sessions(&sessions, &RetentionPolicy::default(), now());
+    assert!(disabled.is_empty());
+}
+
+#[test]
+fn apply_retention_dry_run_lists_without_deleting() {
+    let test_dir = TestDir::new("apply");
+    let db = init_db(&test_dir.path.join("sessions.db")).expect("test database should initialize");
+
+    let mut output_paths = Vec::new();
+    for (id, days_ago) in [("keep", 1), ("old", 400), ("old-pinned", 500)] {
+        let output_path = test_dir.path.join(format!("{id}.md"));
+        fs::write(&output_path, "# Note\n").expect("export file should be written");
+        let mut record = session(id, "us.i.mi.com", days_ago);
+        record.started_at = (Utc::now() - Duration::days(days_ago)).to_rfc3339();
+        record.output_path = output_path.to_string_lossy().to_string();
+        insert_session(&db, &record).expect("session should be inserted");
+        output_paths.push(output_path);
+    }
+    set_session_pinned(&db, "old-pinned", true)
+        .expect("pin should succeed")
+        .expect("pinned session should exist");
+    let policy = RetentionPolicy {
+        keep_last: Some(1),
+        max_age_days: None,
+    };
+
+    let preview = apply_retention(&db, &policy, true).expect("dry run should succeed");
+    assert!(preview.dry_run);
+    assert_eq!(ids(&preview.sessions), ["old"]);
+    assert_eq!(preview.freed_bytes, 7);
+    assert_eq!(
+        fetch_all_sessions(&db).expect("sessions should load").len(),
+        3
+    );
+    assert!(output_paths[1].exists());
+
+    let applied = apply_retention(&db, &policy, false).expect("retention should succeed");
+    assert!(applied.failures.is_empty());
+    assert_eq!(ids(&applied.sessions), ["old"]);
+    let remaining = fetch_all_sessions(&db).expect("sessions should load");
+    assert_eq!(ids(&remaining), ["old-pinned", "keep"]);
+    assert!(output_paths[0].exists());
+    assert!(!output_paths[1].exists());
+    assert!(output_paths[2].exists());
+}
@@ -1,5 +1,11 @@
-use crate::services::scraper::{
-    auth_window_label, build_notes_url, build_scrape_script, AUTH_WINDOW_LABEL_PREFIX,
+use reqwest::blocking::Client;
+
+use crate::{
+    services::scraper::{
+        auth_window_label, build_notes_url, build_notes_url_at, build_scrape_script,
+        AUTH_WINDOW_LABEL_PREFIX,
+    },
+    tests::mock_micloud::MockMiCloud,
 };
 
 #[test]
@@ -12,14 +18,46 @@ fn auth_window_label_prefixes_session_id() {
 
 #[test]
 fn build_notes_url_uses_expected_micloud_notes_path() {
-    let url = build_notes_url("us.i.mi.com").expect("valid domain should produce URL");
+    let url = build_notes_url("us.i.mi.com", "en-US").expect("valid domain should produce URL");
 
     assert_eq!(url.as_str(), "https://us.i.mi.com/note/h5/?_locale=en-US");
 }
 
+#[test]
+fn build_notes_url_uses_configured_locale() {
+    let url = build_notes_url("i.mi.com", "zh-CN").expect("valid domain should produce URL");
+
+    assert_eq!(url.as_str(), "https://i.mi.com/note/h5/?_locale=zh-CN");
+}
+
+#[test]
+fn build_notes_url_falls_back_to_default_locale() {
+    let url = build_notes_url("i.mi.com", "  ").expect("valid domain should produce URL");
+
+    assert_eq!(url.as_str(), "https://i.mi.com/note/h5/?_locale=en-US");
+}
+
+#[test]
+fn build_notes_url_at_targets_injected_base_url() {
+    let server = MockMiCloud::start(Vec::new());
+    let url =
+        build_notes_url_at(&server.base_url(), "de-DE").expect("mock base should produce URL");
+
+    assert_eq!(
+        url.as_str(),
+        format!("{}note/h5/?_locale=de-DE", server.base_url())
+    );
+    let response = Client::new()
+        .get(url)
+        .send()
+        .expect("mock notes page should respond");
+    assert!(response.status().is_success());
+}
+
 #[test]
 fn build_notes_url_rejects_invalid_domain() {
-    let error = build_notes_url("bad domain").expect_err("invalid domain should fail URL parse");
+    let error =
+        build_notes_url("bad domain", "en-US").expect_err("invalid domain should fail URL parse");
 
     assert!(
         error
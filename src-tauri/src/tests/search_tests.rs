@@ -0,0 +1,160 @@
+use std::{fs, path::PathBuf};
+
+use crate::{
+    db::{
+        search::{replace_path_index, NoteIndexEntry},
+        sessions::init_db,
+    },
+    models::NoteSearchFilters,
+    services::{
+        search::{build_match_expression, reindex_export, search_notes},
+        timezone::OutputTimezone,
+    },
+    tests::TestDir,
+};
+
+fn note_section(title: &str, body: &str, created: &str) -> String {
+    format!("****\n## Title: {title}\n{body}\n\n*Created at: {created}*\n")
+}
+
+fn utc() -> OutputTimezone {
+    OutputTimezone::parse("UTC").expect("UTC should parse")
+}
+
+fn write_export(test_dir: &TestDir) -> PathBuf {
+    let export_dir = test_dir.path.join("exported_notes");
+    fs::create_dir_all(&export_dir).expect("export directory should be created");
+    fs::write(
+        export_dir.join("note_0001.md"),
+        note_section(
+            "Garden",
+            "Plant tomatoes near the fence",
+            "2021-04-10T09:00:00Z",
+        ),
+    )
+    .expect("first note should be written");
+    fs::write(
+        export_dir.join("note_0002.md"),
+        note_section(
+            "Shopping",
+            "Buy tomatoes, basil and garden gloves",
+            "2023-06-01T18:30:00Z",
+        ),
+    )
+    .expect("second note should be written");
+    export_dir
+}
+
+#[test]
+fn build_match_expression_quotes_terms_and_drops_quotes() {
+    assert_eq!(
+        build_match_expression("  tomato \"fence-post\" "),
+        Some("\"tomato\" \"fence-post\"".to_string())
+    );
+    assert_eq!(build_match_expression("   "), None);
+}
+
+#[test]
+fn reindex_and_search_return_ranked_snippets() {
+    let test_dir = TestDir::new("ranked");
+    let db_path = test_dir.path.join("sessions.db");
+    let db = init_db(&db_path).expect("test database should initialize");
+    let export_dir = write_export(&test_dir);
+
+    let report = reindex_export(&db, export_dir.to_string_lossy().as_ref(), &utc())
+        .expect("reindex should succeed");
+    assert_eq!(report.files_count, 2);
+    assert_eq!(report.notes_count, 2);
+
+    let results = search_notes(&db, "garden", &NoteSearchFilters::default(), &utc())
+        .expect("search should succeed");
+
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[0].title, "Garden");
+    assert!(results[0].file_path.ends_with("note_0001.md"));
+    assert_eq!(results[0].creation_date, "2021-04-10T09:00:00+00:00");
+    assert!(results[1].snippet.contains("**garden**"));
+}
+
+#[test]
+fn reindex_replaces_previous_entries_for_the_same_folder() {
+    let test_dir = TestDir::new("replace");
+    let db_path = test_dir.path.join("sessions.db");
+    let db = init_db(&db_path).expect("test database should initialize");
+    let export_dir = write_export(&test_dir);
+    let source = export_dir.to_string_lossy().to_string();
+
+    reindex_export(&db, &source, &utc()).expect("first reindex should succeed");
+    reindex_export(&db, &source, &utc()).expect("second reindex should succeed");
+
+    let results = search_notes(&db, "tomatoes", &NoteSearchFilters::default(), &utc())
+        .expect("search should succeed");
+    assert_eq!(results.len(), 2);
+}
+
+#[test]
+fn search_applies_date_and_session_filters() {
+    let test_dir = TestDir::new("filters");
+    let db_path = test_dir.path.join("sessions.db");
+    let db = init_db(&db_path).expect("test database should initialize");
+    let export_dir = write_export(&test_dir);
+    reindex_export(&db, export_dir.to_string_lossy().as_ref(), &utc())
+        .expect("reindex should succeed");
+    replace_path_index(
+        &db,
+        "other.md",
+        &[NoteIndexEntry {
+            session_id: Some("session-1".to_string()),
+            file_path: "other.md".to_string(),
+            title: "Tomatoes".to_string(),
+            content: "Session scoped tomatoes".to_string(),
+            creation_date: "2024-01-01T00:00:00Z".to_string(),
+        }],
+    )
+    .expect("note should be indexed");
+
+    let after_2022 = search_notes(
+        &db,
+        "tomatoes",
+        &NoteSearchFilters {
+            created_after: Some("2022-01-01".to_string()),
+            ..NoteSearchFilters::default()
+        },
+        &utc(),
+    )
+    .expect("filtered search should succeed");
+    assert_eq!(after_2022.len(), 2);
+
+    let in_session = search_notes(
+        &db,
+        "tomatoes",
+        &NoteSearchFilters {
+            session_id: Some("session-1".to_string()),
+            ..NoteSearchFilters::default()
+        },
+        &utc(),
+    )
+    .expect("session search should succeed");
+    assert_eq!(in_session.len(), 1);
+    assert_eq!(in_session[0].session_id.as_deref(), Some("session-1"));
+}
+
+#[test]
+fn search_rejects_empty_query_and_bad_dates() {
+    let test_dir = TestDir::new("invalid");
+    let db_path = test_dir.path.join("sessions.db");
+    let db = init_db(&db_path).expect("test database should initialize");
+
+    assert!(search_notes(&db, " ", &NoteSearchFilters::default(), &utc()).is_err());
+    let error = search_notes(
+        &db,
+        "tomatoes",
+        &NoteSearchFilters {
+            created_before: Some("last week".to_string()),
+            ..NoteSearchFilters::default()
+        },
+        &utc(),
+    )
+    .expect_err("invalid date filter should be rejected");
+    assert!(error.to_string().contains("last week"));
+}
@@ -0,0 +1,325 @@
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    db::{
+        connection::Database,
+        search::NoteIndexEntry,
+        sessions::{
+            fetch_session_by_id, fetch_sessions, init_db, insert_session, update_session_metadata,
+            update_session_progress,
+        },
+    },
+    models::{Session, SessionFilters, SessionMetadata, SessionSortKey, SortDirection},
+    state::PROGRESS_BATCH_SIZE,
+    tests::{session_fixture, TestDir},
+};
+
+fn session(id: &str, started_at: &str, status: &str, notes_count: u32) -> Session {
+    Session {
+        started_at: started_at.to_string(),
+        status: status.to_string(),
+        notes_count,
+        images_enabled: true,
+        output_path: format!("/exports/{id}.md"),
+        ..session_fixture(id)
+    }
+}
+
+fn seeded_db(test_dir: &TestDir) -> Database {
+    let db_path = test_dir.path.join("sessions.db");
+    let db = init_db(&db_path).expect("test database should initialize");
+
+    let mut failed = session("failed", "2026-01-03T08:00:00+00:00", "error", 2);
+    failed.error_message = Some("Login window closed_early 100%".to_string());
+    let mut split = session("split", "2026-01-02T08:00:00+00:00", "completed", 30);
+    split.split_mode = true;
+    split.domain = "i.mi.com".to_string();
+    split.output_path = "/exports/team/exported_notes".to_string();
+
+    for session in [
+        session("old", "2025-12-31T23:00:00+00:00", "completed", 10),
+        split,
+        failed,
+    ] {
+        insert_session(&db, &session).expect("session should be inserted");
+    }
+    db
+}
+
+fn ids(filters: &SessionFilters, db: &Database) -> Vec<String> {
+    fetch_sessions(db, 1, 50, filters)
+        .expect("sessions should load")
+        .sessions
+        .into_iter()
+        .map(|session| session.id)
+        .collect()
+}
+
+#[test]
+fn fetch_sessions_defaults_to_newest_first_with_total() {
+    let test_dir = TestDir::new("defaults");
+    let db = seeded_db(&test_dir);
+
+    let page = fetch_sessions(&db, 1, 2, &SessionFilters::default()).expect("sessions should load");
+
+    assert_eq!(page.total, 3);
+    assert_eq!(page.per_page, 2);
+    assert_eq!(
+        page.sessions
+            .iter()
+            .map(|session| session.id.as_str())
+            .collect::<Vec<_>>(),
+        ["failed", "split"]
+    );
+}
+
+#[test]
+fn fetch_sessions_filters_by_status_domain_mode_and_dates() {
+    let test_dir = TestDir::new("filters");
+    let db = seeded_db(&test_dir);
+
+    let by_status = SessionFilters {
+        status: Some("completed".to_string()),
+        ..SessionFilters::default()
+    };
+    assert_eq!(ids(&by_status, &db), ["split", "old"]);
+
+    let by_domain = SessionFilters {
+        domain: Some("I.MI.COM".to_string()),
+        split_mode: Some(true),
+        ..SessionFilters::default()
+    };
+    assert_eq!(ids(&by_domain, &db), ["split"]);
+
+    let by_dates = SessionFilters {
+        started_after: Some("2026-01-01".to_string()),
+        started_before: Some("2026-01-03".to_string()),
+        ..SessionFilters::default()
+    };
+    assert_eq!(ids(&by_dates, &db), ["split"]);
+
+    let mut midnight = session("midnight", "2026-01-02T00:00:00.250+00:00", "completed", 1);
+    midnight.output_path = "/exports/midnight.md".to_string();
+    insert_session(&db, &midnight).expect("session should be inserted");
+    let from_midnight = SessionFilters {
+        started_after: Some("2026-01-02T00:00:00Z".to_string()),
+        started_before: Some("2026-01-02T09:00:00+01:00".to_string()),
+        ..SessionFilters::default()
+    };
+    assert_eq!(ids(&from_midnight, &db), ["midnight"]);
+}
+
+#[test]
+fn fetch_sessions_searches_paths_and_errors_literally() {
+    let test_dir = TestDir::new("search");
+    let db = seeded_db(&test_dir);
+
+    let by_path = SessionFilters {
+        search: Some("team/".to_string()),
+        ..SessionFilters::default()
+    };
+    assert_eq!(ids(&by_path, &db), ["split"]);
+
+    let by_error = SessionFilters {
+        search: Some("closed_early 100%".to_string()),
+        ..SessionFilters::default()
+    };
+    assert_eq!(ids(&by_error, &db), ["failed"]);
+
+    let wildcard_only = SessionFilters {
+        search: Some("%".to_string()),
+        ..SessionFilters::default()
+    };
+    assert_eq!(ids(&wildcard_only, &db), ["failed"]);
+}
+
+#[test]
+fn fetch_sessions_sorts_by_requested_key() {
+    let test_dir = TestDir::new("sort");
+    let db = seeded_db(&test_dir);
+
+    let filters = SessionFilters {
+        sort_by: SessionSortKey::NotesCount,
+        sort_direction: SortDirection::Asc,
+        ..SessionFilters::default()
+    };
+
+    assert_eq!(ids(&filters, &db), ["failed", "old", "split"]);
+}
+
+#[test]
+fn fetch_sessions_rejects_invalid_date_filter() {
+    let test_dir = TestDir::new("invalid_date");
+    let db = seeded_db(&test_dir);
+
+    let filters = SessionFilters {
+        started_after: Some("yesterday".to_string()),
+        ..SessionFilters::default()
+    };
+
+    assert!(fetch_sessions(&db, 1, 50, &filters).is_err());
+}
+
+#[test]
+fn update_session_metadata_is_stored_and_filterable() {
+    let test_dir = TestDir::new("metadata");
+    let db = seeded_db(&test_dir);
+
+    let updated = update_session_metadata(
+        &db,
+        "old",
+        &SessionMetadata {
+            label: Some("  Mom's phone ".to_string()),
+            comment: Some("Before the factory reset".to_string()),
+            account_hint: Some("mom@example.com".to_string()),
+            pinned: Some(true),
+        },
+    )
+    .expect("metadata should update")
+    .expect("session should exist");
+    assert_eq!(updated.label.as_deref(), Some("Mom's phone"));
+    assert!(updated.pinned);
+
+    let by_label = SessionFilters {
+        label: Some("mom's PHONE".to_string()),
+        ..SessionFilters::default()
+    };
+    assert_eq!(ids(&by_label, &db), ["old"]);
+
+    let by_comment = SessionFilters {
+        search: Some("factory reset".to_string()),
+        ..SessionFilters::default()
+    };
+    assert_eq!(ids(&by_comment, &db), ["old"]);
+
+    let unpinned = SessionFilters {
+        pinned: Some(false),
+        ..SessionFilters::default()
+    };
+    assert_eq!(ids(&unpinned, &db), ["failed", "split"]);
+
+    let cleared = update_session_metadata(&db, "old", &SessionMetadata::default())
+        .expect("metadata should clear")
+        .expect("session should exist");
+    assert_eq!(cleared.label, None);
+    assert_eq!(cleared.account_hint, None);
+    assert!(cleared.pinned);
+
+    let relabeled = update_session_metadata(
+        &db,
+        "old",
+        &SessionMetadata {
+            label: Some("Archive".to_string()),
+            ..SessionMetadata::default()
+        },
+    )
+    .expect("label should update")
+    .expect("session should exist");
+    assert_eq!(relabeled.label.as_deref(), Some("Archive"));
+    assert!(relabeled.pinned, "saving a label should keep the pin");
+
+    let unpinned_again = update_session_metadata(
+        &db,
+        "old",
+        &SessionMetadata {
+            pinned: Some(false),
+            ..SessionMetadata::default()
+        },
+    )
+    .expect("pin should update")
+    .expect("session should exist");
+    assert!(!unpinned_again.pinned);
+
+    assert!(
+        update_session_metadata(&db, "unknown", &SessionMetadata::default())
+            .expect("update should succeed")
+            .is_none()
+    );
+}
+
+#[test]
+fn batched_progress_handles_5000_note_export_with_concurrent_reads() {
+    let test_dir = TestDir::new("benchmark");
+    let db_path = test_dir.path.join("sessions.db");
+    let db = init_db(&db_path).expect("test database should initialize");
+    insert_session(
+        &db,
+        &session("bench", "2026-01-01T00:00:00+00:00", "running", 0),
+    )
+    .expect("session should be inserted");
+
+    let reader = Database::open(&db_path).expect("second connection should open");
+    let done = Arc::new(AtomicBool::new(false));
+    let reader_done = Arc::clone(&done);
+    let reader_thread = thread::spawn(move || {
+        let mut reads = 0_u32;
+        while !reader_done.load(Ordering::Relaxed) {
+            fetch_sessions(&reader, 1, 50, &SessionFilters::default())
+                .expect("concurrent read should not fail");
+            reads += 1;
+        }
+        reads
+    });
+
+    let started = Instant::now();
+    let mut pending = Vec::new();
+    for index in 1..=5_000_u32 {
+        pending.push(NoteIndexEntry {
+            session_id: Some("bench".to_string()),
+            file_path: format!("/exports/bench/note_{index:04}.md"),
+            title: format!("Note {index}"),
+            content: format!("Synthetic note body number {index} with some searchable text."),
+            creation_date: "2026-01-01T00:00:00Z".to_string(),
+        });
+        if pending.len() >= PROGRESS_BATCH_SIZE || index == 5_000 {
+            update_session_progress(&db, "bench", index, 0, 0, 0, &pending)
+                .expect("progress batch should be written");
+            pending.clear();
+        }
+    }
+    let elapsed = started.elapsed();
+    done.store(true, Ordering::Relaxed);
+    let reads = reader_thread.join().expect("reader thread should finish");
+
+    assert!(
+        elapsed < Duration::from_secs(30),
+        "5000-note synthetic export took {elapsed:?}"
+    );
+    assert!(reads > 0, "sessions should be readable during the export");
+
+    let session = fetch_session_by_id(&db, "bench")
+        .expect("session should load")
+        .expect("session should exist");
+    assert_eq!(session.notes_count, 5_000);
+    let indexed = db
+        .connection()
+        .expect("connection should lock")
+        .query_row(
+            "SELECT COUNT(*) FROM note_search WHERE session_id = 'bench'",
+            [],
+            |row| row.get::<_, i64>(0),
+        )
+        .expect("index rows should be counted");
+    assert_eq!(indexed, 5_000);
+}
+
+#[test]
+fn database_uses_wal_journal_mode() {
+    let test_dir = TestDir::new("wal");
+    let db = init_db(&test_dir.path.join("sessions.db")).expect("test database should initialize");
+
+    let journal_mode = db
+        .connection()
+        .expect("connection should lock")
+        .query_row("PRAGMA journal_mode", [], |row| row.get::<_, String>(0))
+        .expect("journal mode should be readable");
+
+    assert_eq!(journal_mode.to_ascii_lowercase(), "wal");
+}
@@ -0,0 +1,139 @@
+use std::{fs, path::PathBuf};
+
+use crate::{
+    models::{ExportJobSettings, ExportProfile},
+    services::{
+        export::{ExportOptions, DEFAULT_CREATED_DATE_FORMAT, DEFAULT_TIMESTAMP_FORMAT},
+        settings::{
+            bootstrap_settings, delete_export_profile, find_export_profile, load_settings,
+            save_export_profile,
+        },
+    },
+    tests::TestDir,
+};
+
+fn settings_path(test_dir: &TestDir) -> PathBuf {
+    let settings_path = test_dir.path.join("settings.json");
+    if !settings_path.exists() {
+        bootstrap_settings(&settings_path, &test_dir.path).expect("settings should bootstrap");
+    }
+    settings_path
+}
+
+fn profile(name: &str) -> ExportProfile {
+    ExportProfile {
+        id: String::new(),
+        name: name.to_string(),
+        settings: ExportJobSettings {
+            domain: "i.mi.com".to_string(),
+            output_dir: String::new(),
+            split: true,
+            name_by_title: true,
+            timestamp_format: DEFAULT_TIMESTAMP_FORMAT.to_string(),
+            created_date_format: DEFAULT_CREATED_DATE_FORMAT.to_string(),
+            export_images: false,
+            skip_duplicates: true,
+            unparsed_date_policy: Default::default(),
+            source: Default::default(),
+            filter: Default::default(),
+            dry_run: false,
+        },
+    }
+}
+
+#[test]
+fn export_profiles_are_saved_found_updated_and_deleted() {
+    let test_dir = TestDir::new("profiles");
+    let settings_path = settings_path(&test_dir);
+
+    let saved = save_export_profile(&settings_path, &profile("  Obsidian weekly "))
+        .expect("profile should be saved");
+    assert!(!saved.id.is_empty());
+    assert_eq!(saved.name, "Obsidian weekly");
+    save_export_profile(&settings_path, &profile("Archive ZIP")).expect("profile should be saved");
+
+    let mut renamed = saved.clone();
+    renamed.settings.split = false;
+    save_export_profile(&settings_path, &renamed).expect("profile should be updated");
+
+    let settings = load_settings(&settings_path).expect("settings should load");
+    assert_eq!(settings.export_profiles.len(), 2);
+    let found = find_export_profile(&settings, &saved.id).expect("profile should be found by id");
+    assert!(!found.settings.split);
+    assert_eq!(
+        find_export_profile(&settings, "archive zip")
+            .expect("profile should be found by name")
+            .name,
+        "Archive ZIP"
+    );
+
+    let options = ExportOptions::from_job(&found.settings, &settings);
+    assert_eq!(options.domain, "i.mi.com");
+    assert_eq!(
+        options.output_dir,
+        PathBuf::from(&settings.default_export_dir)
+    );
+
+    let settings =
+        delete_export_profile(&settings_path, &saved.id).expect("profile should be deleted");
+    assert_eq!(settings.export_profiles.len(), 1);
+    assert!(find_export_profile(&settings, &saved.id).is_err());
+    assert!(delete_export_profile(&settings_path, &saved.id).is_err());
+}
+
+#[test]
+fn export_profiles_with_invalid_options_are_rejected_at_save() {
+    let test_dir = TestDir::new("invalid");
+    let settings_path = settings_path(&test_dir);
+    save_export_profile(&settings_path, &profile("Weekly")).expect("profile should be saved");
+
+    let mut timestamp = profile("Timestamp");
+    timestamp.settings.timestamp_format = "weekly".to_string();
+    let mut created = profile("Created");
+    created.settings.created_date_format = "dd/MM/yyyy 100%".to_string();
+    let mut domain = profile("Domain");
+    domain.settings.domain = "not a domain".to_string();
+    let mut filter = profile("Filter");
+    filter.settings.filter.title_regex = Some("(".to_string());
+
+    for invalid in [
+        profile(" "),
+        profile("weekly"),
+        timestamp,
+        created,
+        domain,
+        filter,
+    ] {
+        assert!(
+            save_export_profile(&settings_path, &invalid).is_err(),
+            "profile '{}' should be rejected",
+            invalid.name
+        );
+    }
+    let settings = load_settings(&settings_path).expect("settings should load");
+    assert_eq!(settings.export_profiles.len(), 1);
+}
+
+#[test]
+fn bootstrap_drops_stored_profiles_that_no_longer_validate() {
+    let test_dir = TestDir::new("bootstrap");
+    let settings_path = test_dir.path.join("settings.json");
+    let mut broken = profile("Broken");
+    broken.id = "broken".to_string();
+    broken.settings.timestamp_format = "none".to_string();
+    let mut kept = profile("Kept");
+    kept.id = "kept".to_string();
+    fs::write(
+        &settings_path,
+        serde_json::json!({
+            "defaultExportDir": test_dir.path.join("out"),
+            "exportProfiles": [broken, kept],
+        })
+        .to_string(),
+    )
+    .expect("settings should be written");
+
+    let settings =
+        bootstrap_settings(&settings_path, &test_dir.path).expect("settings should bootstrap");
+    assert_eq!(settings.export_profiles, [kept]);
+}
@@ -0,0 +1,97 @@
+use std::fs;
+
+use crate::{
+    models::Session,
+    services::statistics::compute_export_statistics,
+    tests::{session_fixture, TestDir},
+};
+
+fn session(id: &str, status: &str, notes_count: u32, minutes: Option<i64>) -> Session {
+    Session {
+        completed_at: minutes.map(|minutes| format!("2026-01-01T10:{minutes:02}:00+00:00")),
+        status: status.to_string(),
+        notes_count,
+        images_count: notes_count / 2,
+        images_enabled: true,
+        ..session_fixture(id)
+    }
+}
+
+#[test]
+fn compute_export_statistics_reports_totals_and_throughput() {
+    let sessions = vec![
+        session("a", "completed", 20, Some(10)),
+        session("b", "completed", 40, Some(10)),
+        session("c", "running", 5, None),
+    ];
+
+    let stats = compute_export_statistics(&sessions);
+
+    assert_eq!(stats.total_sessions, 3);
+    assert_eq!(stats.total_notes, 65);
+    assert_eq!(stats.total_images, 32);
+    assert_eq!(stats.sessions[0].duration_ms, Some(600_000));
+    assert_eq!(stats.sessions[0].notes_per_minute, Some(2.0));
+    assert_eq!(stats.average_notes_per_minute, Some(3.0));
+    assert!(!stats.sessions[2].output_exists);
+}
+
+#[test]
+fn compute_export_statistics_ranks_failures_and_errors() {
+    let mut failed_login = session("a", "error", 0, Some(1));
+    failed_login.error_message = Some("No notes found for export.".to_string());
+    let mut failed_again = session("b", "error", 0, Some(1));
+    failed_again.error_message = Some(" No notes found for export. ".to_string());
+    let mut timeout = session("c", "error", 3, Some(2));
+    timeout.error_message = Some("Timed out".to_string());
+    let sessions = vec![
+        failed_login,
+        failed_again,
+        timeout,
+        session("d", "completed", 10, Some(5)),
+        session("e", "running", 1, None),
+    ];
+
+    let stats = compute_export_statistics(&sessions);
+
+    assert_eq!(stats.failure_rate, 0.75);
+    assert_eq!(stats.common_errors[0].message, "No notes found for export.");
+    assert_eq!(stats.common_errors[0].count, 2);
+    assert_eq!(stats.common_errors[1].count, 1);
+    let error_status = stats
+        .status_counts
+        .iter()
+        .find(|count| count.status == "error")
+        .expect("error status should be counted");
+    assert_eq!(error_status.count, 3);
+    assert_eq!(error_status.rate, 0.6);
+}
+
+#[test]
+fn compute_export_statistics_measures_disk_usage_with_images() {
+    let test_dir = TestDir::new("disk_usage");
+    let output_path = test_dir.path.join("exported_notes.md");
+    fs::write(&output_path, "0123456789").expect("export file should be written");
+    let images_dir = test_dir.path.join("images_1");
+    fs::create_dir_all(&images_dir).expect("images dir should be created");
+    fs::write(images_dir.join("a.png"), [0_u8; 32]).expect("image should be written");
+
+    let mut single_file = session("a", "completed", 1, Some(1));
+    single_file.output_path = output_path.to_string_lossy().to_string();
+    single_file.images_dir_name = Some("images_1".to_string());
+
+    let stats = compute_export_statistics(&[single_file]);
+
+    assert!(stats.sessions[0].output_exists);
+    assert_eq!(stats.sessions[0].disk_usage_bytes, 42);
+    assert_eq!(stats.total_disk_usage_bytes, 42);
+}
+
+#[test]
+fn compute_export_statistics_handles_empty_history() {
+    let stats = compute_export_statistics(&[]);
+
+    assert_eq!(stats.total_sessions, 0);
+    assert_eq!(stats.failure_rate, 0.0);
+    assert_eq!(stats.average_notes_per_minute, None);
+}
@@ -0,0 +1,93 @@
+use chrono::{DateTime, Offset, TimeZone, Utc};
+
+use crate::services::{
+    date_locale::ENGLISH,
+    timezone::{normalize_output_timezone, OutputTimezone},
+};
+
+fn berlin() -> OutputTimezone {
+    OutputTimezone::parse("Europe/Berlin").expect("Europe/Berlin should be a known timezone")
+}
+
+#[test]
+fn parse_accepts_local_utc_and_iana_names() {
+    assert_eq!(OutputTimezone::parse("").unwrap(), OutputTimezone::Local);
+    assert_eq!(
+        OutputTimezone::parse("Local").unwrap(),
+        OutputTimezone::Local
+    );
+    assert_eq!(OutputTimezone::parse("utc").unwrap().name(), "UTC");
+    assert_eq!(berlin().name(), "Europe/Berlin");
+}
+
+#[test]
+fn parse_rejects_unknown_timezone_names() {
+    let error = OutputTimezone::parse("Mars/Olympus").expect_err("unknown zone should fail");
+
+    assert!(error.to_string().contains("Mars/Olympus"));
+}
+
+#[test]
+fn normalize_output_timezone_falls_back_to_local() {
+    assert_eq!(normalize_output_timezone(" utc "), "UTC");
+    assert_eq!(normalize_output_timezone("Mars/Olympus"), "local");
+}
+
+#[test]
+fn from_unix_timestamp_follows_dst_offsets() {
+    let timezone = berlin();
+    let before = Utc
+        .with_ymd_and_hms(2026, 3, 29, 0, 59, 0)
+        .unwrap()
+        .timestamp_millis();
+    let after = Utc
+        .with_ymd_and_hms(2026, 3, 29, 1, 0, 0)
+        .unwrap()
+        .timestamp_millis();
+
+    let before = timezone.datetime_from_unix_timestamp(before).unwrap();
+    let after = timezone.datetime_from_unix_timestamp(after).unwrap();
+
+    assert_eq!(before.to_rfc3339(), "2026-03-29T01:59:00+01:00");
+    assert_eq!(after.to_rfc3339(), "2026-03-29T03:00:00+02:00");
+}
+
+#[test]
+fn parse_created_date_moves_times_inside_dst_gap_forward() {
+    let parsed = berlin()
+        .parse_created_date("29/03/2026 02:30", &ENGLISH)
+        .expect("time inside the spring-forward gap should still parse");
+
+    assert_eq!(parsed.to_rfc3339(), "2026-03-29T03:30:00+02:00");
+}
+
+#[test]
+fn parse_created_date_picks_earliest_ambiguous_time() {
+    let parsed = berlin()
+        .parse_created_date("25/10/2026 02:30", &ENGLISH)
+        .expect("time inside the fall-back overlap should parse");
+
+    assert_eq!(parsed.to_rfc3339(), "2026-10-25T02:30:00+02:00");
+}
+
+#[test]
+fn same_instant_renders_identically_for_named_timezone() {
+    let instant = DateTime::parse_from_rfc3339("2026-07-01T12:00:00-07:00").unwrap();
+    let other_offset = DateTime::parse_from_rfc3339("2026-07-01T21:00:00+02:00").unwrap();
+
+    assert_eq!(
+        berlin().format_rfc3339(&instant),
+        berlin().format_rfc3339(&other_offset)
+    );
+    assert_eq!(
+        berlin().format_rfc3339(&instant),
+        "2026-07-01T21:00:00+02:00"
+    );
+}
+
+#[test]
+fn utc_timezone_has_zero_offset() {
+    let timezone = OutputTimezone::parse("UTC").unwrap();
+
+    assert_eq!(timezone.now().offset().fix().local_minus_utc(), 0);
+}
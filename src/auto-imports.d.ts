@@ -9,6 +9,7 @@ declare global {
   const EffectScope: typeof import('vue').EffectScope
   const RELEASES_LATEST_URL: typeof import('./lib/update').RELEASES_LATEST_URL
   const acceptHMRUpdate: typeof import('pinia').acceptHMRUpdate
+  const applyRetentionPolicy: typeof import('./lib/api').applyRetentionPolicy
   const cancelExport: typeof import('./lib/api').cancelExport
   const checkLatestReleaseVersion: typeof import('./lib/api').checkLatestReleaseVersion
   const closeSplashscreen: typeof import('./lib/api').closeSplashscreen
@@ -22,18 +23,26 @@ declare global {
   const defineAsyncComponent: typeof import('vue').defineAsyncComponent
   const defineComponent: typeof import('vue').defineComponent
   const defineStore: typeof import('pinia').defineStore
+  const deleteExportProfile: typeof import('./lib/api').deleteExportProfile
   const deleteSession: typeof import('./lib/api').deleteSession
+  const diffExports: typeof import('./lib/api').diffExports
   const dirname: typeof import('./lib/image').dirname
   const effectScope: typeof import('vue').effectScope
+  const enqueueExport: typeof import('./lib/api').enqueueExport
+  const exportAppData: typeof import('./lib/api').exportAppData
+  const findDuplicateNotes: typeof import('./lib/api').findDuplicateNotes
   const firstLetterToUpperCase: typeof import('./lib/utils').firstLetterToUpperCase
   const getActivePinia: typeof import('pinia').getActivePinia
   const getAppSettings: typeof import('./lib/api').getAppSettings
   const getCurrentInstance: typeof import('vue').getCurrentInstance
   const getCurrentScope: typeof import('vue').getCurrentScope
   const getCurrentWatcher: typeof import('vue').getCurrentWatcher
+  const getExportQueue: typeof import('./lib/api').getExportQueue
+  const getExportStatistics: typeof import('./lib/api').getExportStatistics
   const getSession: typeof import('./lib/api').getSession
   const getSessions: typeof import('./lib/api').getSessions
   const h: typeof import('vue').h
+  const importAppData: typeof import('./lib/api').importAppData
   const inject: typeof import('vue').inject
   const isProxy: typeof import('vue').isProxy
   const isReactive: typeof import('vue').isReactive
@@ -48,6 +57,7 @@ declare global {
   const mapStores: typeof import('pinia').mapStores
   const mapWritableState: typeof import('pinia').mapWritableState
   const markRaw: typeof import('vue').markRaw
+  const moveQueuedExport: typeof import('./lib/api').moveQueuedExport
   const nextTick: typeof import('vue').nextTick
   const normalizeToForwardSlashes: typeof import('./lib/image').normalizeToForwardSlashes
   const normalizeVersion: typeof import('./lib/update').normalizeVersion
@@ -68,19 +78,30 @@ declare global {
   const onUpdated: typeof import('vue').onUpdated
   const onWatcherCleanup: typeof import('vue').onWatcherCleanup
   const openInExplorer: typeof import('./lib/api').openInExplorer
+  const pauseExport: typeof import('./lib/api').pauseExport
   const provide: typeof import('vue').provide
   const reactive: typeof import('vue').reactive
   const readExportFile: typeof import('./lib/api').readExportFile
   const readonly: typeof import('vue').readonly
   const ref: typeof import('vue').ref
+  const reindexNotes: typeof import('./lib/api').reindexNotes
+  const relocateSessions: typeof import('./lib/api').relocateSessions
+  const removeQueuedExport: typeof import('./lib/api').removeQueuedExport
   const resolveComponent: typeof import('vue').resolveComponent
   const resolveRelativePath: typeof import('./lib/image').resolveRelativePath
+  const resumeExportRun: typeof import('./lib/api').resumeExportRun
+  const saveExportProfile: typeof import('./lib/api').saveExportProfile
+  const searchNotes: typeof import('./lib/api').searchNotes
   const setActivePinia: typeof import('pinia').setActivePinia
   const setMapStoreSuffix: typeof import('pinia').setMapStoreSuffix
+  const setSessionPinned: typeof import('./lib/api').setSessionPinned
   const shallowReactive: typeof import('vue').shallowReactive
   const shallowReadonly: typeof import('vue').shallowReadonly
   const shallowRef: typeof import('vue').shallowRef
   const startExport: typeof import('./lib/api').startExport
+  const startExportQueue: typeof import('./lib/api').startExportQueue
+  const startExportWithProfile: typeof import('./lib/api').startExportWithProfile
+  const stopExportQueue: typeof import('./lib/api').stopExportQueue
   const storeToRefs: typeof import('pinia').storeToRefs
   const toNativeFilePath: typeof import('./lib/image').toNativeFilePath
   const toRaw: typeof import('vue').toRaw
@@ -90,6 +111,7 @@ declare global {
   const triggerRef: typeof import('vue').triggerRef
   const unref: typeof import('vue').unref
   const updateAppSettings: typeof import('./lib/api').updateAppSettings
+  const updateSessionMetadata: typeof import('./lib/api').updateSessionMetadata
   const useAttrs: typeof import('vue').useAttrs
   const useCssModule: typeof import('vue').useCssModule
   const useCssVars: typeof import('vue').useCssVars
@@ -97,6 +119,7 @@ declare global {
   const useId: typeof import('vue').useId
   const useLink: typeof import('vue-router').useLink
   const useModel: typeof import('vue').useModel
+  const useQueueStore: typeof import('./stores/queue').useQueueStore
   const useRoute: typeof import('vue-router').useRoute
   const useRouter: typeof import('vue-router').useRouter
   const useSessionsStore: typeof import('./stores/sessions').useSessionsStore
@@ -105,6 +128,7 @@ declare global {
   const useTemplateRef: typeof import('vue').useTemplateRef
   const useUpdateStore: typeof import('./stores/update').useUpdateStore
   const useYupForm: typeof import('./composables/use-yup-form').useYupForm
+  const verifySession: typeof import('./lib/api').verifySession
   const watch: typeof import('vue').watch
   const watchEffect: typeof import('vue').watchEffect
   const watchPostEffect: typeof import('vue').watchPostEffect
@@ -126,6 +150,7 @@ declare module 'vue' {
     readonly EffectScope: UnwrapRef<typeof import('vue')['EffectScope']>
     readonly RELEASES_LATEST_URL: UnwrapRef<typeof import('./lib/update')['RELEASES_LATEST_URL']>
     readonly acceptHMRUpdate: UnwrapRef<typeof import('pinia')['acceptHMRUpdate']>
+    readonly applyRetentionPolicy: UnwrapRef<typeof import('./lib/api')['applyRetentionPolicy']>
     readonly cancelExport: UnwrapRef<typeof import('./lib/api')['cancelExport']>
     readonly checkLatestReleaseVersion: UnwrapRef<typeof import('./lib/api')['checkLatestReleaseVersion']>
     readonly closeSplashscreen: UnwrapRef<typeof import('./lib/api')['closeSplashscreen']>
@@ -139,18 +164,26 @@ declare module 'vue' {
     readonly defineAsyncComponent: UnwrapRef<typeof import('vue')['defineAsyncComponent']>
     readonly defineComponent: UnwrapRef<typeof import('vue')['defineComponent']>
     readonly defineStore: UnwrapRef<typeof import('pinia')['defineStore']>
+    readonly deleteExportProfile: UnwrapRef<typeof import('./lib/api')['deleteExportProfile']>
     readonly deleteSession: UnwrapRef<typeof import('./lib/api')['deleteSession']>
+    readonly diffExports: UnwrapRef<typeof import('./lib/api')['diffExports']>
     readonly dirname: UnwrapRef<typeof import('./lib/image')['dirname']>
     readonly effectScope: UnwrapRef<typeof import('vue')['effectScope']>
+    readonly enqueueExport: UnwrapRef<typeof import('./lib/api')['enqueueExport']>
+    readonly exportAppData: UnwrapRef<typeof import('./lib/api')['exportAppData']>
+    readonly findDuplicateNotes: UnwrapRef<typeof import('./lib/api')['findDuplicateNotes']>
     readonly firstLetterToUpperCase: UnwrapRef<typeof import('./lib/utils')['firstLetterToUpperCase']>
     readonly getActivePinia: UnwrapRef<typeof import('pinia')['getActivePinia']>
     readonly getAppSettings: UnwrapRef<typeof import('./lib/api')['getAppSettings']>
     readonly getCurrentInstance: UnwrapRef<typeof import('vue')['getCurrentInstance']>
     readonly getCurrentScope: UnwrapRef<typeof import('vue')['getCurrentScope']>
     readonly getCurrentWatcher: UnwrapRef<typeof import('vue')['getCurrentWatcher']>
+    readonly getExportQueue: UnwrapRef<typeof import('./lib/api')['getExportQueue']>
+    readonly getExportStatistics: UnwrapRef<typeof import('./lib/api')['getExportStatistics']>
     readonly getSession: UnwrapRef<typeof import('./lib/api')['getSession']>
     readonly getSessions: UnwrapRef<typeof import('./lib/api')['getSessions']>
     readonly h: UnwrapRef<typeof import('vue')['h']>
+    readonly importAppData: UnwrapRef<typeof import('./lib/api')['importAppData']>
     readonly inject: UnwrapRef<typeof import('vue')['inject']>
     readonly isProxy: UnwrapRef<typeof import('vue')['isProxy']>
     readonly isReactive: UnwrapRef<typeof import('vue')['isReactive']>
@@ -165,6 +198,7 @@ declare module 'vue' {
     readonly mapStores: UnwrapRef<typeof import('pinia')['mapStores']>
     readonly mapWritableState: UnwrapRef<typeof import('pinia')['mapWritableState']>
     readonly markRaw: UnwrapRef<typeof import('vue')['markRaw']>
+    readonly moveQueuedExport: UnwrapRef<typeof import('./lib/api')['moveQueuedExport']>
     readonly nextTick: UnwrapRef<typeof import('vue')['nextTick']>
     readonly normalizeToForwardSlashes: UnwrapRef<typeof import('./lib/image')['normalizeToForwardSlashes']>
     readonly normalizeVersion: UnwrapRef<typeof import('./lib/update')['normalizeVersion']>
@@ -185,19 +219,30 @@ declare module 'vue' {
     readonly onUpdated: UnwrapRef<typeof import('vue')['onUpdated']>
     readonly onWatcherCleanup: UnwrapRef<typeof import('vue')['onWatcherCleanup']>
     readonly openInExplorer: UnwrapRef<typeof import('./lib/api')['openInExplorer']>
+    readonly pauseExport: UnwrapRef<typeof import('./lib/api')['pauseExport']>
     readonly provide: UnwrapRef<typeof import('vue')['provide']>
     readonly reactive: UnwrapRef<typeof import('vue')['reactive']>
     readonly readExportFile: UnwrapRef<typeof import('./lib/api')['readExportFile']>
     readonly readonly: UnwrapRef<typeof import('vue')['readonly']>
     readonly ref: UnwrapRef<typeof import('vue')['ref']>
+    readonly reindexNotes: UnwrapRef<typeof import('./lib/api')['reindexNotes']>
+    readonly relocateSessions: UnwrapRef<typeof import('./lib/api')['relocateSessions']>
+    readonly removeQueuedExport: UnwrapRef<typeof import('./lib/api')['removeQueuedExport']>
     readonly resolveComponent: UnwrapRef<typeof import('vue')['resolveComponent']>
     readonly resolveRelativePath: UnwrapRef<typeof import('./lib/image')['resolveRelativePath']>
+    readonly resumeExportRun: UnwrapRef<typeof import('./lib/api')['resumeExportRun']>
+    readonly saveExportProfile: UnwrapRef<typeof import('./lib/api')['saveExportProfile']>
+    readonly searchNotes: UnwrapRef<typeof import('./lib/api')['searchNotes']>
     readonly setActivePinia: UnwrapRef<typeof import('pinia')['setActivePinia']>
     readonly setMapStoreSuffix: UnwrapRef<typeof import('pinia')['setMapStoreSuffix']>
+    readonly setSessionPinned: UnwrapRef<typeof import('./lib/api')['setSessionPinned']>
     readonly shallowReactive: UnwrapRef<typeof import('vue')['shallowReactive']>
     readonly shallowReadonly: UnwrapRef<typeof import('vue')['shallowReadonly']>
     readonly shallowRef: UnwrapRef<typeof import('vue')['shallowRef']>
     readonly startExport: UnwrapRef<typeof import('./lib/api')['startExport']>
+    readonly startExportQueue: UnwrapRef<typeof import('./lib/api')['startExportQueue']>
+    readonly startExportWithProfile: UnwrapRef<typeof import('./lib/api')['startExportWithProfile']>
+    readonly stopExportQueue: UnwrapRef<typeof import('./lib/api')['stopExportQueue']>
     readonly storeToRefs: UnwrapRef<typeof import('pinia')['storeToRefs']>
     readonly toNativeFilePath: UnwrapRef<typeof import('./lib/image')['toNativeFilePath']>
     readonly toRaw: UnwrapRef<typeof import('vue')['toRaw']>
@@ -207,6 +252,7 @@ declare module 'vue' {
     readonly triggerRef: UnwrapRef<typeof import('vue')['triggerRef']>
     readonly unref: UnwrapRef<typeof import('vue')['unref']>
     readonly updateAppSettings: UnwrapRef<typeof import('./lib/api')['updateAppSettings']>
+    readonly updateSessionMetadata: UnwrapRef<typeof import('./lib/api')['updateSessionMetadata']>
     readonly useAttrs: UnwrapRef<typeof import('vue')['useAttrs']>
     readonly useCssModule: UnwrapRef<typeof import('vue')['useCssModule']>
     readonly useCssVars: UnwrapRef<typeof import('vue')['useCssVars']>
@@ -214,6 +260,7 @@ declare module 'vue' {
     readonly useId: UnwrapRef<typeof import('vue')['useId']>
     readonly useLink: UnwrapRef<typeof import('vue-router')['useLink']>
     readonly useModel: UnwrapRef<typeof import('vue')['useModel']>
+    readonly useQueueStore: UnwrapRef<typeof import('./stores/queue')['useQueueStore']>
     readonly useRoute: UnwrapRef<typeof import('vue-router')['useRoute']>
     readonly useRouter: UnwrapRef<typeof import('vue-router')['useRouter']>
     readonly useSessionsStore: UnwrapRef<typeof import('./stores/sessions')['useSessionsStore']>
@@ -222,6 +269,7 @@ declare module 'vue' {
     readonly useTemplateRef: UnwrapRef<typeof import('vue')['useTemplateRef']>
     readonly useUpdateStore: UnwrapRef<typeof import('./stores/update')['useUpdateStore']>
     readonly useYupForm: UnwrapRef<typeof import('./composables/use-yup-form')['useYupForm']>
+    readonly verifySession: UnwrapRef<typeof import('./lib/api')['verifySession']>
     readonly watch: UnwrapRef<typeof import('vue')['watch']>
     readonly watchEffect: UnwrapRef<typeof import('vue')['watchEffect']>
     readonly watchPostEffect: UnwrapRef<typeof import('vue')['watchPostEffect']>
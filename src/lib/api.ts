@@ -1,8 +1,25 @@
 import { invoke } from "@tauri-apps/api/core";
 import type {
+  AppDataImportReport,
+  AppDataManifest,
   AppSettings,
+  DuplicateReport,
+  ExportDiffReport,
+  ExportProfile,
+  ExportQueueStatus,
+  ExportStatistics,
   FileEntry,
+  NoteSearchFilters,
+  NoteSearchResult,
+  QueuedExport,
+  ReindexReport,
+  RelocationReport,
+  RetentionReport,
   Session,
+  SessionFilters,
+  SessionMetadata,
+  SessionPage,
+  SessionVerification,
   StartExportPayload,
 } from "@/types";
 
@@ -17,15 +34,69 @@ export async function startExport(
     timestampFormat: payload.timestampFormat,
     createdDateFormat: payload.createdDateFormat,
     exportImages: payload.exportImages,
+    skipDuplicates: payload.skipDuplicates ?? false,
+    unparsedDatePolicy: payload.unparsedDatePolicy ?? "useNow",
+    source: payload.source ?? "api",
+    filter: payload.filter ?? null,
+    dryRun: payload.dryRun ?? false,
   });
 }
 
+export async function startExportWithProfile(profileId: string): Promise<string> {
+  return invoke<string>("start_export_with_profile", { profileId });
+}
+
 export async function cancelExport(): Promise<void> {
   return invoke<void>("cancel_export");
 }
 
-export async function getSessions(page = 1, perPage = 50): Promise<Session[]> {
-  return invoke<Session[]>("get_sessions", { page, perPage });
+export async function pauseExport(): Promise<void> {
+  return invoke<void>("pause_export");
+}
+
+export async function resumeExportRun(): Promise<void> {
+  return invoke<void>("resume_export_run");
+}
+
+export async function getExportQueue(): Promise<ExportQueueStatus> {
+  return invoke<ExportQueueStatus>("get_export_queue");
+}
+
+export async function enqueueExport(
+  settings: StartExportPayload,
+): Promise<QueuedExport> {
+  return invoke<QueuedExport>("enqueue_export", { settings });
+}
+
+export async function removeQueuedExport(jobId: string): Promise<ExportQueueStatus> {
+  return invoke<ExportQueueStatus>("remove_queued_export", { jobId });
+}
+
+export async function moveQueuedExport(
+  jobId: string,
+  position: number,
+): Promise<ExportQueueStatus> {
+  return invoke<ExportQueueStatus>("move_queued_export", { jobId, position });
+}
+
+export async function startExportQueue(): Promise<ExportQueueStatus> {
+  return invoke<ExportQueueStatus>("start_export_queue");
+}
+
+export async function stopExportQueue(): Promise<ExportQueueStatus> {
+  return invoke<ExportQueueStatus>("stop_export_queue");
+}
+
+export async function getSessions(
+  page = 1,
+  perPage = 50,
+  filters?: SessionFilters,
+): Promise<SessionPage> {
+  return invoke<SessionPage>("get_sessions", {
+    page,
+    perPage,
+    filters: filters ?? null,
+  });
 }
 
 export async function getSession(id: string): Promise<Session | null> {
@@ -39,6 +110,44 @@ export async function deleteSession(
   return invoke<void>("delete_session", { id, deleteFiles });
 }
 
+export async function setSessionPinned(
+  id: string,
+  pinned: boolean,
+): Promise<Session | null> {
+  return invoke<Session | null>("set_session_pinned", { id, pinned });
+}
+
+export async function updateSessionMetadata(
+  id: string,
+  metadata: SessionMetadata,
+): Promise<Session | null> {
+  return invoke<Session | null>("update_session_metadata", { id, metadata });
+}
+
+export async function applyRetentionPolicy(
+  dryRun: boolean,
+): Promise<RetentionReport> {
+  return invoke<RetentionReport>("apply_retention_policy", { dryRun });
+}
+
+export async function relocateSessions(
+  oldPrefix: string,
+  newPrefix: string,
+  moveFiles: boolean,
+): Promise<RelocationReport> {
+  return invoke<RelocationReport>("relocate_sessions", {
+    oldPrefix,
+    newPrefix,
+    moveFiles,
+  });
+}
+
+export async function verifySession(
+  id: string,
+): Promise<SessionVerification | null> {
+  return invoke<SessionVerification | null>("verify_session", { id });
+}
+
 export async function readExportFile(path: string): Promise<string> {
   return invoke<string>("read_export_file", { path });
 }
@@ -64,6 +173,46 @@ export async function convertToJson(
   });
 }
 
+export async function getExportStatistics(): Promise<ExportStatistics> {
+  return invoke<ExportStatistics>("get_export_statistics");
+}
+
+export async function diffExports(
+  left: string,
+  right: string,
+  summaryPath?: string,
+): Promise<ExportDiffReport> {
+  return invoke<ExportDiffReport>("diff_exports", {
+    left,
+    right,
+    summaryPath: summaryPath ?? null,
+  });
+}
+
+export async function findDuplicateNotes(
+  source: string,
+  threshold?: number,
+): Promise<DuplicateReport> {
+  return invoke<DuplicateReport>("find_duplicate_notes", {
+    source,
+    threshold: threshold ?? null,
+  });
+}
+
+export async function searchNotes(
+  query: string,
+  filters?: NoteSearchFilters,
+): Promise<NoteSearchResult[]> {
+  return invoke<NoteSearchResult[]>("search_notes", {
+    query,
+    filters: filters ?? null,
+  });
+}
+
+export async function reindexNotes(source: string): Promise<ReindexReport> {
+  return invoke<ReindexReport>("reindex_notes", { source });
+}
+
 export async function openInExplorer(path: string): Promise<void> {
   return invoke<void>("open_in_explorer", { path });
 }
@@ -80,6 +229,36 @@ export async function updateAppSettings(
   });
 }
 
+export async function saveExportProfile(
+  profile: ExportProfile,
+): Promise<ExportProfile> {
+  return invoke<ExportProfile>("save_export_profile", { profile });
+}
+
+export async function deleteExportProfile(profileId: string): Promise<AppSettings> {
+  return invoke<AppSettings>("delete_export_profile", { profileId });
+}
+
+export async function exportAppData(
+  path: string,
+  includeSearchIndex: boolean,
+): Promise<AppDataManifest> {
+  return invoke<AppDataManifest>("export_app_data", {
+    path,
+    includeSearchIndex,
+  });
+}
+
+export async function importAppData(
+  path: string,
+  exportRoot?: string,
+): Promise<AppDataImportReport> {
+  return invoke<AppDataImportReport>("import_app_data", {
+    path,
+    exportRoot: exportRoot ?? null,
+  });
+}
+
 export async function checkLatestReleaseVersion(): Promise<string> {
   return invoke<string>("check_latest_release_version");
 }
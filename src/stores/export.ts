@@ -1,23 +1,28 @@
 import { listen, type UnlistenFn } from "@tauri-apps/api/event";
 import type {
   ExportCompleteEvent,
+  ExportDryRunReport,
   ExportErrorEvent,
+  ExportProfile,
   ExportProgressEvent,
   StartExportPayload,
 } from "@/types";
 
 interface ExportState {
   isRunning: boolean;
+  isPaused: boolean;
   activeSessionId: string | null;
   current: number;
   total: number;
   notesCount: number;
   imagesCount: number;
+  filteredCount: number;
   startedAtMs: number | null;
   elapsedMs: number;
   outputPath: string;
   statusText: string;
   logs: string[];
+  dryRunReport: ExportDryRunReport | null;
 }
 
 let unlisteners: UnlistenFn[] = [];
@@ -26,16 +31,19 @@ let listenersReady = false;
 export const useExportStore = defineStore("export", {
   state: (): ExportState => ({
     isRunning: false,
+    isPaused: false,
     activeSessionId: null,
     current: 0,
     total: 0,
     notesCount: 0,
     imagesCount: 0,
+    filteredCount: 0,
     startedAtMs: null,
     elapsedMs: 0,
     outputPath: "",
     statusText: "Idle",
     logs: [],
+    dryRunReport: null,
   }),
   getters: {
     progressPercent(state): number {
@@ -53,13 +61,23 @@ export const useExportStore = defineStore("export", {
 
       const progressUnlisten = await listen<ExportProgressEvent>("export:progress", (event) => {
         const payload = event.payload;
+        if (payload.sessionId !== this.activeSessionId && !this.isRunning) {
+          // A queued export started without going through `start`.
+          this.startedAtMs = Date.now();
+          this.dryRunReport = null;
+          this.isPaused = false;
+          this.logs = [];
+        }
         this.isRunning = true;
         this.activeSessionId = payload.sessionId;
         this.current = payload.current;
         this.total = payload.total;
         this.notesCount = payload.notesCount;
         this.imagesCount = payload.imagesCount;
-        this.statusText = `Exporting ${payload.current}/${payload.total}`;
+        this.filteredCount = payload.filteredCount;
+        this.statusText = this.isPaused
+          ? `Paused at ${payload.current}/${payload.total}`
+          : `Exporting ${payload.current}/${payload.total}`;
         this.logs = [payload.logLine, ...this.logs].slice(0, 100);
         if (this.startedAtMs) {
           this.elapsedMs = Date.now() - this.startedAtMs;
@@ -69,15 +87,23 @@ export const useExportStore = defineStore("export", {
       const completeUnlisten = await listen<ExportCompleteEvent>("export:complete", (event) => {
         const payload = event.payload;
         this.isRunning = false;
-        this.statusText = "Completed";
+        this.isPaused = false;
         this.outputPath = payload.outputPath;
         this.elapsedMs = payload.elapsedMs;
+        this.dryRunReport = payload.dryRun;
+        if (payload.dryRun) {
+          this.statusText = "Dry run completed";
+          this.logs = [`Dry run completed (${payload.total} notes planned).`, ...this.logs].slice(0, 100);
+          return;
+        }
+        this.statusText = "Completed";
         this.logs = [`Export completed (${payload.total} notes).`, ...this.logs].slice(0, 100);
       });
 
       const errorUnlisten = await listen<ExportErrorEvent>("export:error", (event) => {
         const payload = event.payload;
         this.isRunning = false;
+        this.isPaused = false;
         this.statusText = "Error";
         this.logs = [`Error: ${payload.message}`, ...this.logs].slice(0, 100);
       });
@@ -85,24 +111,52 @@ export const useExportStore = defineStore("export", {
       unlisteners = [progressUnlisten, completeUnlisten, errorUnlisten];
       listenersReady = true;
     },
-    async start(payload: StartExportPayload) {
+    reset(outputDir: string) {
       this.startedAtMs = Date.now();
       this.elapsedMs = 0;
       this.current = 0;
       this.total = 0;
       this.notesCount = 0;
       this.imagesCount = 0;
-      this.outputPath = payload.outputDir;
+      this.filteredCount = 0;
+      this.dryRunReport = null;
+      this.isPaused = false;
+      this.outputPath = outputDir;
       this.statusText = "Starting...";
       this.logs = ["Starting export..."];
+    },
+    async start(payload: StartExportPayload) {
+      this.reset(payload.outputDir);
       this.activeSessionId = await startExport(payload);
       this.isRunning = true;
     },
+    async startWithProfile(profile: ExportProfile) {
+      this.reset(profile.settings.outputDir);
+      this.activeSessionId = await startExportWithProfile(profile.id);
+      this.isRunning = true;
+    },
+    async pause() {
+      this.isPaused = true;
+      try {
+        await pauseExport();
+      } catch (error) {
+        this.isPaused = false;
+        throw error;
+      }
+    },
+    async resume() {
+      await resumeExportRun();
+      this.isPaused = false;
+      this.statusText = `Exporting ${this.current}/${this.total}`;
+    },
     async cancel() {
       await cancelExport();
       this.isRunning = false;
+      this.isPaused = false;
       this.statusText = "Cancelled";
       this.logs = ["Export cancelled by user.", ...this.logs].slice(0, 100);
+      // Cancelling also stops the export queue.
+      await useQueueStore().refresh();
     },
     disposeListeners() {
       for (const unlisten of unlisteners) {
@@ -0,0 +1,70 @@
+import { listen, type UnlistenFn } from "@tauri-apps/api/event";
+import type { ExportQueueStatus, QueuedExport, StartExportPayload } from "@/types";
+
+interface QueueState {
+  running: boolean;
+  jobs: QueuedExport[];
+  error: string;
+}
+
+let unlisteners: UnlistenFn[] = [];
+let listenersReady = false;
+
+export const useQueueStore = defineStore("queue", {
+  state: (): QueueState => ({
+    running: false,
+    jobs: [],
+    error: "",
+  }),
+  actions: {
+    apply(status: ExportQueueStatus) {
+      this.running = status.running;
+      this.jobs = status.jobs;
+    },
+    async initializeListeners() {
+      if (listenersReady) {
+        return;
+      }
+
+      // Finished exports advance the queue in the backend; pick up the new state.
+      const refreshOnEnd = () => void this.refresh();
+      unlisteners = [
+        await listen("export:complete", refreshOnEnd),
+        await listen("export:error", refreshOnEnd),
+      ];
+      listenersReady = true;
+      await this.refresh();
+    },
+    async refresh() {
+      this.error = "";
+      try {
+        this.apply(await getExportQueue());
+      } catch (error) {
+        this.error = error instanceof Error ? error.message : "Failed to load export queue.";
+      }
+    },
+    async enqueue(payload: StartExportPayload) {
+      await enqueueExport(payload);
+      await this.refresh();
+    },
+    async remove(jobId: string) {
+      this.apply(await removeQueuedExport(jobId));
+    },
+    async move(jobId: string, position: number) {
+      this.apply(await moveQueuedExport(jobId, Math.max(0, position)));
+    },
+    async start() {
+      this.apply(await startExportQueue());
+    },
+    async stop() {
+      this.apply(await stopExportQueue());
+    },
+    disposeListeners() {
+      for (const unlisten of unlisteners) {
+        unlisten();
+      }
+      unlisteners = [];
+      listenersReady = false;
+    },
+  },
+});
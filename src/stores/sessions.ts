@@ -1,7 +1,13 @@
-import type { Session } from "@/types";
+import type {
+  RelocationReport,
+  RetentionReport,
+  Session,
+  SessionMetadata,
+} from "@/types";
 
 interface SessionsState {
   items: Session[];
+  total: number;
   loading: boolean;
   error: string;
 }
@@ -9,6 +15,7 @@ interface SessionsState {
 export const useSessionsStore = defineStore("sessions", {
   state: (): SessionsState => ({
     items: [],
+    total: 0,
     loading: false,
     error: "",
   }),
@@ -17,7 +24,9 @@ export const useSessionsStore = defineStore("sessions", {
       this.loading = true;
       this.error = "";
       try {
-        this.items = await getSessions(1, 200);
+        const result = await getSessions(1, 200);
+        this.items = result.sessions;
+        this.total = result.total;
       } catch (error) {
         this.error = error instanceof Error ? error.message : "Failed to load sessions.";
       } finally {
@@ -27,9 +36,51 @@ export const useSessionsStore = defineStore("sessions", {
     async findById(id: string): Promise<Session | null> {
       return getSession(id);
     },
+    async setPinned(id: string, pinned: boolean) {
+      const updated = await setSessionPinned(id, pinned);
+      if (updated) {
+        this.items = this.items.map((session) =>
+          session.id === id ? updated : session,
+        );
+      }
+    },
+    async updateMetadata(id: string, metadata: SessionMetadata) {
+      const updated = await updateSessionMetadata(id, metadata);
+      if (updated) {
+        this.items = this.items.map((session) =>
+          session.id === id ? updated : session,
+        );
+      }
+    },
+    async applyRetention(dryRun: boolean): Promise<RetentionReport> {
+      const report = await applyRetentionPolicy(dryRun);
+      if (!dryRun) {
+        await this.refresh();
+      }
+      return report;
+    },
+    async relocate(
+      oldPrefix: string,
+      newPrefix: string,
+      moveFiles: boolean,
+    ): Promise<RelocationReport> {
+      const report = await relocateSessions(oldPrefix, newPrefix, moveFiles);
+      await this.refresh();
+      return report;
+    },
+    async verify(id: string) {
+      const verification = await verifySession(id);
+      if (verification) {
+        this.items = this.items.map((session) =>
+          session.id === id ? verification.session : session,
+        );
+      }
+      return verification;
+    },
     async remove(id: string, deleteFiles: boolean) {
       await deleteSession(id, deleteFiles);
       this.items = this.items.filter((session) => session.id !== id);
+      this.total = Math.max(0, this.total - 1);
     },
   },
 });
@@ -1,8 +1,16 @@
-import type { AppSettings } from "@/types";
+import type { AppSettings, ExportProfile } from "@/types";
 
 const FALLBACK_SETTINGS: AppSettings = {
   defaultExportDir: "",
   theme: "system",
+  notesLocale: "en-US",
+  outputTimezone: "local",
+  retention: {
+    keepLast: null,
+    maxAgeDays: null,
+  },
+  relativeSessionPaths: false,
+  exportProfiles: [],
 };
 
 interface SettingsState {
@@ -76,5 +84,19 @@ export const useSettingsStore = defineStore("settings", {
         this.loading = false;
       }
     },
+    async saveProfile(profile: ExportProfile) {
+      const saved = await saveExportProfile(profile);
+      const profiles = this.current.exportProfiles;
+      this.current = {
+        ...this.current,
+        exportProfiles: profiles.some((existing) => existing.id === saved.id)
+          ? profiles.map((existing) => (existing.id === saved.id ? saved : existing))
+          : [...profiles, saved],
+      };
+      return saved;
+    },
+    async deleteProfile(profileId: string) {
+      this.current = await deleteExportProfile(profileId);
+    },
   },
 });
@@ -1,4 +1,27 @@
-export type SessionStatus = "running" | "completed" | "cancelled" | "error";
+export type SessionStatus =
+  | "running"
+  | "paused"
+  | "completed"
+  | "cancelled"
+  | "error"
+  | "interrupted"
+  | "missing";
+
+export type UnparsedDatePolicy = "fail" | "useNow" | "useEpoch" | "leaveBlank";
+
+export type ExportSource = "api" | "dom";
+
+export type UnsupportedNotesFilter = "include" | "exclude" | "only";
+
+export interface ExportFilterSpec {
+  createdAfter?: string | null;
+  createdBefore?: string | null;
+  titleRegex?: string | null;
+  bodyRegex?: string | null;
+  unsupported?: UnsupportedNotesFilter;
+  minLength?: number | null;
+  folders?: string[];
+}
 
 export interface Session {
   id: string;
@@ -15,6 +38,59 @@ export interface Session {
   outputPath: string;
   imagesDirName: string | null;
   errorMessage: string | null;
+  unparsedDates: number;
+  pinned: boolean;
+  label: string | null;
+  comment: string | null;
+  accountHint: string | null;
+  folders: string[];
+  filteredCount: number;
+}
+
+export interface SessionMetadata {
+  label: string | null;
+  comment: string | null;
+  accountHint: string | null;
+  pinned?: boolean | null;
+}
+
+export interface SessionVerification {
+  session: Session;
+  previousStatus: SessionStatus;
+  outputExists: boolean;
+  notesOnDisk: number;
+  imagesOnDisk: number;
+  changed: boolean;
+}
+
+export type SessionSortKey =
+  | "startedAt"
+  | "completedAt"
+  | "status"
+  | "domain"
+  | "notesCount"
+  | "imagesCount";
+
+export interface SessionFilters {
+  status?: SessionStatus;
+  domain?: string;
+  startedAfter?: string;
+  startedBefore?: string;
+  splitMode?: boolean;
+  imagesEnabled?: boolean;
+  search?: string;
+  label?: string;
+  accountHint?: string;
+  pinned?: boolean;
+  sortBy?: SessionSortKey;
+  sortDirection?: "asc" | "desc";
+}
+
+export interface SessionPage {
+  sessions: Session[];
+  total: number;
+  page: number;
+  perPage: number;
 }
 
 export interface FileEntry {
@@ -30,6 +106,28 @@ export interface StartExportPayload {
   timestampFormat: string;
   createdDateFormat: string;
   exportImages: boolean;
+  skipDuplicates?: boolean;
+  unparsedDatePolicy?: UnparsedDatePolicy;
+  source?: ExportSource;
+  filter?: ExportFilterSpec;
+  dryRun?: boolean;
+}
+
+export interface ExportProfile {
+  id: string;
+  name: string;
+  settings: StartExportPayload;
+}
+
+export interface QueuedExport {
+  id: string;
+  enqueuedAt: string;
+  settings: StartExportPayload;
+}
+
+export interface ExportQueueStatus {
+  running: boolean;
+  jobs: QueuedExport[];
 }
 
 export interface ExportProgressEvent {
@@ -39,14 +137,34 @@ export interface ExportProgressEvent {
   lastTitle: string;
   notesCount: number;
   imagesCount: number;
+  filteredCount: number;
   logLine: string;
 }
 
+export interface DryRunCollision {
+  name: string;
+  files: string[];
+}
+
+export interface ExportDryRunReport {
+  notesCount: number;
+  imagesCount: number;
+  imageBytes: number;
+  markdownBytes: number;
+  duplicatesSkipped: number;
+  filteredCount: number;
+  files: string[];
+  collisions: DryRunCollision[];
+  unsupportedNotes: string[];
+  unparsedDates: { title: string; created: string }[];
+}
+
 export interface ExportCompleteEvent {
   sessionId: string;
   total: number;
   elapsedMs: number;
   outputPath: string;
+  dryRun: ExportDryRunReport | null;
 }
 
 export interface ExportErrorEvent {
@@ -59,9 +177,147 @@ export interface NoteDto {
   content: string;
   creationDate: string;
   lastModified: string;
+  folder: string | null;
+}
+
+export interface NoteSummary {
+  id: string;
+  creationDate: string;
+  preview: string;
+}
+
+export interface ModifiedNoteEntry {
+  leftId: string;
+  rightId: string;
+  creationDate: string;
+  preview: string;
+  diff: string;
+}
+
+export interface ExportDiffReport {
+  leftSource: string;
+  rightSource: string;
+  leftCount: number;
+  rightCount: number;
+  unchangedCount: number;
+  added: NoteSummary[];
+  removed: NoteSummary[];
+  modified: ModifiedNoteEntry[];
+}
+
+export interface DuplicateCluster {
+  similarity: number;
+  notes: NoteSummary[];
+}
+
+export interface DuplicateReport {
+  source: string;
+  notesCount: number;
+  exactClusters: DuplicateCluster[];
+  nearClusters: DuplicateCluster[];
+}
+
+export interface SessionStatistics {
+  sessionId: string;
+  startedAt: string;
+  status: SessionStatus;
+  notesCount: number;
+  imagesCount: number;
+  durationMs: number | null;
+  notesPerMinute: number | null;
+  outputExists: boolean;
+  diskUsageBytes: number;
+}
+
+export interface StatusCount {
+  status: SessionStatus;
+  count: number;
+  rate: number;
+}
+
+export interface ErrorFrequency {
+  message: string;
+  count: number;
+}
+
+export interface ExportStatistics {
+  totalSessions: number;
+  totalNotes: number;
+  totalImages: number;
+  averageNotesPerSession: number;
+  averageImagesPerSession: number;
+  averageNotesPerMinute: number | null;
+  failureRate: number;
+  recentFailureRate: number;
+  statusCounts: StatusCount[];
+  commonErrors: ErrorFrequency[];
+  totalDiskUsageBytes: number;
+  sessions: SessionStatistics[];
+}
+
+export interface NoteSearchFilters {
+  sessionId?: string;
+  createdAfter?: string;
+  createdBefore?: string;
+  limit?: number;
+}
+
+export interface NoteSearchResult {
+  sessionId: string | null;
+  filePath: string;
+  title: string;
+  creationDate: string;
+  snippet: string;
+  rank: number;
+}
+
+export interface ReindexReport {
+  source: string;
+  sessionId: string | null;
+  filesCount: number;
+  notesCount: number;
+}
+
+export interface RetentionPolicy {
+  keepLast: number | null;
+  maxAgeDays: number | null;
+}
+
+export interface RetentionReport {
+  dryRun: boolean;
+  sessions: Session[];
+  freedBytes: number;
+  failures: string[];
+}
+
+export interface RelocationReport {
+  relocatedSessions: number;
+  movedFiles: boolean;
+  verifiedSessions: number;
+  missingSessions: string[];
+}
+
+export interface AppDataManifest {
+  formatVersion: number;
+  appVersion: string;
+  schemaVersion: number;
+  createdAt: string;
+  exportRoot: string;
+  includesSearchIndex: boolean;
+}
+
+export interface AppDataImportReport {
+  manifest: AppDataManifest;
+  sessionsCount: number;
+  relocatedSessions: number;
 }
 
 export interface AppSettings {
   defaultExportDir: string;
   theme: "system" | "light" | "dark";
+  notesLocale: string;
+  outputTimezone: string;
+  retention: RetentionPolicy;
+  relativeSessionPaths: boolean;
+  exportProfiles: ExportProfile[];
 }